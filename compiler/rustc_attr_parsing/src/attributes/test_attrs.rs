@@ -3,6 +3,7 @@
 use rustc_session::lint::builtin::ILL_FORMED_ATTRIBUTE_INPUT;
 
 use super::prelude::*;
+use crate::session_diagnostics;
 
 pub(crate) struct IgnoreParser;
 
@@ -190,6 +191,47 @@ fn convert(cx: &mut AcceptContext<'_, '_>, args: &ArgParser) -> Option<Attribute
     }
 }
 
+pub(crate) struct TestTimeoutParser;
+
+impl SingleAttributeParser for TestTimeoutParser {
+    const PATH: &[Symbol] = &[sym::test_timeout];
+    const ALLOWED_TARGETS: AllowedTargets =
+        AllowedTargets::AllowListWarnRest(&[Allow(Target::Fn), Error(Target::WherePredicate)]);
+    const TEMPLATE: AttributeTemplate = template!(NameValueStr: "duration");
+    const STABILITY: AttributeStability = unstable!(test_timeout);
+
+    fn convert(cx: &mut AcceptContext<'_, '_>, args: &ArgParser) -> Option<AttributeKind> {
+        let nv = cx.expect_name_value(
+            args,
+            args.span().unwrap_or(cx.inner_span),
+            Some(sym::test_timeout),
+        )?;
+        let value = cx.expect_string_literal(nv)?;
+
+        let Some(millis) = parse_timeout_millis(value.as_str()) else {
+            cx.emit_err(session_diagnostics::InvalidTestTimeout { span: nv.value_span, value });
+            return None;
+        };
+
+        Some(AttributeKind::TestTimeout { millis, span: cx.attr_span })
+    }
+}
+
+/// Parses a duration of the form `<number>[ms|s|m]` into milliseconds, like
+/// libtest does for `--test-timeout`. Zero is rejected.
+fn parse_timeout_millis(s: &str) -> Option<u64> {
+    let digits_end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(digits_end);
+    let value = value.parse::<u64>().ok()?;
+    let millis = match unit {
+        "ms" => value,
+        "" | "s" => value.checked_mul(1000)?,
+        "m" => value.checked_mul(60 * 1000)?,
+        _ => return None,
+    };
+    (millis != 0).then_some(millis)
+}
+
 pub(crate) struct RustcTestMarkerParser;
 
 impl SingleAttributeParser for RustcTestMarkerParser {
@@ -233,6 +233,7 @@ macro_rules! attribute_parsers {
         Single<SanitizeParser>,
         Single<ShouldPanicParser>,
         Single<TestRunnerParser>,
+        Single<TestTimeoutParser>,
         Single<TypeLengthLimitParser>,
         Single<UnrollParser>,
         Single<WindowsSubsystemParser>,
@@ -207,6 +207,15 @@ pub(crate) struct ExpectedSingleVersionLiteral {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag("invalid test timeout `{$value}`")]
+#[help("expected a positive duration such as `500ms`, `30s` or `2m`")]
+pub(crate) struct InvalidTestTimeout {
+    #[primary_span]
+    pub span: Span,
+    pub value: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag("expected a version literal")]
 pub(crate) struct ExpectedVersionLiteral {
@@ -266,7 +266,7 @@ pub(crate) fn expand_test_or_bench(
         &fn_.ident,
     ));
 
-    let location_info = get_location_info(cx, &fn_);
+    let location_info = get_location_info(cx, &item, &fn_);
 
     let mut test_const =
         cx.item(
@@ -327,6 +327,8 @@ pub(crate) fn expand_test_or_bench(
                                 field("end_line", cx.expr_usize(sp, location_info.3)),
                                 // end_col: end column of the test fn identifier.
                                 field("end_col", cx.expr_usize(sp, location_info.4)),
+                                // body_end_line: end line of the test fn body.
+                                field("body_end_line", cx.expr_usize(sp, location_info.5)),
                                 // compile_fail: true | false
                                 field("compile_fail", cx.expr_bool(sp, false)),
                                 // no_run: true | false
@@ -363,6 +365,26 @@ pub(crate) fn expand_test_or_bench(
                                         cx.expr_path(test_type_path("Unknown"))
                                     }
                                 },),
+                                // timeout: Some(Duration::from_millis(...)) | None
+                                field(
+                                    "timeout",
+                                    if let Some(millis) = test_timeout(cx, &item) {
+                                        cx.expr_some(
+                                            sp,
+                                            cx.expr_call_global(
+                                                sp,
+                                                cx.std_path(&[
+                                                    sym::time,
+                                                    sym::Duration,
+                                                    sym::from_millis,
+                                                ]),
+                                                thin_vec![cx.expr_u64(sp, millis)],
+                                            ),
+                                        )
+                                    } else {
+                                        cx.expr_none(sp)
+                                    },
+                                ),
                                 // },
                             ],),
                         ),
@@ -438,17 +460,22 @@ fn not_testable_error(cx: &ExtCtxt<'_>, is_bench: bool, attr_sp: Span, item: Opt
     }
 }
 
-fn get_location_info(cx: &ExtCtxt<'_>, fn_: &ast::Fn) -> (Symbol, usize, usize, usize, usize) {
+fn get_location_info(
+    cx: &ExtCtxt<'_>,
+    item: &ast::Item,
+    fn_: &ast::Fn,
+) -> (Symbol, usize, usize, usize, usize, usize) {
     let span = fn_.ident.span;
     let (source_file, lo_line, lo_col, hi_line, hi_col) =
         cx.sess.source_map().span_to_location_info(span);
+    let (_, _, _, body_hi_line, _) = cx.sess.source_map().span_to_location_info(item.span);
 
     let file_name = match source_file {
         Some(sf) => sf.name.display(RemapPathScopeComponents::MACRO).to_string(),
         None => "no-location".to_string(),
     };
 
-    (Symbol::intern(&file_name), lo_line, lo_col, hi_line, hi_col)
+    (Symbol::intern(&file_name), lo_line, lo_col, hi_line, hi_col, body_hi_line)
 }
 
 fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
@@ -488,6 +515,16 @@ fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     }
 }
 
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    if let Some(Attribute::Parsed(AttributeKind::TestTimeout { millis, .. })) =
+        AttributeParser::parse_limited(cx.sess, &i.attrs, &[sym::test_timeout])
+    {
+        Some(millis)
+    } else {
+        None
+    }
+}
+
 enum TestType {
     UnitTest,
     IntegrationTest,
@@ -172,6 +172,7 @@ pub enum AttributeStability {
 
     // Testing:
     sym::test_runner,
+    sym::test_timeout,
 
     sym::reexport_test_harness_main,
 
@@ -1638,6 +1638,13 @@ pub enum AttributeKind {
     /// Represents `#![test_runner(path)]`
     TestRunner(Path),
 
+    /// Represents `#[test_timeout = "..."]`
+    TestTimeout {
+        /// The time limit, in milliseconds.
+        millis: u64,
+        span: Span,
+    },
+
     /// Represents `#[thread_local]`
     ThreadLocal,
 
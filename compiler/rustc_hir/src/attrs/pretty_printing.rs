@@ -192,7 +192,7 @@ fn print_attribute(&self, p: &mut Printer) {
 
 print_tup!(A B C D E F G H);
 print_skip!(Span, (), ErrorGuaranteed, AttrId);
-print_disp!(u8, u16, u32, u128, usize, bool, NonZero<u32>, Limit);
+print_disp!(u8, u16, u32, u64, u128, usize, bool, NonZero<u32>, Limit);
 print_debug!(
     Symbol,
     Ident,
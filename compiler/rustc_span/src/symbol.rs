@@ -208,6 +208,7 @@
         DualvOnly,
         Duplicated,
         DuplicatedOnly,
+        Duration,
         DynTrait,
         Enum,
         Eq,
@@ -1016,6 +1017,7 @@
         from,
         from_desugaring,
         from_iter,
+        from_millis,
         from_output,
         from_residual,
         from_size_align_unchecked,
@@ -2089,6 +2091,7 @@
         test_incomplete_feature,
         test_removed_feature,
         test_runner,
+        test_timeout,
         test_unstable_lint,
         thread,
         thread_local,
@@ -0,0 +1,477 @@
+use core::{fmt, mem};
+
+use super::{Positions, sift_down_range, sift_down_to_bottom, sift_up};
+use crate::vec::Vec;
+
+/// A priority queue implemented with a binary heap, whose elements can be
+/// changed or removed after they were pushed.
+///
+/// Like [`BinaryHeap`], this is a max-heap. Additionally, [`push`] returns a
+/// [`HeapHandle`] that refers to the pushed element for as long as it is in
+/// the heap. The handle can be used to look at the element, to
+/// [change its priority](IndexedBinaryHeap::change_priority) or to
+/// [remove](IndexedBinaryHeap::remove) it, all in *O*(log(*n*)) time.
+///
+/// This makes `IndexedBinaryHeap` suitable for algorithms that need to
+/// update the priority of queued elements, such as Dijkstra's algorithm or
+/// schedulers, without pushing duplicate elements and skipping outdated ones
+/// when they are popped.
+///
+/// Handles of elements that have been popped or removed are never reused for
+/// other elements. Using a handle with a heap other than the one that
+/// returned it is a logic error, see [`BinaryHeap`] for what that entails.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(binary_heap_indexed)]
+///
+/// use std::cmp::Reverse;
+/// use std::collections::binary_heap::IndexedBinaryHeap;
+///
+/// // Dijkstra's algorithm on a small directed graph, given as
+/// // `(from, to, cost)` edges.
+/// let edges = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+///
+/// let mut dist = [u32::MAX; 4];
+/// let mut handles = [None; 4];
+/// let mut queue = IndexedBinaryHeap::new();
+/// dist[0] = 0;
+/// handles[0] = Some(queue.push((Reverse(0), 0)));
+///
+/// while let Some((Reverse(cost), node)) = queue.pop() {
+///     for &(from, to, edge_cost) in &edges {
+///         let next = cost + edge_cost;
+///         if from != node || next >= dist[to] {
+///             continue;
+///         }
+///         dist[to] = next;
+///         match handles[to] {
+///             // The node is still queued with a higher cost, decrease it.
+///             Some(handle) if queue.contains(handle) => {
+///                 queue.change_priority(handle, (Reverse(next), to));
+///             }
+///             _ => handles[to] = Some(queue.push((Reverse(next), to))),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(dist, [0, 3, 1, 4]);
+/// ```
+///
+/// [`BinaryHeap`]: super::BinaryHeap
+/// [`push`]: IndexedBinaryHeap::push
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+pub struct IndexedBinaryHeap<T> {
+    data: Vec<Entry<T>>,
+    slots: Slots,
+}
+
+/// A handle to an element of an [`IndexedBinaryHeap`].
+///
+/// This `struct` is returned by [`IndexedBinaryHeap::push`]. See its
+/// documentation for more.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+pub struct HeapHandle {
+    slot: usize,
+    generation: usize,
+}
+
+/// An element of the heap, together with the index of the slot that stores
+/// its position.
+#[derive(Clone)]
+struct Entry<T> {
+    item: T,
+    slot: usize,
+}
+
+// Entries are ordered by their items only, so that the sift functions of
+// `BinaryHeap` can be used on them.
+impl<T: PartialEq> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item
+    }
+}
+
+impl<T: Eq> Eq for Entry<T> {}
+
+impl<T: PartialOrd> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.item.partial_cmp(&other.item)
+    }
+}
+
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.item.cmp(&other.item)
+    }
+}
+
+/// The position of the element of each handle, indexed by `HeapHandle::slot`.
+#[derive(Clone)]
+struct Slots {
+    slots: Vec<Slot>,
+    // Indices of the slots that are not used by any element.
+    free: Vec<usize>,
+}
+
+#[derive(Clone)]
+struct Slot {
+    // The index of the element in `data`, if the slot is used.
+    pos: usize,
+    // Incremented whenever the element of the slot is popped or removed, so
+    // that handles to it are no longer accepted.
+    generation: usize,
+}
+
+impl<T> Positions<Entry<T>> for Slots {
+    #[inline]
+    fn moved(&mut self, entry: &Entry<T>, pos: usize) {
+        self.slots[entry.slot].pos = pos;
+    }
+}
+
+impl Slots {
+    /// Returns a slot for a new element at `pos`.
+    fn insert(&mut self, pos: usize) -> HeapHandle {
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].pos = pos;
+                slot
+            }
+            None => {
+                self.slots.push(Slot { pos, generation: 0 });
+                self.slots.len() - 1
+            }
+        };
+        HeapHandle { slot, generation: self.slots[slot].generation }
+    }
+
+    /// Invalidates the handles to the element of `slot`, and makes the slot
+    /// available to new elements.
+    fn remove(&mut self, slot: usize) {
+        let generation = &mut self.slots[slot].generation;
+        *generation = generation.wrapping_add(1);
+        self.free.push(slot);
+    }
+}
+
+impl<T> IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    ///
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub const fn new() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap { data: Vec::new(), slots: Slots { slots: Vec::new(), free: Vec::new() } }
+    }
+
+    /// Creates an empty `IndexedBinaryHeap` with at least the specified
+    /// capacity.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap {
+            data: Vec::with_capacity(capacity),
+            slots: Slots { slots: Vec::with_capacity(capacity), free: Vec::new() },
+        }
+    }
+
+    /// Returns the length of the heap.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the heap is empty.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the greatest item in the heap, or `None` if it is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|entry| &entry.item)
+    }
+
+    /// Returns the handle of the greatest item in the heap, or `None` if it is
+    /// empty.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn peek_handle(&self) -> Option<HeapHandle> {
+        self.data.first().map(|entry| self.handle(entry.slot))
+    }
+
+    fn handle(&self, slot: usize) -> HeapHandle {
+        HeapHandle { slot, generation: self.slots.slots[slot].generation }
+    }
+
+    /// Returns the index in `data` of the element of `handle`, if it is still
+    /// in the heap.
+    fn position(&self, handle: HeapHandle) -> Option<usize> {
+        let slot = self.slots.slots.get(handle.slot)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        // A handle of another heap may refer to an unused slot.
+        self.data.get(slot.pos).is_some_and(|entry| entry.slot == handle.slot).then_some(slot.pos)
+    }
+
+    /// Returns `true` if the element of `handle` is still in the heap, that
+    /// is, if it has not been popped or removed.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn contains(&self, handle: HeapHandle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the element of `handle`, or `None` if it is no longer in the
+    /// heap.
+    #[must_use]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.position(handle).map(|pos| &self.data[pos].item)
+    }
+
+    /// Drops all items from the heap.
+    ///
+    /// The handles of the dropped items are no longer accepted.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn clear(&mut self) {
+        for entry in self.data.drain(..) {
+            self.slots.remove(entry.slot);
+        }
+    }
+
+    /// Consumes the heap and returns the underlying vector in arbitrary
+    /// order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.into_iter().map(|entry| entry.item).collect()
+    }
+}
+
+impl<T: Ord> IndexedBinaryHeap<T> {
+    /// Pushes an item onto the heap, returning a handle to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    ///
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let three = heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.get(three), Some(&3));
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push` on a heap containing *n* elements is
+    /// *O*(log(*n*)), not counting the occasional resize of the heap.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn push(&mut self, item: T) -> HeapHandle {
+        let pos = self.len();
+        let handle = self.slots.insert(pos);
+        self.data.push(Entry { item, slot: handle.slot });
+        // SAFETY: `pos` is the index of the element we just pushed.
+        unsafe { sift_up(&mut self.data, &mut self.slots, 0, pos) };
+        handle
+    }
+
+    /// Removes the greatest item from the heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or
+    /// `None` if it is no longer in the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    ///
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(1);
+    /// let two = heap.push(2);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.remove(two), Some(2));
+    /// assert_eq!(heap.remove(two), None);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `remove` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn remove(&mut self, handle: HeapHandle) -> Option<T> {
+        let pos = self.position(handle)?;
+        Some(self.remove_at(pos))
+    }
+
+    /// Replaces the element of `handle` by `item`, moving it to its new
+    /// position in the heap, and returns the previous element.
+    ///
+    /// `item` can compare greater or less than the previous element, so this
+    /// can be used both to increase and to decrease a priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element of `handle` is no longer in the heap, see
+    /// [`contains`](Self::contains).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    ///
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let task = heap.push((1, "write docs"));
+    /// heap.push((5, "fix bug"));
+    ///
+    /// // The docs became urgent.
+    /// assert_eq!(heap.change_priority(task, (9, "write docs")), (1, "write docs"));
+    /// assert_eq!(heap.pop(), Some((9, "write docs")));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `change_priority` on a heap containing *n*
+    /// elements is *O*(log(*n*)).
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn change_priority(&mut self, handle: HeapHandle, item: T) -> T {
+        let Some(pos) = self.position(handle) else {
+            panic!("handle of an element that is no longer in the heap");
+        };
+        let old = mem::replace(&mut self.data[pos].item, item);
+        // SAFETY: `pos` is the index of an element.
+        unsafe { self.sift(pos) };
+        old
+    }
+
+    /// Consumes the heap and returns a vector in sorted (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    ///
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// for x in [4, 1, 5, 2] {
+    ///     heap.push(x);
+    /// }
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 4, 5]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            // The positions are not needed anymore, so they are not updated.
+            // SAFETY: 0 < end < self.len().
+            unsafe { sift_down_range(&mut self.data, &mut (), 0, end) };
+        }
+        self.into_vec()
+    }
+
+    /// Removes and returns the element at `pos`, which must be an index of
+    /// `data`.
+    fn remove_at(&mut self, pos: usize) -> T {
+        let entry = self.data.swap_remove(pos);
+        self.slots.remove(entry.slot);
+        if pos < self.len() {
+            // The last element was moved into the gap.
+            if pos == 0 {
+                // SAFETY: 0 < self.len().
+                unsafe { sift_down_to_bottom(&mut self.data, &mut self.slots, 0) };
+            } else {
+                // SAFETY: pos < self.len().
+                unsafe { self.sift(pos) };
+            }
+        }
+        entry.item
+    }
+
+    /// Moves the element at `pos` up or down to restore the heap property,
+    /// after it was replaced.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift(&mut self, pos: usize) {
+        let len = self.len();
+        // SAFETY: The caller guarantees that pos < self.len().
+        let new_pos = unsafe { sift_up(&mut self.data, &mut self.slots, 0, pos) };
+        if new_pos == pos {
+            // SAFETY: Same as above.
+            unsafe { sift_down_range(&mut self.data, &mut self.slots, pos, len) };
+        }
+    }
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T: Clone> Clone for IndexedBinaryHeap<T> {
+    fn clone(&self) -> Self {
+        IndexedBinaryHeap { data: self.data.clone(), slots: self.slots.clone() }
+    }
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T> Default for IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap<T>`.
+    #[inline]
+    fn default() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::new()
+    }
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T: fmt::Debug> fmt::Debug for IndexedBinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter().map(|entry| &entry.item)).finish()
+    }
+}
@@ -56,8 +56,8 @@
 //!
 //! // Start at `start` and use `dist` to track the current shortest distance
 //! // to each node. This implementation isn't memory-efficient as it may leave duplicate
-//! // nodes in the queue. It also uses `usize::MAX` as a sentinel value,
-//! // for a simpler implementation.
+//! // nodes in the queue (see `IndexedBinaryHeap` for a queue that can update them instead).
+//! // It also uses `usize::MAX` as a sentinel value, for a simpler implementation.
 //! fn shortest_path(adj_list: &Vec<Vec<Edge>>, start: usize, goal: usize) -> Option<usize> {
 //!     // dist[node] = current shortest distance from `start` to `node`
 //!     let mut dist: Vec<_> = (0..adj_list.len()).map(|_| usize::MAX).collect();
@@ -157,6 +157,11 @@
 use crate::vec::AsVecIntoIter;
 use crate::vec::{self, Vec};
 
+mod indexed;
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+pub use indexed::{HeapHandle, IndexedBinaryHeap};
+
 /// A priority queue implemented with a binary heap.
 ///
 /// This will be a max-heap.
@@ -791,41 +796,14 @@ pub fn into_sorted_vec(mut self) -> Vec<T, A> {
         self.into_vec()
     }
 
-    // The implementations of sift_up and sift_down use unsafe blocks in
-    // order to move an element out of the vector (leaving behind a
-    // hole), shift along the others and move the removed element back into the
-    // vector at the final location of the hole.
-    // The `Hole` type is used to represent this, and make sure
-    // the hole is filled back at the end of its scope, even on panic.
-    // Using a hole reduces the constant factor compared to using swaps,
-    // which involves twice as many moves.
-
     /// # Safety
     ///
     /// The caller must guarantee that `pos < self.len()`.
     ///
     /// Returns the new position of the element.
     unsafe fn sift_up(&mut self, start: usize, pos: usize) -> usize {
-        // Take out the value at `pos` and create a hole.
         // SAFETY: The caller guarantees that pos < self.len()
-        let mut hole = unsafe { Hole::new(&mut self.data, pos) };
-
-        while hole.pos() > start {
-            let parent = (hole.pos() - 1) / 2;
-
-            // SAFETY: hole.pos() > start >= 0, which means hole.pos() > 0
-            //  and so hole.pos() - 1 can't underflow.
-            //  This guarantees that parent < hole.pos() so
-            //  it's a valid index and also != hole.pos().
-            if hole.element() <= unsafe { hole.get(parent) } {
-                break;
-            }
-
-            // SAFETY: Same as above
-            unsafe { hole.move_to(parent) };
-        }
-
-        hole.pos()
+        unsafe { sift_up(&mut self.data, &mut (), start, pos) }
     }
 
     /// Take an element at `pos` and move it down the heap,
@@ -838,41 +816,7 @@ unsafe fn sift_up(&mut self, start: usize, pos: usize) -> usize {
     /// The caller must guarantee that `pos < end <= self.len()`.
     unsafe fn sift_down_range(&mut self, pos: usize, end: usize) -> usize {
         // SAFETY: The caller guarantees that pos < end <= self.len().
-        let mut hole = unsafe { Hole::new(&mut self.data, pos) };
-        let mut child = 2 * hole.pos() + 1;
-
-        // Loop invariant: child == 2 * hole.pos() + 1.
-        while child <= end.saturating_sub(2) {
-            // compare with the greater of the two children
-            // SAFETY: child < end - 1 < self.len() and
-            //  child + 1 < end <= self.len(), so they're valid indexes.
-            //  child == 2 * hole.pos() + 1 != hole.pos() and
-            //  child + 1 == 2 * hole.pos() + 2 != hole.pos().
-            // FIXME: 2 * hole.pos() + 1 or 2 * hole.pos() + 2 could overflow
-            //  if T is a ZST
-            child += unsafe { hole.get(child) <= hole.get(child + 1) } as usize;
-
-            // if we are already in order, stop.
-            // SAFETY: child is now either the old child or the old child+1
-            //  We already proven that both are < self.len() and != hole.pos()
-            if hole.element() >= unsafe { hole.get(child) } {
-                return hole.pos();
-            }
-
-            // SAFETY: same as above.
-            unsafe { hole.move_to(child) };
-            child = 2 * hole.pos() + 1;
-        }
-
-        // SAFETY: && short circuit, which means that in the
-        //  second condition it's already true that child == end - 1 < self.len().
-        if child == end - 1 && hole.element() < unsafe { hole.get(child) } {
-            // SAFETY: child is already proven to be a valid index and
-            //  child == 2 * hole.pos() + 1 != hole.pos().
-            unsafe { hole.move_to(child) };
-        }
-
-        hole.pos()
+        unsafe { sift_down_range(&mut self.data, &mut (), pos, end) }
     }
 
     /// # Safety
@@ -894,40 +838,9 @@ unsafe fn sift_down(&mut self, pos: usize) -> usize {
     /// # Safety
     ///
     /// The caller must guarantee that `pos < self.len()`.
-    unsafe fn sift_down_to_bottom(&mut self, mut pos: usize) {
-        let end = self.len();
-        let start = pos;
-
+    unsafe fn sift_down_to_bottom(&mut self, pos: usize) {
         // SAFETY: The caller guarantees that pos < self.len().
-        let mut hole = unsafe { Hole::new(&mut self.data, pos) };
-        let mut child = 2 * hole.pos() + 1;
-
-        // Loop invariant: child == 2 * hole.pos() + 1.
-        while child <= end.saturating_sub(2) {
-            // SAFETY: child < end - 1 < self.len() and
-            //  child + 1 < end <= self.len(), so they're valid indexes.
-            //  child == 2 * hole.pos() + 1 != hole.pos() and
-            //  child + 1 == 2 * hole.pos() + 2 != hole.pos().
-            // FIXME: 2 * hole.pos() + 1 or 2 * hole.pos() + 2 could overflow
-            //  if T is a ZST
-            child += unsafe { hole.get(child) <= hole.get(child + 1) } as usize;
-
-            // SAFETY: Same as above
-            unsafe { hole.move_to(child) };
-            child = 2 * hole.pos() + 1;
-        }
-
-        if child == end - 1 {
-            // SAFETY: child == end - 1 < self.len(), so it's a valid index
-            //  and child == 2 * hole.pos() + 1 != hole.pos().
-            unsafe { hole.move_to(child) };
-        }
-        pos = hole.pos();
-        drop(hole);
-
-        // SAFETY: pos is the position in the hole and was already proven
-        //  to be a valid index.
-        unsafe { self.sift_up(start, pos) };
+        unsafe { sift_down_to_bottom(&mut self.data, &mut (), pos) };
     }
 
     /// Rebuild assuming data[0..start] is still a proper heap.
@@ -1523,22 +1436,39 @@ pub fn clear(&mut self) {
 /// (because it was moved from or duplicated).
 /// In drop, `Hole` will restore the slice by filling the hole
 /// position with the value that was originally removed.
-struct Hole<'a, T: 'a> {
+///
+/// Every element moved by a `Hole`, including the removed one when it is put
+/// back, is reported to `positions` with its new index.
+struct Hole<'a, T: 'a, P: Positions<T> + 'a> {
     data: &'a mut [T],
+    positions: &'a mut P,
     elt: ManuallyDrop<T>,
     pos: usize,
 }
 
-impl<'a, T> Hole<'a, T> {
+/// Keeps track of the index of each element of a heap, so that elements can
+/// be found again after they were moved by `sift_up` or `sift_down`.
+trait Positions<T> {
+    /// Records that `elt` is now stored at index `pos`.
+    fn moved(&mut self, elt: &T, pos: usize);
+}
+
+/// Used by heaps that do not keep track of their elements.
+impl<T> Positions<T> for () {
+    #[inline(always)]
+    fn moved(&mut self, _elt: &T, _pos: usize) {}
+}
+
+impl<'a, T, P: Positions<T>> Hole<'a, T, P> {
     /// Creates a new `Hole` at index `pos`.
     ///
     /// Unsafe because pos must be within the data slice.
     #[inline]
-    unsafe fn new(data: &'a mut [T], pos: usize) -> Self {
+    unsafe fn new(data: &'a mut [T], positions: &'a mut P, pos: usize) -> Self {
         debug_assert!(pos < data.len());
         // SAFE: pos should be inside the slice
         let elt = unsafe { ptr::read(data.get_unchecked(pos)) };
-        Hole { data, elt: ManuallyDrop::new(elt), pos }
+        Hole { data, positions, elt: ManuallyDrop::new(elt), pos }
     }
 
     #[inline]
@@ -1574,20 +1504,165 @@ unsafe fn move_to(&mut self, index: usize) {
             let index_ptr: *const _ = ptr.add(index);
             let hole_ptr = ptr.add(self.pos);
             ptr::copy_nonoverlapping(index_ptr, hole_ptr, 1);
+            self.positions.moved(&*hole_ptr, self.pos);
         }
         self.pos = index;
     }
 }
 
-impl<T> Drop for Hole<'_, T> {
+impl<T, P: Positions<T>> Drop for Hole<'_, T, P> {
     #[inline]
     fn drop(&mut self) {
         // fill the hole again
         unsafe {
             let pos = self.pos;
             ptr::copy_nonoverlapping(&*self.elt, self.data.get_unchecked_mut(pos), 1);
+            self.positions.moved(self.data.get_unchecked(pos), pos);
+        }
+    }
+}
+
+// The implementations of sift_up and sift_down use unsafe blocks in
+// order to move an element out of the vector (leaving behind a
+// hole), shift along the others and move the removed element back into the
+// vector at the final location of the hole.
+// The `Hole` type is used to represent this, and make sure
+// the hole is filled back at the end of its scope, even on panic.
+// Using a hole reduces the constant factor compared to using swaps,
+// which involves twice as many moves.
+
+/// # Safety
+///
+/// The caller must guarantee that `pos < data.len()`.
+///
+/// Returns the new position of the element.
+unsafe fn sift_up<T: Ord, P: Positions<T>>(
+    data: &mut [T],
+    positions: &mut P,
+    start: usize,
+    pos: usize,
+) -> usize {
+    // Take out the value at `pos` and create a hole.
+    // SAFETY: The caller guarantees that pos < data.len()
+    let mut hole = unsafe { Hole::new(data, positions, pos) };
+
+    while hole.pos() > start {
+        let parent = (hole.pos() - 1) / 2;
+
+        // SAFETY: hole.pos() > start >= 0, which means hole.pos() > 0
+        //  and so hole.pos() - 1 can't underflow.
+        //  This guarantees that parent < hole.pos() so
+        //  it's a valid index and also != hole.pos().
+        if hole.element() <= unsafe { hole.get(parent) } {
+            break;
+        }
+
+        // SAFETY: Same as above
+        unsafe { hole.move_to(parent) };
+    }
+
+    hole.pos()
+}
+
+/// Take an element at `pos` and move it down the heap,
+/// while its children are larger.
+///
+/// Returns the new position of the element.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < end <= data.len()`.
+unsafe fn sift_down_range<T: Ord, P: Positions<T>>(
+    data: &mut [T],
+    positions: &mut P,
+    pos: usize,
+    end: usize,
+) -> usize {
+    // SAFETY: The caller guarantees that pos < end <= data.len().
+    let mut hole = unsafe { Hole::new(data, positions, pos) };
+    let mut child = 2 * hole.pos() + 1;
+
+    // Loop invariant: child == 2 * hole.pos() + 1.
+    while child <= end.saturating_sub(2) {
+        // compare with the greater of the two children
+        // SAFETY: child < end - 1 < data.len() and
+        //  child + 1 < end <= data.len(), so they're valid indexes.
+        //  child == 2 * hole.pos() + 1 != hole.pos() and
+        //  child + 1 == 2 * hole.pos() + 2 != hole.pos().
+        // FIXME: 2 * hole.pos() + 1 or 2 * hole.pos() + 2 could overflow
+        //  if T is a ZST
+        child += unsafe { hole.get(child) <= hole.get(child + 1) } as usize;
+
+        // if we are already in order, stop.
+        // SAFETY: child is now either the old child or the old child+1
+        //  We already proven that both are < data.len() and != hole.pos()
+        if hole.element() >= unsafe { hole.get(child) } {
+            return hole.pos();
         }
+
+        // SAFETY: same as above.
+        unsafe { hole.move_to(child) };
+        child = 2 * hole.pos() + 1;
+    }
+
+    // SAFETY: && short circuit, which means that in the
+    //  second condition it's already true that child == end - 1 < data.len().
+    if child == end - 1 && hole.element() < unsafe { hole.get(child) } {
+        // SAFETY: child is already proven to be a valid index and
+        //  child == 2 * hole.pos() + 1 != hole.pos().
+        unsafe { hole.move_to(child) };
     }
+
+    hole.pos()
+}
+
+/// Take an element at `pos` and move it all the way down the heap,
+/// then sift it up to its position.
+///
+/// Note: This is faster when the element is known to be large / should
+/// be closer to the bottom.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < data.len()`.
+unsafe fn sift_down_to_bottom<T: Ord, P: Positions<T>>(
+    data: &mut [T],
+    positions: &mut P,
+    mut pos: usize,
+) {
+    let end = data.len();
+    let start = pos;
+
+    // SAFETY: The caller guarantees that pos < data.len().
+    let mut hole = unsafe { Hole::new(data, positions, pos) };
+    let mut child = 2 * hole.pos() + 1;
+
+    // Loop invariant: child == 2 * hole.pos() + 1.
+    while child <= end.saturating_sub(2) {
+        // SAFETY: child < end - 1 < data.len() and
+        //  child + 1 < end <= data.len(), so they're valid indexes.
+        //  child == 2 * hole.pos() + 1 != hole.pos() and
+        //  child + 1 == 2 * hole.pos() + 2 != hole.pos().
+        // FIXME: 2 * hole.pos() + 1 or 2 * hole.pos() + 2 could overflow
+        //  if T is a ZST
+        child += unsafe { hole.get(child) <= hole.get(child + 1) } as usize;
+
+        // SAFETY: Same as above
+        unsafe { hole.move_to(child) };
+        child = 2 * hole.pos() + 1;
+    }
+
+    if child == end - 1 {
+        // SAFETY: child == end - 1 < data.len(), so it's a valid index
+        //  and child == 2 * hole.pos() + 1 != hole.pos().
+        unsafe { hole.move_to(child) };
+    }
+    pos = hole.pos();
+    drop(hole);
+
+    // SAFETY: pos is the position in the hole and was already proven
+    //  to be a valid index.
+    unsafe { sift_up(data, positions, start, pos) };
 }
 
 /// An iterator over the elements of a `BinaryHeap`.
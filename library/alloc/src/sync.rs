@@ -44,6 +44,11 @@
 #[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 
+mod atomic_arc;
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+pub use atomic_arc::{AtomicArc, AtomicOptionArc};
+
 /// A soft limit on the amount of references that may be made to an `Arc`.
 ///
 /// Going above this limit will abort your program (although not
@@ -0,0 +1,676 @@
+//! Atomically swappable `Arc` pointers.
+//!
+//! # Implementation
+//!
+//! Loading an `Arc` from an atomic pointer has to increment the strong count
+//! of the `ArcInner` the pointer points to, but another thread may replace
+//! the pointer and drop the last strong reference between reading the
+//! pointer and incrementing the count.
+//!
+//! To prevent that, `load` first *reserves* the pointer: the low bits of the
+//! pointer, which are always zero because of the alignment of `ArcInner`,
+//! count the loading threads that may not have incremented the strong count
+//! yet. While the pointer is stored, the strong reference owned by the atomic
+//! keeps the `ArcInner` alive. When the pointer is replaced, the replacing
+//! thread adds the number of reservations to the strong count, handing each
+//! loading thread a strong reference that keeps the `ArcInner` alive in the
+//! meantime.
+//!
+//! After incrementing the strong count, `load` gives back its reservation:
+//! if the pointer is still stored with reservations, it decrements the count
+//! in the low bits. Otherwise, its reservation was turned into a strong
+//! reference, which it gives back by decrementing the strong count instead.
+//!
+//! The pointer may have been replaced and stored again in the meantime, in
+//! which case a thread decrements the count in the low bits even though its
+//! reservation was turned into a strong reference. That leaves the count in
+//! the low bits one too low and the strong count one too high, which is
+//! corrected by whichever thread finds the count at zero, or by the thread
+//! replacing the pointer adding one too few to the strong count.
+//!
+//! ## Debts
+//!
+//! Only a few reservations fit into the low bits. Rather than waiting for one
+//! to be given back, a thread that finds them all taken records a *debt*
+//! instead: it claims a slot in a global list, stores the pointer in it, and
+//! checks that the pointer is still stored in the atomic. If so, the thread
+//! replacing the pointer will see the debt, so the `ArcInner` is kept alive
+//! until the loading thread has incremented the strong count and cleared the
+//! slot again.
+//!
+//! After replacing a pointer, a thread pays the debts recorded for it: it
+//! adds a strong reference for every slot holding the pointer, and clears the
+//! slot, which tells the loading thread that it owns that reference. The list
+//! only grows when more threads than it has slots are loading at the same
+//! time, and its slots are reused, so it stays as long as the largest number
+//! of threads that ever had to record a debt at once.
+
+#[cfg(not(no_global_oom_handling))]
+use core::alloc::Layout;
+use core::intrinsics::abort;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
+use core::sync::atomic::{Atomic, AtomicPtr, AtomicUsize, fence};
+use core::{fmt, mem};
+
+use super::{Arc, ArcInner, MAX_REFCOUNT};
+#[cfg(not(no_global_oom_handling))]
+use crate::alloc::handle_alloc_error;
+use crate::boxed::Box;
+
+/// The global list of debts, see the module documentation.
+mod debts {
+    use super::*;
+
+    /// A slot that no thread has claimed.
+    pub(super) const FREE: usize = 0;
+    /// A slot that has been claimed, but holds no debt. Pointers to an
+    /// `ArcInner` are never this small.
+    pub(super) const NO_DEBT: usize = 1;
+
+    const SLOTS: usize = 16;
+
+    struct Block {
+        slots: [Atomic<usize>; SLOTS],
+        next: Atomic<*mut Block>,
+    }
+
+    impl Block {
+        const fn new() -> Block {
+            Block {
+                slots: [const { AtomicUsize::new(FREE) }; SLOTS],
+                next: AtomicPtr::new(ptr::null_mut()),
+            }
+        }
+    }
+
+    // SAFETY: blocks are never deallocated once they are in the list.
+    static DEBTS: Block = Block::new();
+
+    /// Returns all slots, including those that are free.
+    pub(super) fn slots() -> impl Iterator<Item = &'static Atomic<usize>> {
+        let blocks = core::iter::successors(Some(&DEBTS), |block| {
+            // SAFETY: blocks in the list are never deallocated.
+            unsafe { block.next.load(Acquire).as_ref() }
+        });
+        blocks.flat_map(|block| &block.slots)
+    }
+
+    /// Claims a free slot, which holds `NO_DEBT` until it is given back by
+    /// storing `FREE`.
+    pub(super) fn claim() -> &'static Atomic<usize> {
+        let mut block = &DEBTS;
+        loop {
+            for slot in &block.slots {
+                if slot.load(Relaxed) == FREE
+                    && slot.compare_exchange(FREE, NO_DEBT, Acquire, Relaxed).is_ok()
+                {
+                    return slot;
+                }
+            }
+            let next = block.next.load(Acquire);
+            if !next.is_null() {
+                // SAFETY: blocks in the list are never deallocated.
+                block = unsafe { &*next };
+                continue;
+            }
+            // All slots are taken, add a block with its first slot claimed.
+            let new = match Box::try_new(Block::new()) {
+                Ok(new) => new,
+                #[cfg(not(no_global_oom_handling))]
+                Err(_) => handle_alloc_error(Layout::new::<Block>()),
+                #[cfg(no_global_oom_handling)]
+                Err(_) => abort(),
+            };
+            new.slots[0].store(NO_DEBT, Relaxed);
+            let new = Box::into_raw(new);
+            match block.next.compare_exchange(ptr::null_mut(), new, AcqRel, Acquire) {
+                // SAFETY: the block was just added to the list and is never
+                // deallocated.
+                Ok(_) => return unsafe { &(*new).slots[0] },
+                Err(next) => {
+                    // Another thread added a block first, try that one.
+                    // SAFETY: `new` was not added to the list.
+                    drop(unsafe { Box::from_raw(new) });
+                    // SAFETY: blocks in the list are never deallocated.
+                    block = unsafe { &*next };
+                }
+            }
+        }
+    }
+}
+
+/// An `Option<Arc<T>>` that can be loaded and replaced atomically, shared by
+/// [`AtomicArc`] and [`AtomicOptionArc`].
+struct RawAtomicArc<T> {
+    ptr: Atomic<*mut ArcInner<T>>,
+    phantom: PhantomData<Option<Arc<T>>>,
+}
+
+impl<T> RawAtomicArc<T> {
+    /// The low bits of the pointer, which count the reservations of loading
+    /// threads.
+    const RESERVATIONS: usize = mem::align_of::<ArcInner<T>>() - 1;
+
+    const fn null() -> RawAtomicArc<T> {
+        RawAtomicArc { ptr: AtomicPtr::new(ptr::null_mut()), phantom: PhantomData }
+    }
+
+    fn new(arc: Option<Arc<T>>) -> RawAtomicArc<T> {
+        RawAtomicArc { ptr: AtomicPtr::new(Self::into_ptr(arc)), phantom: PhantomData }
+    }
+
+    fn into_ptr(arc: Option<Arc<T>>) -> *mut ArcInner<T> {
+        match arc {
+            Some(arc) => ManuallyDrop::new(arc).ptr.as_ptr(),
+            None => ptr::null_mut(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be null or own a strong reference.
+    unsafe fn from_ptr(ptr: *mut ArcInner<T>) -> Option<Arc<T>> {
+        // SAFETY: guaranteed by the caller.
+        NonNull::new(ptr).map(|ptr| unsafe { Arc::from_inner(ptr) })
+    }
+
+    fn untag(word: *mut ArcInner<T>) -> *mut ArcInner<T> {
+        word.map_addr(|addr| addr & !Self::RESERVATIONS)
+    }
+
+    fn load(&self) -> Option<Arc<T>> {
+        let mut word = self.ptr.load(Relaxed);
+        let ptr = loop {
+            let ptr = Self::untag(word);
+            if ptr.is_null() {
+                return None;
+            }
+            if word.addr() & Self::RESERVATIONS == Self::RESERVATIONS {
+                // Too many threads are loading at the same time.
+                return self.load_with_debt();
+            }
+            let reserved = word.map_addr(|addr| addr + 1);
+            match self.ptr.compare_exchange_weak(word, reserved, Acquire, Relaxed) {
+                Ok(_) => break ptr,
+                Err(actual) => word = actual,
+            }
+        };
+
+        // SAFETY: the reservation keeps the `ArcInner` alive, see the module
+        // documentation.
+        let inner = unsafe { &*ptr };
+        // See `Arc::clone` for the overflow check.
+        if inner.strong.fetch_add(1, Relaxed) > MAX_REFCOUNT {
+            abort();
+        }
+
+        let mut word = self.ptr.load(Relaxed);
+        loop {
+            if Self::untag(word) != ptr || word.addr() & Self::RESERVATIONS == 0 {
+                // The reservation was turned into a strong reference. This
+                // cannot drop the last strong reference, as the one we just
+                // added is still there.
+                inner.strong.fetch_sub(1, Release);
+                break;
+            }
+            let released = word.map_addr(|addr| addr - 1);
+            match self.ptr.compare_exchange_weak(word, released, Release, Relaxed) {
+                Ok(_) => break,
+                Err(actual) => word = actual,
+            }
+        }
+
+        // SAFETY: we incremented the strong count above.
+        Some(unsafe { Arc::from_ptr(ptr) })
+    }
+
+    /// Loads the pointer by recording a debt rather than reserving it, see
+    /// the module documentation.
+    #[cold]
+    fn load_with_debt(&self) -> Option<Arc<T>> {
+        let slot = debts::claim();
+        let arc = loop {
+            let ptr = Self::untag(self.ptr.load(Acquire));
+            if ptr.is_null() {
+                break None;
+            }
+            slot.store(ptr.addr(), Relaxed);
+            // Either the thread replacing the pointer sees the debt, or we
+            // see that the pointer was replaced. Pairs with the fence in
+            // `pay_debts`.
+            fence(SeqCst);
+            if Self::untag(self.ptr.load(Acquire)) != ptr {
+                if slot.compare_exchange(ptr.addr(), debts::NO_DEBT, Relaxed, Relaxed).is_ok() {
+                    continue;
+                }
+                // The debt was paid in the meantime, so we own a strong
+                // reference to the value that was stored when we loaded it.
+                // SAFETY: see above.
+                break Some(unsafe { Arc::from_ptr(ptr) });
+            }
+
+            // SAFETY: the debt keeps the `ArcInner` alive until it is paid or
+            // cleared.
+            let inner = unsafe { &*ptr };
+            // See `Arc::clone` for the overflow check.
+            if inner.strong.fetch_add(1, Relaxed) > MAX_REFCOUNT {
+                abort();
+            }
+            if slot.compare_exchange(ptr.addr(), debts::NO_DEBT, Relaxed, Relaxed).is_err() {
+                // The debt was paid as well, so give back one of the two
+                // strong references we own.
+                inner.strong.fetch_sub(1, Release);
+            }
+            // SAFETY: we own a strong reference, see above.
+            break Some(unsafe { Arc::from_ptr(ptr) });
+        };
+        slot.store(debts::FREE, Release);
+        arc
+    }
+
+    /// Pays the debts recorded for `ptr`, which the calling thread has just
+    /// replaced in `self.ptr` and owns a strong reference to.
+    fn pay_debts(ptr: *mut ArcInner<T>) {
+        // Pairs with the fence in `load_with_debt`.
+        fence(SeqCst);
+        for slot in debts::slots() {
+            if slot.load(Relaxed) != ptr.addr() {
+                continue;
+            }
+            // Add the reference before clearing the debt, as the loading
+            // thread may drop it right after.
+            // SAFETY: we own a strong reference.
+            let inner = unsafe { &*ptr };
+            if inner.strong.fetch_add(1, Relaxed) > MAX_REFCOUNT {
+                abort();
+            }
+            if slot.compare_exchange(ptr.addr(), debts::NO_DEBT, Relaxed, Relaxed).is_err() {
+                // The loading thread cleared the debt itself. This doesn't
+                // drop the last strong reference, as we own one.
+                inner.strong.fetch_sub(1, Relaxed);
+            }
+        }
+    }
+
+    /// Takes the strong reference owned by the replaced pointer `word`,
+    /// turning the reservations and debts of loading threads into strong
+    /// references.
+    ///
+    /// # Safety
+    ///
+    /// `word` must have been replaced in `self.ptr` by the calling thread.
+    unsafe fn take_replaced(word: *mut ArcInner<T>) -> Option<Arc<T>> {
+        let ptr = Self::untag(word);
+        if ptr.is_null() {
+            return None;
+        }
+        let reservations = word.addr() & Self::RESERVATIONS;
+        if reservations > 0 {
+            // SAFETY: the strong reference owned by `word` keeps the
+            // `ArcInner` alive.
+            unsafe { (*ptr).strong.fetch_add(reservations, Relaxed) };
+        }
+        Self::pay_debts(ptr);
+        // SAFETY: the pointer stored in `self.ptr` owns a strong reference,
+        // which now belongs to the calling thread.
+        unsafe { Self::from_ptr(ptr) }
+    }
+
+    fn swap(&self, new: Option<Arc<T>>) -> Option<Arc<T>> {
+        let word = self.ptr.swap(Self::into_ptr(new), AcqRel);
+        // SAFETY: we just replaced `word`.
+        unsafe { Self::take_replaced(word) }
+    }
+
+    fn compare_exchange(
+        &self,
+        current: Option<&Arc<T>>,
+        new: Option<Arc<T>>,
+    ) -> Result<Option<Arc<T>>, Option<Arc<T>>> {
+        let current = current.map_or(ptr::null_mut(), |arc| arc.ptr.as_ptr());
+        let new = Self::into_ptr(new);
+        let mut word = self.ptr.load(Relaxed);
+        loop {
+            if Self::untag(word) != current {
+                // Returning the stored pointer needs a strong reference, which
+                // is taken like in `load`. The pointer may have been replaced
+                // by `current` again in the meantime, so check once more.
+                let actual = self.load();
+                if actual.as_ref().map_or(ptr::null_mut(), |arc| arc.ptr.as_ptr()) != current {
+                    // SAFETY: `new` was created from an `Arc` above.
+                    drop(unsafe { Self::from_ptr(new) });
+                    return Err(actual);
+                }
+                word = self.ptr.load(Relaxed);
+                continue;
+            }
+            // The count of reservations may change while we try, so retry
+            // until either the pointer differs or we replaced it.
+            match self.ptr.compare_exchange_weak(word, new, AcqRel, Relaxed) {
+                // SAFETY: we just replaced `word`.
+                Ok(_) => return Ok(unsafe { Self::take_replaced(word) }),
+                Err(actual) => word = actual,
+            }
+        }
+    }
+
+    fn into_inner(self) -> Option<Arc<T>> {
+        let mut this = ManuallyDrop::new(self);
+        let word = *this.ptr.get_mut();
+        // Having `self` by value means that no thread is loading.
+        debug_assert_eq!(word.addr() & Self::RESERVATIONS, 0);
+        // SAFETY: the stored pointer owns a strong reference, and `this` is
+        // not dropped.
+        unsafe { Self::from_ptr(word) }
+    }
+}
+
+impl<T> Drop for RawAtomicArc<T> {
+    fn drop(&mut self) {
+        let word = *self.ptr.get_mut();
+        debug_assert_eq!(word.addr() & Self::RESERVATIONS, 0);
+        // SAFETY: the stored pointer owns a strong reference.
+        drop(unsafe { Self::from_ptr(word) });
+    }
+}
+
+/// An [`Arc`] that can be loaded and replaced atomically.
+///
+/// `AtomicArc` is meant for values that are read often and replaced rarely,
+/// such as configuration that is shared between threads. Compared to a
+/// `RwLock<Arc<T>>`, loading never waits for a thread replacing the value,
+/// and replacing never waits for threads loading it, see [Progress](#progress).
+///
+/// [`load`](AtomicArc::load) returns a new `Arc` to the current value, so the
+/// value stays alive for as long as the loading thread needs it, even after
+/// it has been replaced.
+///
+/// For an atomic `Option<Arc<T>>`, use [`AtomicOptionArc`].
+///
+/// # Progress
+///
+/// All operations of `AtomicArc` are lock-free: no thread ever waits for
+/// another one to finish. Loading briefly *reserves* the current value by
+/// modifying the atomic pointer. On most platforms, up to 7 threads can hold
+/// such a reservation at the same time (3 on 32-bit platforms). Threads
+/// loading while all reservations are taken record the value in a global list
+/// instead, which every thread replacing a value checks. Storing, swapping and
+/// compare-exchanging therefore take longer while many threads are loading.
+///
+/// Every load also modifies the strong count of the stored value, so threads
+/// loading at the same time contend on the same cache lines, much like the
+/// readers of a `RwLock<Arc<T>>` do.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(atomic_arc)]
+///
+/// use std::sync::{Arc, AtomicArc};
+/// use std::thread;
+///
+/// let config = AtomicArc::new(Arc::new(String::from("v1")));
+///
+/// thread::scope(|s| {
+///     s.spawn(|| {
+///         let current = config.load();
+///         assert!(*current == "v1" || *current == "v2");
+///     });
+///     config.store(Arc::new(String::from("v2")));
+/// });
+///
+/// assert_eq!(*config.load(), "v2");
+/// ```
+#[unstable(feature = "atomic_arc", issue = "none")]
+pub struct AtomicArc<T> {
+    // Never null.
+    raw: RawAtomicArc<T>,
+}
+
+impl<T> AtomicArc<T> {
+    /// Creates a new `AtomicArc` storing `arc`.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn new(arc: Arc<T>) -> AtomicArc<T> {
+        AtomicArc { raw: RawAtomicArc::new(Some(arc)) }
+    }
+
+    /// Returns the stored `Arc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(atomic_arc)]
+    ///
+    /// use std::sync::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Arc::new(5));
+    /// let five = atomic.load();
+    /// assert_eq!(*five, 5);
+    /// assert_eq!(Arc::strong_count(&five), 2);
+    /// ```
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn load(&self) -> Arc<T> {
+        // SAFETY: `raw` is never null.
+        unsafe { self.raw.load().unwrap_unchecked() }
+    }
+
+    /// Stores `arc`, dropping the previously stored `Arc`.
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn store(&self, arc: Arc<T>) {
+        drop(self.swap(arc));
+    }
+
+    /// Stores `arc`, returning the previously stored `Arc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(atomic_arc)]
+    ///
+    /// use std::sync::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Arc::new(1));
+    /// assert_eq!(*atomic.swap(Arc::new(2)), 1);
+    /// assert_eq!(*atomic.load(), 2);
+    /// ```
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn swap(&self, arc: Arc<T>) -> Arc<T> {
+        // SAFETY: `raw` is never null.
+        unsafe { self.raw.swap(Some(arc)).unwrap_unchecked() }
+    }
+
+    /// Stores `new` if the stored `Arc` points to the same allocation as
+    /// `current`.
+    ///
+    /// On success, returns the previously stored `Arc`. Otherwise, drops `new`
+    /// and returns the stored `Arc`. Allocations are compared like with
+    /// [`Arc::ptr_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(atomic_arc)]
+    ///
+    /// use std::sync::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Arc::new(1));
+    ///
+    /// // Update the value based on the current one, retrying if another
+    /// // thread replaced it in the meantime.
+    /// let mut current = atomic.load();
+    /// loop {
+    ///     match atomic.compare_exchange(&current, Arc::new(*current + 1)) {
+    ///         Ok(_) => break,
+    ///         Err(actual) => current = actual,
+    ///     }
+    /// }
+    /// assert_eq!(*atomic.load(), 2);
+    /// ```
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn compare_exchange(&self, current: &Arc<T>, new: Arc<T>) -> Result<Arc<T>, Arc<T>> {
+        match self.raw.compare_exchange(Some(current), Some(new)) {
+            // SAFETY: `raw` is never null.
+            Ok(previous) => Ok(unsafe { previous.unwrap_unchecked() }),
+            // SAFETY: `raw` is never null.
+            Err(actual) => Err(unsafe { actual.unwrap_unchecked() }),
+        }
+    }
+
+    /// Consumes the `AtomicArc`, returning the stored `Arc`.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn into_inner(self) -> Arc<T> {
+        // SAFETY: `raw` is never null.
+        unsafe { self.raw.into_inner().unwrap_unchecked() }
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T> From<Arc<T>> for AtomicArc<T> {
+    fn from(arc: Arc<T>) -> AtomicArc<T> {
+        AtomicArc::new(arc)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T: Default> Default for AtomicArc<T> {
+    fn default() -> AtomicArc<T> {
+        AtomicArc::new(Arc::default())
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T: fmt::Debug> fmt::Debug for AtomicArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(), f)
+    }
+}
+
+/// An `Option<Arc<T>>` that can be loaded and replaced atomically.
+///
+/// This is like [`AtomicArc`], but can also store `None`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(atomic_arc)]
+///
+/// use std::sync::{Arc, AtomicOptionArc};
+///
+/// let cache = AtomicOptionArc::none();
+/// assert!(cache.load().is_none());
+///
+/// cache.store(Some(Arc::new([1, 2, 3])));
+/// assert_eq!(cache.load().as_deref(), Some(&[1, 2, 3]));
+///
+/// // Invalidate the cache.
+/// assert!(cache.swap(None).is_some());
+/// ```
+#[unstable(feature = "atomic_arc", issue = "none")]
+pub struct AtomicOptionArc<T> {
+    raw: RawAtomicArc<T>,
+}
+
+impl<T> AtomicOptionArc<T> {
+    /// Creates a new `AtomicOptionArc` storing `arc`.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn new(arc: Option<Arc<T>>) -> AtomicOptionArc<T> {
+        AtomicOptionArc { raw: RawAtomicArc::new(arc) }
+    }
+
+    /// Creates a new `AtomicOptionArc` storing `None`.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub const fn none() -> AtomicOptionArc<T> {
+        AtomicOptionArc { raw: RawAtomicArc::null() }
+    }
+
+    /// Returns the stored `Arc`, if any.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn load(&self) -> Option<Arc<T>> {
+        self.raw.load()
+    }
+
+    /// Stores `arc`, dropping the previously stored `Arc`.
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn store(&self, arc: Option<Arc<T>>) {
+        drop(self.swap(arc));
+    }
+
+    /// Stores `arc`, returning the previously stored `Arc`.
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn swap(&self, arc: Option<Arc<T>>) -> Option<Arc<T>> {
+        self.raw.swap(arc)
+    }
+
+    /// Stores `new` if the stored `Arc` points to the same allocation as
+    /// `current`, or if both are `None`.
+    ///
+    /// On success, returns the previously stored `Arc`. Otherwise, drops `new`
+    /// and returns the stored `Arc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(atomic_arc)]
+    ///
+    /// use std::sync::{Arc, AtomicOptionArc};
+    ///
+    /// // Initialize the value only once.
+    /// let atomic = AtomicOptionArc::none();
+    /// assert!(atomic.compare_exchange(None, Some(Arc::new(1))).is_ok());
+    /// assert!(atomic.compare_exchange(None, Some(Arc::new(2))).is_err());
+    /// assert_eq!(atomic.load().as_deref(), Some(&1));
+    /// ```
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn compare_exchange(
+        &self,
+        current: Option<&Arc<T>>,
+        new: Option<Arc<T>>,
+    ) -> Result<Option<Arc<T>>, Option<Arc<T>>> {
+        self.raw.compare_exchange(current, new)
+    }
+
+    /// Consumes the `AtomicOptionArc`, returning the stored `Arc`.
+    #[must_use]
+    #[unstable(feature = "atomic_arc", issue = "none")]
+    pub fn into_inner(self) -> Option<Arc<T>> {
+        self.raw.into_inner()
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T> From<Option<Arc<T>>> for AtomicOptionArc<T> {
+    fn from(arc: Option<Arc<T>>) -> AtomicOptionArc<T> {
+        AtomicOptionArc::new(arc)
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T> From<Arc<T>> for AtomicOptionArc<T> {
+    fn from(arc: Arc<T>) -> AtomicOptionArc<T> {
+        AtomicOptionArc::new(Some(arc))
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T> Default for AtomicOptionArc<T> {
+    fn default() -> AtomicOptionArc<T> {
+        AtomicOptionArc::none()
+    }
+}
+
+#[unstable(feature = "atomic_arc", issue = "none")]
+impl<T: fmt::Debug> fmt::Debug for AtomicOptionArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(), f)
+    }
+}
@@ -590,3 +590,182 @@ fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         }
     }
 }
+
+#[test]
+fn test_indexed_push_pop() {
+    let mut heap = IndexedBinaryHeap::new();
+    assert_eq!(heap.pop(), None);
+    for x in [5, 1, 8, 3, 9, 3] {
+        heap.push(x);
+    }
+    assert_eq!(heap.len(), 6);
+    assert_eq!(heap.peek(), Some(&9));
+    let mut popped = Vec::new();
+    while let Some(x) = heap.pop() {
+        popped.push(x);
+    }
+    assert_eq!(popped, [9, 8, 5, 3, 3, 1]);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn test_indexed_change_priority() {
+    let mut heap = IndexedBinaryHeap::new();
+    let handles: Vec<_> = (0..10).map(|x| heap.push(x * 10)).collect();
+
+    // Increase.
+    assert_eq!(heap.change_priority(handles[2], 95), 20);
+    assert_eq!(heap.peek(), Some(&95));
+    assert_eq!(heap.peek_handle(), Some(handles[2]));
+
+    // Decrease.
+    assert_eq!(heap.change_priority(handles[2], 5), 95);
+    assert_eq!(heap.change_priority(handles[9], 15), 90);
+    assert_eq!(heap.peek(), Some(&80));
+
+    for (i, &handle) in handles.iter().enumerate() {
+        let expected = match i {
+            2 => 5,
+            9 => 15,
+            _ => i * 10,
+        };
+        assert_eq!(heap.get(handle), Some(&expected));
+    }
+    assert_eq!(heap.into_sorted_vec(), [0, 5, 10, 15, 30, 40, 50, 60, 70, 80]);
+}
+
+#[test]
+fn test_indexed_stale_handles() {
+    let mut heap = IndexedBinaryHeap::new();
+    let a = heap.push(1);
+    let b = heap.push(2);
+    let c = heap.push(3);
+
+    assert_eq!(heap.remove(b), Some(2));
+    assert_eq!(heap.remove(b), None);
+    assert!(!heap.contains(b));
+    assert_eq!(heap.pop(), Some(3));
+    assert!(!heap.contains(c));
+    assert_eq!(heap.get(c), None);
+
+    // The slots of removed elements are reused, but not their handles.
+    let d = heap.push(4);
+    let e = heap.push(5);
+    assert_ne!(d, b);
+    assert_ne!(e, c);
+    assert_eq!(heap.get(b), None);
+    assert_eq!(heap.get(d), Some(&4));
+
+    heap.clear();
+    assert!(heap.is_empty());
+    assert!(!heap.contains(a) && !heap.contains(d) && !heap.contains(e));
+    let f = heap.push(6);
+    assert_eq!(heap.get(a), None);
+    assert_eq!(heap.get(f), Some(&6));
+    assert_eq!(format!("{heap:?}"), "[6]");
+}
+
+#[test]
+#[should_panic = "no longer in the heap"]
+fn test_indexed_change_priority_stale() {
+    let mut heap = IndexedBinaryHeap::new();
+    let handle = heap.push(1);
+    heap.pop();
+    heap.change_priority(handle, 2);
+}
+
+#[test]
+fn test_indexed_random_ops() {
+    use rand::Rng;
+
+    let mut rng = crate::test_rng();
+    let mut heap = IndexedBinaryHeap::new();
+    // The handles and values of the elements in the heap.
+    let mut model: Vec<(HeapHandle, u32)> = Vec::new();
+    let mut removed = Vec::new();
+    let ops = if cfg!(miri) { 200 } else { 5000 };
+
+    for _ in 0..ops {
+        match rng.random_range(0..5) {
+            0 | 1 => {
+                let x = rng.random_range(0..100);
+                model.push((heap.push(x), x));
+            }
+            2 if !model.is_empty() => {
+                let i = rng.random_range(0..model.len());
+                let x = rng.random_range(0..100);
+                assert_eq!(heap.change_priority(model[i].0, x), model[i].1);
+                model[i].1 = x;
+            }
+            3 if !model.is_empty() => {
+                let (handle, x) = model.swap_remove(rng.random_range(0..model.len()));
+                assert_eq!(heap.remove(handle), Some(x));
+                removed.push(handle);
+            }
+            _ => {
+                let max = model.iter().map(|&(_, x)| x).max();
+                assert_eq!(heap.pop(), max);
+                if let Some(max) = max {
+                    let i =
+                        model.iter().position(|&(handle, x)| x == max && !heap.contains(handle));
+                    removed.push(model.swap_remove(i.unwrap()).0);
+                }
+            }
+        }
+
+        assert_eq!(heap.len(), model.len());
+        for &(handle, x) in &model {
+            assert_eq!(heap.get(handle), Some(&x));
+        }
+    }
+    for handle in removed {
+        assert!(!heap.contains(handle));
+    }
+
+    let mut sorted: Vec<_> = model.iter().map(|&(_, x)| x).collect();
+    sorted.sort();
+    assert_eq!(heap.into_sorted_vec(), sorted);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_indexed_panic_safe() {
+    use std::cmp::Ordering;
+
+    #[derive(PartialEq, Eq, Debug)]
+    struct PanicOrd(u32, bool);
+
+    impl PartialOrd for PanicOrd {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for PanicOrd {
+        fn cmp(&self, other: &Self) -> Ordering {
+            if self.1 || other.1 {
+                panic!("Panicking comparison");
+            }
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut heap = IndexedBinaryHeap::new();
+    let handles: Vec<_> = (0..20).map(|x| heap.push(PanicOrd(x, false))).collect();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        heap.change_priority(handles[3], PanicOrd(100, true));
+    }));
+    assert!(result.is_err());
+
+    // All elements can still be found through their handles.
+    for (x, &handle) in handles.iter().enumerate() {
+        let item = heap.get(handle).unwrap();
+        if x == 3 {
+            assert_eq!(*item, PanicOrd(100, true));
+        } else {
+            assert_eq!(*item, PanicOrd(x as u32, false));
+        }
+    }
+    assert_eq!(heap.len(), 20);
+}
@@ -1,5 +1,7 @@
 #![feature(allocator_api)]
+#![feature(atomic_arc)]
 #![feature(binary_heap_pop_if)]
+#![feature(binary_heap_indexed)]
 #![feature(const_heap)]
 #![feature(deque_extend_front)]
 #![feature(iter_array_chunks)]
@@ -716,3 +716,138 @@ fn arc_doesnt_leak_allocator() {
 
     assert_eq!(counter.load(SeqCst), 0);
 }
+
+#[test]
+fn atomic_arc_smoke() {
+    let one = Arc::new(1);
+    let atomic = AtomicArc::new(one.clone());
+    assert!(Arc::ptr_eq(&atomic.load(), &one));
+    assert_eq!(Arc::strong_count(&one), 2);
+
+    let previous = atomic.swap(Arc::new(2));
+    assert!(Arc::ptr_eq(&previous, &one));
+    assert_eq!(*atomic.load(), 2);
+
+    atomic.store(Arc::new(3));
+    assert_eq!(format!("{atomic:?}"), "3");
+    let three = atomic.into_inner();
+    assert_eq!(*three, 3);
+    assert_eq!(Arc::strong_count(&three), 1);
+
+    drop(previous);
+    assert_eq!(Arc::strong_count(&one), 1);
+}
+
+#[test]
+fn atomic_arc_compare_exchange() {
+    let one = Arc::new(1);
+    let atomic = AtomicArc::new(one.clone());
+
+    // An equal value in another allocation does not match.
+    let actual = atomic.compare_exchange(&Arc::new(1), Arc::new(2)).unwrap_err();
+    assert!(Arc::ptr_eq(&actual, &one));
+    assert_eq!(Arc::strong_count(&one), 3);
+    drop(actual);
+    assert!(Arc::ptr_eq(&atomic.load(), &one));
+
+    let previous = atomic.compare_exchange(&one, Arc::new(3)).unwrap();
+    assert!(Arc::ptr_eq(&previous, &one));
+    assert_eq!(*atomic.load(), 3);
+}
+
+#[test]
+fn atomic_option_arc() {
+    let atomic = AtomicOptionArc::default();
+    assert!(atomic.load().is_none());
+    assert_eq!(format!("{atomic:?}"), "None");
+
+    assert!(atomic.compare_exchange(None, Some(Arc::new(1))).unwrap().is_none());
+    let one = atomic.load().unwrap();
+    assert_eq!(*one, 1);
+    let actual = atomic.compare_exchange(None, Some(Arc::new(2))).unwrap_err().unwrap();
+    assert!(Arc::ptr_eq(&actual, &one));
+    drop(actual);
+
+    let previous = atomic.compare_exchange(Some(&one), None).unwrap().unwrap();
+    assert!(Arc::ptr_eq(&previous, &one));
+    assert!(atomic.swap(None).is_none());
+
+    atomic.store(Some(one.clone()));
+    assert_eq!(Arc::strong_count(&one), 3);
+    drop(atomic);
+    assert_eq!(Arc::strong_count(&one), 2);
+
+    assert!(AtomicOptionArc::<i32>::none().into_inner().is_none());
+}
+
+#[test]
+fn atomic_arc_drops_values() {
+    let mut canary = AtomicUsize::new(0);
+    let first = Arc::new(Canary(&mut canary as *mut AtomicUsize));
+    let atomic = AtomicOptionArc::new(Some(first));
+    let loaded = atomic.load();
+    atomic.store(Some(Arc::new(Canary(&mut canary as *mut AtomicUsize))));
+    assert_eq!(canary.load(Acquire), 0);
+    drop(loaded);
+    assert_eq!(canary.load(Acquire), 1);
+    drop(atomic);
+    assert_eq!(canary.load(Acquire), 2);
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn atomic_arc_concurrent() {
+    struct Counted<'a>(usize, &'a AtomicUsize);
+
+    impl<'a> Counted<'a> {
+        fn new(value: usize, live: &'a AtomicUsize) -> Arc<Counted<'a>> {
+            live.fetch_add(1, Relaxed);
+            Arc::new(Counted(value, live))
+        }
+    }
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.1.fetch_sub(1, Relaxed);
+        }
+    }
+
+    const ITERS: usize = if cfg!(miri) { 50 } else { 10_000 };
+
+    let live = AtomicUsize::new(0);
+    let atomic = AtomicArc::new(Counted::new(0, &live));
+    thread::scope(|s| {
+        // More threads than can reserve the pointer at the same time, so that
+        // some of them record debts instead.
+        for _ in 0..10 {
+            s.spawn(|| {
+                let mut last = 0;
+                for _ in 0..ITERS {
+                    let value = atomic.load().0;
+                    assert!(value >= last);
+                    last = value;
+                }
+            });
+        }
+        s.spawn(|| {
+            for _ in 0..ITERS {
+                let current = atomic.load();
+                let new = Counted::new(current.0 + 1, &live);
+                if let Err(actual) = atomic.compare_exchange(&current, new) {
+                    assert!(!Arc::ptr_eq(&actual, &current));
+                }
+            }
+        });
+        // Storing the same `Arc` again makes the pointer match earlier loads.
+        s.spawn(|| {
+            for _ in 0..ITERS {
+                let current = atomic.load();
+                atomic.store(current.clone());
+            }
+        });
+    });
+    let last = atomic.into_inner();
+    assert_eq!(Arc::strong_count(&last), 1);
+    drop(last);
+    assert_eq!(live.load(Relaxed), 0);
+}
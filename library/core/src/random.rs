@@ -1,6 +1,11 @@
 //! Random value generation.
 
-use crate::ops::RangeFull;
+use crate::ops::{Range, RangeFull, RangeInclusive};
+
+mod xoshiro;
+
+#[unstable(feature = "random", issue = "130703")]
+pub use xoshiro::Xoshiro256PlusPlus;
 
 /// A source of randomness.
 #[unstable(feature = "random", issue = "130703")]
@@ -58,3 +63,249 @@ fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
 impl_primitive!(i128);
 impl_primitive!(usize);
 impl_primitive!(isize);
+
+/// Returns 32 random bits, independent of the endianness of the platform.
+fn next_u32(source: &mut (impl Rng + ?Sized)) -> u32 {
+    let mut bytes = [0; 4];
+    source.fill_bytes(&mut bytes);
+    u32::from_le_bytes(bytes)
+}
+
+/// Returns 64 random bits, independent of the endianness of the platform.
+fn next_u64(source: &mut (impl Rng + ?Sized)) -> u64 {
+    let mut bytes = [0; 8];
+    source.fill_bytes(&mut bytes);
+    u64::from_le_bytes(bytes)
+}
+
+/// Returns 128 random bits, independent of the endianness of the platform.
+fn next_u128(source: &mut (impl Rng + ?Sized)) -> u128 {
+    let mut bytes = [0; 16];
+    source.fill_bytes(&mut bytes);
+    u128::from_le_bytes(bytes)
+}
+
+// Samples uniformly from `0..n` without bias, using Lemire's multiply-and-reject
+// method, which only needs a division in the rare case that a sample is
+// rejected. `n` must not be zero.
+macro_rules! uniform_below {
+    ($name:ident, $t:ty, $wide:ty, $next:ident) => {
+        fn $name(source: &mut (impl Rng + ?Sized), n: $t) -> $t {
+            let mut m = $next(source) as $wide * n as $wide;
+            if (m as $t) < n {
+                let threshold = n.wrapping_neg() % n;
+                while (m as $t) < threshold {
+                    m = $next(source) as $wide * n as $wide;
+                }
+            }
+            (m >> <$t>::BITS) as $t
+        }
+    };
+}
+
+uniform_below!(uniform_below_u32, u32, u64, next_u32);
+uniform_below!(uniform_below_u64, u64, u128, next_u64);
+
+/// Samples uniformly from `0..n`, where `n` must not be zero.
+///
+/// There is no wider integer type to multiply in, so this masks samples to
+/// the bit width of `n` and rejects those that are too large instead.
+fn uniform_below_u128(source: &mut (impl Rng + ?Sized), n: u128) -> u128 {
+    // For `n == 1`, all bits of `n - 1` are zero and so is the mask.
+    let mask = u128::MAX.checked_shr((n - 1).leading_zeros()).unwrap_or(0);
+    loop {
+        let x = next_u128(source) & mask;
+        if x < n {
+            return x;
+        }
+    }
+}
+
+// Integers are sampled by sampling the offset from the start of the range as
+// an unsigned integer of the same width. Types narrower than 32 bits are
+// sampled as `u32`, and `usize` and `isize` as `u64`, so that the result does
+// not depend on the pointer width of the platform.
+macro_rules! impl_range {
+    ($t:ty, $unsigned:ty, $sample:ty, $uniform_below:ident, $next:ident) => {
+        impl Distribution<$t> for Range<$t> {
+            /// Samples a value from the range with uniform probability.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range is empty.
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(self.start < self.end, "cannot sample from an empty range");
+                let n = self.end.wrapping_sub(self.start) as $unsigned as $sample;
+                let offset = $uniform_below(source, n) as $unsigned;
+                self.start.wrapping_add(offset as $t)
+            }
+        }
+
+        impl Distribution<$t> for RangeInclusive<$t> {
+            /// Samples a value from the range with uniform probability.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range is empty.
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                let (start, end) = (*self.start(), *self.end());
+                assert!(!self.is_empty(), "cannot sample from an empty range");
+                let n = (end.wrapping_sub(start) as $unsigned as $sample).wrapping_add(1);
+                let offset = if n == 0 {
+                    // The range covers all values of the sampled type.
+                    $next(source) as $unsigned
+                } else {
+                    $uniform_below(source, n) as $unsigned
+                };
+                start.wrapping_add(offset as $t)
+            }
+        }
+    };
+}
+
+impl_range!(u8, u8, u32, uniform_below_u32, next_u32);
+impl_range!(i8, u8, u32, uniform_below_u32, next_u32);
+impl_range!(u16, u16, u32, uniform_below_u32, next_u32);
+impl_range!(i16, u16, u32, uniform_below_u32, next_u32);
+impl_range!(u32, u32, u32, uniform_below_u32, next_u32);
+impl_range!(i32, u32, u32, uniform_below_u32, next_u32);
+impl_range!(u64, u64, u64, uniform_below_u64, next_u64);
+impl_range!(i64, u64, u64, uniform_below_u64, next_u64);
+impl_range!(u128, u128, u128, uniform_below_u128, next_u128);
+impl_range!(i128, u128, u128, uniform_below_u128, next_u128);
+impl_range!(usize, usize, u64, uniform_below_u64, next_u64);
+impl_range!(isize, usize, u64, uniform_below_u64, next_u64);
+
+// Floats are sampled by scaling a float with the full precision of the type,
+// taken from the high bits of a random integer, to the length of the range.
+macro_rules! impl_float_range {
+    ($t:ty, $bits:ty, $next:ident) => {
+        impl Distribution<$t> for Range<$t> {
+            /// Samples a value from the range with uniform probability.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range is empty, or if either of its bounds or the
+            /// distance between them is not finite.
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(self.start < self.end, "cannot sample from an empty range");
+                let scale = self.end - self.start;
+                assert!(scale.is_finite(), "cannot sample from an infinite range");
+                loop {
+                    // A float in `0.0..1.0` with `MANTISSA_DIGITS` random bits.
+                    let shift = <$bits>::BITS - <$t>::MANTISSA_DIGITS;
+                    let unit = ($next(source) >> shift) as $t
+                        / ((1 as $bits) << <$t>::MANTISSA_DIGITS) as $t;
+                    let x = self.start + unit * scale;
+                    // Rounding may produce the end of the range, which must
+                    // not be returned.
+                    if x < self.end {
+                        return x;
+                    }
+                }
+            }
+        }
+
+        impl Distribution<$t> for RangeInclusive<$t> {
+            /// Samples a value from the range with uniform probability.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range is empty, or if either of its bounds or the
+            /// distance between them is not finite.
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                let (start, end) = (*self.start(), *self.end());
+                assert!(start <= end, "cannot sample from an empty range");
+                let scale = end - start;
+                assert!(scale.is_finite(), "cannot sample from an infinite range");
+                // A float in `0.0..=1.0` with `MANTISSA_DIGITS` random bits.
+                let shift = <$bits>::BITS - <$t>::MANTISSA_DIGITS;
+                let max = ((1 as $bits) << <$t>::MANTISSA_DIGITS) - 1;
+                let unit = ($next(source) >> shift) as $t / max as $t;
+                (start + unit * scale).min(end)
+            }
+        }
+    };
+}
+
+impl_float_range!(f32, u32, next_u32);
+impl_float_range!(f64, u64, next_u64);
+
+/// Shuffles the elements of `slice` in place, using `rng` as the source of
+/// randomness.
+///
+/// Every permutation of the elements is equally likely, provided that `rng`
+/// produces uniformly random bytes. This uses the Fisher-Yates algorithm and
+/// takes *O*(*n*) time.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(random)]
+///
+/// use std::random::{self, Xoshiro256PlusPlus};
+///
+/// let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+/// let mut v = [1, 2, 3, 4, 5];
+/// random::shuffle(&mut v, &mut rng);
+/// v.sort();
+/// assert_eq!(v, [1, 2, 3, 4, 5]);
+/// ```
+#[unstable(feature = "random", issue = "130703")]
+pub fn shuffle<T>(slice: &mut [T], rng: &mut (impl Rng + ?Sized)) {
+    for i in (1..slice.len()).rev() {
+        let j = (0..=i).sample(rng);
+        slice.swap(i, j);
+    }
+}
+
+/// Returns a reference to a random element of `slice`, or `None` if it is
+/// empty, using `rng` as the source of randomness.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(random)]
+///
+/// use std::random::{self, Xoshiro256PlusPlus};
+///
+/// let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+/// let v = [1, 2, 3];
+/// assert!(v.contains(random::choose(&v, &mut rng).unwrap()));
+///
+/// let empty: [i32; 0] = [];
+/// assert_eq!(random::choose(&empty, &mut rng), None);
+/// ```
+#[unstable(feature = "random", issue = "130703")]
+#[must_use]
+pub fn choose<'a, T>(slice: &'a [T], rng: &mut (impl Rng + ?Sized)) -> Option<&'a T> {
+    if slice.is_empty() {
+        return None;
+    }
+    Some(&slice[(0..slice.len()).sample(rng)])
+}
+
+/// Returns a mutable reference to a random element of `slice`, or `None` if
+/// it is empty, using `rng` as the source of randomness.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(random)]
+///
+/// use std::random::{self, Xoshiro256PlusPlus};
+///
+/// let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+/// let mut v = [0; 3];
+/// *random::choose_mut(&mut v, &mut rng).unwrap() += 1;
+/// assert_eq!(v.iter().sum::<i32>(), 1);
+/// ```
+#[unstable(feature = "random", issue = "130703")]
+#[must_use]
+pub fn choose_mut<'a, T>(slice: &'a mut [T], rng: &mut (impl Rng + ?Sized)) -> Option<&'a mut T> {
+    if slice.is_empty() {
+        return None;
+    }
+    let index = (0..slice.len()).sample(rng);
+    Some(&mut slice[index])
+}
@@ -0,0 +1,167 @@
+use super::Rng;
+
+/// A seedable pseudorandom number generator implementing the xoshiro256++
+/// algorithm.
+///
+/// This generator is fast and has good statistical quality, which makes it
+/// suitable for simulations, randomized tests and similar uses. It is **not**
+/// cryptographically secure: its future output can be predicted from a few
+/// of its outputs. Use `std::random::SystemRng` when security is a concern.
+///
+/// # Stability
+///
+/// The output of a generator created from a given seed is fully determined
+/// by the seed, is the same on all platforms, and will never change in
+/// future versions of the standard library. This includes the bytes written
+/// by [`fill_bytes`](Rng::fill_bytes), which are the outputs of
+/// [`next_u64`](Xoshiro256PlusPlus::next_u64) in little-endian order, and the
+/// expansion of a seed by [`seed_from_u64`](Xoshiro256PlusPlus::seed_from_u64).
+///
+/// Note that the way the [`Distribution`](super::Distribution)
+/// implementations of the standard library turn random bytes into values is
+/// not covered by this guarantee.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(random)]
+///
+/// use std::random::{Distribution, Xoshiro256PlusPlus};
+///
+/// let mut a = Xoshiro256PlusPlus::seed_from_u64(42);
+/// let mut b = Xoshiro256PlusPlus::seed_from_u64(42);
+/// for _ in 0..10 {
+///     assert_eq!(a.next_u64(), b.next_u64());
+/// }
+///
+/// let roll: u8 = (1..=6).sample(&mut a);
+/// assert!((1..=6).contains(&roll));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[unstable(feature = "random", issue = "130703")]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+impl Xoshiro256PlusPlus {
+    /// Creates a generator from a 32-byte seed.
+    ///
+    /// The seed is read as four `u64`s in little-endian order. The xoshiro
+    /// algorithm does not work with an all-zero state, so a seed of all zero
+    /// bytes is treated like `seed_from_u64(0)`.
+    #[must_use]
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn from_seed(seed: [u8; 32]) -> Xoshiro256PlusPlus {
+        let mut s = [0; 4];
+        let mut i = 0;
+        while i < 4 {
+            let mut word = [0; 8];
+            let mut j = 0;
+            while j < 8 {
+                word[j] = seed[i * 8 + j];
+                j += 1;
+            }
+            s[i] = u64::from_le_bytes(word);
+            i += 1;
+        }
+        if s[0] == 0 && s[1] == 0 && s[2] == 0 && s[3] == 0 {
+            return Xoshiro256PlusPlus::seed_from_u64(0);
+        }
+        Xoshiro256PlusPlus { s }
+    }
+
+    /// Creates a generator from a 64-bit seed.
+    ///
+    /// The seed is expanded to the full state of the generator with the
+    /// SplitMix64 algorithm, as recommended by the authors of xoshiro, so
+    /// that similar seeds result in unrelated outputs.
+    #[must_use]
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn seed_from_u64(seed: u64) -> Xoshiro256PlusPlus {
+        let mut state = seed;
+        let mut s = [0; 4];
+        let mut i = 0;
+        while i < 4 {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            s[i] = z ^ (z >> 31);
+            i += 1;
+        }
+        Xoshiro256PlusPlus { s }
+    }
+
+    /// Returns the next 64 random bits.
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Advances the generator as if [`next_u64`](Self::next_u64) was called
+    /// 2<sup>128</sup> times.
+    ///
+    /// This can be used to split one seed into up to 2<sup>128</sup>
+    /// non-overlapping streams, for example one per thread of a simulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(random)]
+    ///
+    /// use std::random::Xoshiro256PlusPlus;
+    ///
+    /// let mut streams = Vec::new();
+    /// let mut rng = Xoshiro256PlusPlus::seed_from_u64(7);
+    /// for _ in 0..4 {
+    ///     streams.push(rng.clone());
+    ///     rng.jump();
+    /// }
+    /// assert_ne!(streams[0].next_u64(), streams[1].next_u64());
+    /// ```
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn jump(&mut self) {
+        const JUMP: [u64; 4] =
+            [0x180ec6d33cfd0aba, 0xd5a61266f0c9392c, 0xa9582618e03fc9aa, 0x39abdc4529b1661c];
+
+        let mut s = [0; 4];
+        let mut i = 0;
+        while i < 4 {
+            let mut b = 0;
+            while b < 64 {
+                if JUMP[i] & (1 << b) != 0 {
+                    s[0] ^= self.s[0];
+                    s[1] ^= self.s[1];
+                    s[2] ^= self.s[2];
+                    s[3] ^= self.s[3];
+                }
+                self.next_u64();
+                b += 1;
+            }
+            i += 1;
+        }
+        self.s = s;
+    }
+}
+
+impl Rng for Xoshiro256PlusPlus {
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        let mut chunks = bytes.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            rest.copy_from_slice(&self.next_u64().to_le_bytes()[..rest.len()]);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use core::random::{self, Distribution, Rng, Xoshiro256PlusPlus};
+
+#[test]
+fn xoshiro_reference_output() {
+    // The first outputs of the reference implementation for the state [1, 2, 3, 4].
+    let mut seed = [0; 32];
+    seed[0] = 1;
+    seed[8] = 2;
+    seed[16] = 3;
+    seed[24] = 4;
+    let mut rng = Xoshiro256PlusPlus::from_seed(seed);
+    let expected = [
+        41943041,
+        58720359,
+        3588806011781223,
+        3591011842654386,
+        9228616714210784205,
+        9973669472204895162,
+        14011001112246962877,
+        12406186145184390807,
+        15849039046786891736,
+        10450023813501588000,
+    ];
+    for x in expected {
+        assert_eq!(rng.next_u64(), x);
+    }
+}
+
+#[test]
+fn xoshiro_seeding() {
+    // SplitMix64 expands the seed, so the all-zero seed still works.
+    assert_eq!(Xoshiro256PlusPlus::from_seed([0; 32]), Xoshiro256PlusPlus::seed_from_u64(0));
+    assert_eq!(Xoshiro256PlusPlus::seed_from_u64(0).next_u64(), 5987356902031041503);
+    assert_ne!(Xoshiro256PlusPlus::seed_from_u64(1), Xoshiro256PlusPlus::seed_from_u64(2));
+}
+
+#[test]
+fn xoshiro_fill_bytes() {
+    let mut a = Xoshiro256PlusPlus::seed_from_u64(3);
+    let mut b = a.clone();
+    let mut bytes = [0; 11];
+    a.fill_bytes(&mut bytes);
+    assert_eq!(bytes[..8], b.next_u64().to_le_bytes());
+    assert_eq!(bytes[8..], b.next_u64().to_le_bytes()[..3]);
+}
+
+#[test]
+fn xoshiro_jump() {
+    let mut a = Xoshiro256PlusPlus::seed_from_u64(4);
+    let b = a.clone();
+    a.jump();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn int_ranges() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(5);
+    for _ in 0..1000 {
+        let x: u8 = (10..20).sample(&mut rng);
+        assert!((10..20).contains(&x));
+        let x: i32 = (-3..=3).sample(&mut rng);
+        assert!((-3..=3).contains(&x));
+        let x: i128 = (i128::MIN..i128::MIN + 2).sample(&mut rng);
+        assert!(x == i128::MIN || x == i128::MIN + 1);
+        let x: isize = (-7..=-7).sample(&mut rng);
+        assert_eq!(x, -7);
+    }
+
+    // Ranges with a single value.
+    let x: u128 = (5..6).sample(&mut rng);
+    assert_eq!(x, 5);
+    let x: u128 = (u128::MAX..=u128::MAX).sample(&mut rng);
+    assert_eq!(x, u128::MAX);
+    let x: i128 = (-1..0).sample(&mut rng);
+    assert_eq!(x, -1);
+    let x: i128 = (i128::MIN..=i128::MIN).sample(&mut rng);
+    assert_eq!(x, i128::MIN);
+    let x: u8 = (0..1).sample(&mut rng);
+    assert_eq!(x, 0);
+    let x: u64 = (u64::MAX..=u64::MAX).sample(&mut rng);
+    assert_eq!(x, u64::MAX);
+
+    // Ranges covering the whole type.
+    let _: u64 = (0..=u64::MAX).sample(&mut rng);
+    let _: i8 = (i8::MIN..=i8::MAX).sample(&mut rng);
+    let _: u128 = (0..=u128::MAX).sample(&mut rng);
+}
+
+#[test]
+fn int_range_is_uniform() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(6);
+    let mut counts = [0; 6];
+    for _ in 0..60000 {
+        let roll: usize = (0..6).sample(&mut rng);
+        counts[roll] += 1;
+    }
+    for count in counts {
+        assert!((9000..11000).contains(&count), "{counts:?}");
+    }
+}
+
+#[test]
+#[should_panic = "empty range"]
+fn empty_int_range() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(7);
+    let _: u32 = (5..5).sample(&mut rng);
+}
+
+#[test]
+fn float_ranges() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(8);
+    for _ in 0..1000 {
+        let x: f64 = (1.0..2.0).sample(&mut rng);
+        assert!((1.0..2.0).contains(&x));
+        let x: f32 = (-1.0..=1.0).sample(&mut rng);
+        assert!((-1.0..=1.0).contains(&x));
+        let x: f64 = (0.5..=0.5).sample(&mut rng);
+        assert_eq!(x, 0.5);
+    }
+}
+
+#[test]
+#[should_panic = "infinite range"]
+fn infinite_float_range() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(9);
+    let _: f64 = (0.0..f64::INFINITY).sample(&mut rng);
+}
+
+#[test]
+fn shuffle() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(10);
+    let mut v: [u32; 0] = [];
+    random::shuffle(&mut v, &mut rng);
+
+    let mut v: Vec<u32> = (0..100).collect();
+    random::shuffle(&mut v, &mut rng);
+    assert_ne!(v, (0..100).collect::<Vec<_>>());
+    v.sort();
+    assert_eq!(v, (0..100).collect::<Vec<_>>());
+
+    // Every permutation of three elements is about equally likely.
+    let mut counts = [0; 6];
+    let perms = [[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1]];
+    for _ in 0..60000 {
+        let mut v = [1, 2, 3];
+        random::shuffle(&mut v, &mut rng);
+        counts[perms.iter().position(|p| *p == v).unwrap()] += 1;
+    }
+    for count in counts {
+        assert!((9000..11000).contains(&count), "{counts:?}");
+    }
+}
+
+#[test]
+fn choose() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(11);
+    let empty: [u8; 0] = [];
+    assert_eq!(random::choose(&empty, &mut rng), None);
+
+    let v = [1, 2, 3];
+    let mut seen = [false; 3];
+    for _ in 0..100 {
+        let x = *random::choose(&v, &mut rng).unwrap();
+        seen[x - 1] = true;
+    }
+    assert_eq!(seen, [true; 3]);
+
+    let mut v = [0; 4];
+    *random::choose_mut(&mut v, &mut rng).unwrap() = 1;
+    assert_eq!(v.iter().sum::<i32>(), 1);
+}
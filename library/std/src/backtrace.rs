@@ -90,12 +90,12 @@
 
 use crate::backtrace_rs::{self, BytesOrWideString};
 use crate::ffi::c_void;
-use crate::panic::UnwindSafe;
-use crate::sync::LazyLock;
+use crate::path::PathBuf;
+use crate::sync::OnceLock;
 use crate::sync::atomic::Ordering::Relaxed;
 use crate::sync::atomic::{Atomic, AtomicU8};
-use crate::sys::backtrace::{lock, output_filename, set_image_base};
-use crate::{env, fmt};
+use crate::sys::backtrace::{filename_path, lock, output_filename, set_image_base};
+use crate::{env, fmt, str};
 
 /// A captured OS thread stack backtrace.
 ///
@@ -132,7 +132,7 @@ pub enum BacktraceStatus {
 enum Inner {
     Unsupported,
     Disabled,
-    Captured(LazyLock<Capture, LazyResolve>),
+    Captured(Capture),
 }
 
 struct Capture {
@@ -146,10 +146,13 @@ fn _assert<T: Send + Sync>() {}
 }
 
 /// A single frame of a backtrace.
+///
+/// The symbols of a frame are only resolved when they are first requested, either through
+/// [`BacktraceFrame::symbols`] or by formatting the backtrace.
 #[unstable(feature = "backtrace_frames", issue = "79676")]
 pub struct BacktraceFrame {
     frame: RawFrame,
-    symbols: Vec<BacktraceSymbol>,
+    symbols: OnceLock<Vec<BacktraceSymbol>>,
 }
 
 #[derive(Debug)]
@@ -159,7 +162,13 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A resolved symbol of a [`BacktraceFrame`].
+///
+/// A frame has several symbols if functions were inlined into it, ordered from the innermost
+/// inlined function to the function the frame actually belongs to. Any of the information
+/// about a symbol may be missing, for example when debug info is not available.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -177,9 +186,10 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let capture = match &self.inner {
             Inner::Unsupported => return fmt.write_str("<unsupported>"),
             Inner::Disabled => return fmt.write_str("<disabled>"),
-            Inner::Captured(c) => &**c,
+            Inner::Captured(c) => c,
         };
 
+        capture.resolve();
         let frames = &capture.frames[capture.actual_start..];
 
         write!(fmt, "Backtrace ")?;
@@ -191,7 +201,7 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
                 continue;
             }
 
-            dbg.entries(&frame.symbols);
+            dbg.entries(frame.symbols());
         }
 
         dbg.finish()
@@ -202,11 +212,12 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
 impl fmt::Debug for BacktraceFrame {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut dbg = fmt.debug_list();
-        dbg.entries(&self.symbols);
+        dbg.entries(self.symbols());
         dbg.finish()
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -331,7 +342,7 @@ fn create(ip: usize) -> Backtrace {
             backtrace_rs::trace_unsynchronized(|frame| {
                 frames.push(BacktraceFrame {
                     frame: RawFrame::Actual(frame.clone()),
-                    symbols: Vec::new(),
+                    symbols: OnceLock::new(),
                 });
                 if frame.symbol_address().addr() == ip && actual_start.is_none() {
                     actual_start = Some(frames.len());
@@ -346,10 +357,7 @@ fn create(ip: usize) -> Backtrace {
         let inner = if frames.is_empty() {
             Inner::Unsupported
         } else {
-            Inner::Captured(LazyLock::new(lazy_resolve(Capture {
-                actual_start: actual_start.unwrap_or(0),
-                frames,
-            })))
+            Inner::Captured(Capture { actual_start: actual_start.unwrap_or(0), frames })
         };
 
         Backtrace { inner }
@@ -371,11 +379,194 @@ pub fn status(&self) -> BacktraceStatus {
 
 impl<'a> Backtrace {
     /// Returns an iterator over the backtrace frames.
+    ///
+    /// This includes the frames of capturing the backtrace itself. The symbols of the frames are
+    /// not resolved until they are requested.
     #[must_use]
     #[unstable(feature = "backtrace_frames", issue = "79676")]
     pub fn frames(&'a self) -> &'a [BacktraceFrame] {
         if let Inner::Captured(c) = &self.inner { &c.frames } else { &[] }
     }
+
+    /// Returns the backtrace frames that a short backtrace would print.
+    ///
+    /// This leaves out the frames of capturing the backtrace, the frames of the panic machinery
+    /// if the backtrace was captured while panicking, and the frames of the runtime that start
+    /// the `main` function or a thread. The symbols of all frames are resolved to find these
+    /// boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_frames)]
+    ///
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// for frame in backtrace.short_frames() {
+    ///     for symbol in frame.symbols() {
+    ///         if let (Some(name), Some(file), Some(line)) =
+    ///             (symbol.name(), symbol.filename(), symbol.lineno())
+    ///         {
+    ///             println!("{name} at {}:{line}", file.display());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn short_frames(&'a self) -> &'a [BacktraceFrame] {
+        let Inner::Captured(capture) = &self.inner else { return &[] };
+        capture.resolve();
+
+        let is_marker = |frame: &BacktraceFrame, marker: &str| {
+            frame.symbols().iter().any(|symbol| {
+                symbol
+                    .name
+                    .as_deref()
+                    .and_then(|name| str::from_utf8(name).ok())
+                    .is_some_and(|name| name.contains(marker))
+            })
+        };
+
+        let frames = &capture.frames[capture.actual_start..];
+        // Everything up to `__rust_end_short_backtrace` belongs to the panic machinery.
+        let start = frames
+            .iter()
+            .position(|frame| is_marker(frame, "__rust_end_short_backtrace"))
+            .map_or(0, |i| i + 1);
+        let frames = &frames[start..];
+        // Everything from `__rust_begin_short_backtrace` on belongs to the runtime.
+        let end = frames
+            .iter()
+            .position(|frame| is_marker(frame, "__rust_begin_short_backtrace"))
+            .unwrap_or(frames.len());
+        &frames[..end]
+    }
+}
+
+impl BacktraceFrame {
+    /// Returns the current instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, but not all
+    /// implementations list this with 100% accuracy.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting address of the function this frame belongs to.
+    ///
+    /// Depending on the platform, this may only be an approximation, or the same as
+    /// [`ip`](BacktraceFrame::ip).
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the symbols of this frame, resolving them first if that hasn't happened yet.
+    ///
+    /// Resolving symbols can be slow, as it may involve reading and parsing debug info. If
+    /// the symbols cannot be resolved, the returned slice is empty.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        if let Some(symbols) = self.symbols.get() {
+            return symbols;
+        }
+        // Use the global backtrace lock to synchronize this as it's a requirement of the
+        // `backtrace` crate. It is always taken before initializing `symbols`, so threads racing
+        // to resolve the same frame cannot deadlock.
+        let _lock = lock();
+        self.resolve_unsynchronized()
+    }
+
+    /// Resolves the symbols of this frame, which requires holding the global backtrace lock.
+    fn resolve_unsynchronized(&self) -> &[BacktraceSymbol] {
+        self.symbols.get_or_init(|| {
+            let frame = match &self.frame {
+                RawFrame::Actual(frame) => frame,
+                #[cfg(test)]
+                RawFrame::Fake => unimplemented!(),
+            };
+            let mut symbols = Vec::new();
+            unsafe {
+                backtrace_rs::resolve_frame_unsynchronized(frame, |symbol| {
+                    symbols.push(BacktraceSymbol {
+                        name: symbol.name().map(|m| m.as_bytes().to_vec()),
+                        filename: symbol.filename_raw().map(|b| match b {
+                            BytesOrWideString::Bytes(b) => BytesOrWide::Bytes(b.to_owned()),
+                            BytesOrWideString::Wide(b) => BytesOrWide::Wide(b.to_owned()),
+                        }),
+                        lineno: symbol.lineno(),
+                        colno: symbol.colno(),
+                    });
+                });
+            }
+            symbols
+        })
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of this symbol, without the hash suffix of legacy
+    /// Rust symbols.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<String> {
+        let name = backtrace_rs::SymbolName::new(self.name.as_deref()?);
+        Some(format!("{name:#}"))
+    }
+
+    /// Returns the raw name of this symbol as it appears in the binary, which is usually the
+    /// mangled name.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn mangled_name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the path of the source file this symbol is defined in.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<PathBuf> {
+        let path = match self.filename.as_ref()? {
+            BytesOrWide::Bytes(w) => filename_path(BytesOrWideString::Bytes(w)),
+            BytesOrWide::Wide(w) => filename_path(BytesOrWideString::Wide(w)),
+        };
+        Some(path.into_owned())
+    }
+
+    /// Returns the line number in [`filename`](BacktraceSymbol::filename) of the code
+    /// executing in this frame.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number in [`filename`](BacktraceSymbol::filename) of the code
+    /// executing in this frame.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
+impl Capture {
+    /// Resolves the symbols of all frames, taking the global backtrace lock only once.
+    fn resolve(&self) {
+        if self.frames.iter().all(|frame| frame.symbols.get().is_some()) {
+            return;
+        }
+        let _lock = lock();
+        for frame in &self.frames {
+            frame.resolve_unsynchronized();
+        }
+    }
 }
 
 #[stable(feature = "backtrace", since = "1.65.0")]
@@ -384,8 +575,9 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let capture = match &self.inner {
             Inner::Unsupported => return fmt.write_str("unsupported backtrace"),
             Inner::Disabled => return fmt.write_str("disabled backtrace"),
-            Inner::Captured(c) => &**c,
+            Inner::Captured(c) => c,
         };
+        capture.resolve();
 
         let full = fmt.alternate();
         let (frames, style) = if full {
@@ -406,10 +598,10 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut f = backtrace_rs::BacktraceFmt::new(fmt, style, &mut print_path);
         f.add_context()?;
         for frame in frames {
-            if frame.symbols.is_empty() {
+            if frame.symbols().is_empty() {
                 f.frame().print_raw(frame.frame.ip(), None, None, None)?;
             } else {
-                for symbol in frame.symbols.iter() {
+                for symbol in frame.symbols() {
                     f.frame().print_raw_with_column(
                         frame.frame.ip(),
                         symbol.name.as_ref().map(|b| backtrace_rs::SymbolName::new(b)),
@@ -428,45 +620,6 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
-mod helper {
-    use super::*;
-    pub(super) type LazyResolve = impl (FnOnce() -> Capture) + Send + Sync + UnwindSafe;
-
-    #[define_opaque(LazyResolve)]
-    pub(super) fn lazy_resolve(mut capture: Capture) -> LazyResolve {
-        move || {
-            // Use the global backtrace lock to synchronize this as it's a
-            // requirement of the `backtrace` crate, and then actually resolve
-            // everything.
-            let _lock = lock();
-            for frame in capture.frames.iter_mut() {
-                let symbols = &mut frame.symbols;
-                let frame = match &frame.frame {
-                    RawFrame::Actual(frame) => frame,
-                    #[cfg(test)]
-                    RawFrame::Fake => unimplemented!(),
-                };
-                unsafe {
-                    backtrace_rs::resolve_frame_unsynchronized(frame, |symbol| {
-                        symbols.push(BacktraceSymbol {
-                            name: symbol.name().map(|m| m.as_bytes().to_vec()),
-                            filename: symbol.filename_raw().map(|b| match b {
-                                BytesOrWideString::Bytes(b) => BytesOrWide::Bytes(b.to_owned()),
-                                BytesOrWideString::Wide(b) => BytesOrWide::Wide(b.to_owned()),
-                            }),
-                            lineno: symbol.lineno(),
-                            colno: symbol.colno(),
-                        });
-                    });
-                }
-            }
-
-            capture
-        }
-    }
-}
-use helper::*;
-
 impl RawFrame {
     fn ip(&self) -> *mut c_void {
         match self {
@@ -475,4 +628,12 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::without_provenance_mut(1),
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => crate::ptr::without_provenance_mut(1),
+        }
+    }
 }
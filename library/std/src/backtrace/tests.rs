@@ -1,5 +1,5 @@
 use super::*;
-use crate::panic::RefUnwindSafe;
+use crate::panic::{RefUnwindSafe, UnwindSafe};
 
 fn generate_fake_frames() -> Vec<BacktraceFrame> {
     vec![
@@ -10,7 +10,8 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                 filename: Some(BytesOrWide::Bytes(b"rust/backtrace.rs".to_vec())),
                 lineno: Some(100),
                 colno: None,
-            }],
+            }]
+            .into(),
         },
         BacktraceFrame {
             frame: RawFrame::Fake,
@@ -19,7 +20,8 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                 filename: None,
                 lineno: None,
                 colno: None,
-            }],
+            }]
+            .into(),
         },
         BacktraceFrame {
             frame: RawFrame::Fake,
@@ -36,7 +38,8 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                     lineno: Some(400),
                     colno: None,
                 },
-            ],
+            ]
+            .into(),
         },
     ]
 }
@@ -91,6 +94,65 @@ fn test_frames() {
     assert!(iter.all(|(f, e)| format!("{f:#?}") == *e));
 }
 
+#[test]
+fn test_frame_accessors() {
+    let frames = generate_fake_frames();
+
+    assert_eq!(frames[0].ip(), crate::ptr::without_provenance_mut(1));
+    assert_eq!(frames[0].symbol_address(), crate::ptr::without_provenance_mut(1));
+
+    let symbols = frames[2].symbols();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].name().as_deref(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbols[0].mangled_name(), Some(&b"std::rt::lang_start_internal"[..]));
+    assert_eq!(symbols[0].filename(), Some(PathBuf::from("rust/rt.rs")));
+    assert_eq!(symbols[0].lineno(), Some(300));
+    assert_eq!(symbols[0].colno(), Some(5));
+    assert_eq!(symbols[1].colno(), None);
+
+    let symbol = &frames[1].symbols()[0];
+    assert_eq!(symbol.filename(), None);
+    assert_eq!(symbol.lineno(), None);
+}
+
+#[test]
+fn test_short_frames() {
+    fn frame(name: &str) -> BacktraceFrame {
+        BacktraceFrame {
+            frame: RawFrame::Fake,
+            symbols: vec![BacktraceSymbol {
+                name: Some(name.as_bytes().to_vec()),
+                filename: None,
+                lineno: None,
+                colno: None,
+            }]
+            .into(),
+        }
+    }
+
+    let frames = vec![
+        frame("std::backtrace::Backtrace::create"),
+        frame("std::panicking::begin_panic_handler"),
+        frame("std::sys::backtrace::__rust_end_short_backtrace"),
+        frame("app::inner"),
+        frame("app::main"),
+        frame("std::sys::backtrace::__rust_begin_short_backtrace"),
+        frame("std::rt::lang_start"),
+    ];
+    let backtrace = Backtrace { inner: Inner::Captured(Capture { actual_start: 1, frames }) };
+    let names: Vec<_> =
+        backtrace.short_frames().iter().map(|f| f.symbols()[0].name().unwrap()).collect();
+    assert_eq!(names, ["app::inner", "app::main"]);
+
+    // Without markers, only the frames of capturing the backtrace are left out.
+    let frames = generate_fake_frames();
+    let backtrace = Backtrace { inner: Inner::Captured(Capture { actual_start: 1, frames }) };
+    assert_eq!(backtrace.short_frames().len(), 2);
+
+    let backtrace = Backtrace::disabled();
+    assert!(backtrace.short_frames().is_empty());
+}
+
 #[test]
 fn backtrace_unwind_safe() {
     fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
@@ -3078,7 +3078,9 @@ fn map_entry<'a, K: 'a, V: 'a, A: Allocator>(
 }
 
 #[inline]
-pub(super) fn map_try_reserve_error(err: hashbrown::TryReserveError) -> TryReserveError {
+pub(in crate::collections) fn map_try_reserve_error(
+    err: hashbrown::TryReserveError,
+) -> TryReserveError {
     match err {
         hashbrown::TryReserveError::CapacityOverflow => {
             TryReserveErrorKind::CapacityOverflow.into()
@@ -0,0 +1,1065 @@
+#[cfg(test)]
+mod tests;
+
+use hashbrown::hash_table::{self, HashTable};
+
+use crate::borrow::Borrow;
+use crate::cmp::Ordering;
+use crate::collections::TryReserveError;
+use crate::collections::hash::map::map_try_reserve_error;
+use crate::fmt::{self, Debug};
+use crate::hash::{BuildHasher, Hash, RandomState};
+use crate::iter::FusedIterator;
+use crate::ops::{Index, IndexMut};
+use crate::{mem, slice, vec};
+
+/// A hash map that remembers the order in which its keys were inserted.
+///
+/// Like a [`HashMap`], an `IndexMap` looks up, inserts and removes keys in
+/// *O*(1) expected time, and requires its keys to implement [`Eq`] and
+/// [`Hash`]. Unlike a `HashMap`, it iterates over its entries in a
+/// well-defined order: the order in which the keys were first inserted,
+/// unless the map is [sorted](IndexMap::sort_by) or entries are
+/// [swap-removed](IndexMap::swap_remove). This makes the output of programs
+/// that print or serialize a map reproducible.
+///
+/// Each entry also has an index, its position in that order, which can be
+/// used to [access the entry](IndexMap::get_index) like in a vector.
+///
+/// # Removing entries
+///
+/// Removing an entry leaves a gap in the order, which can be filled in two
+/// ways:
+///
+/// * [`swap_remove`](IndexMap::swap_remove) moves the last entry into the
+///   gap, like [`Vec::swap_remove`]. This takes *O*(1) time, but changes the
+///   position of the last entry.
+/// * [`shift_remove`](IndexMap::shift_remove) shifts all following entries
+///   by one position, like [`Vec::remove`]. This preserves their order, but
+///   takes *O*(*n*) time.
+///
+/// # Logic errors
+///
+/// The same logic errors as for [`HashMap`] apply: if two keys are equal,
+/// their hashes must be equal, and a key must not be modified in such a way
+/// that its hash or equality changes while it is in the map. The behavior
+/// resulting from such a logic error is not specified, but will be
+/// encapsulated to the `IndexMap` that observed the logic error and not
+/// result in undefined behavior.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(index_map)]
+///
+/// use std::collections::IndexMap;
+///
+/// let mut versions = IndexMap::new();
+/// versions.insert("serde", "1.0");
+/// versions.insert("libc", "0.2");
+/// versions.insert("cfg-if", "1.0");
+///
+/// // Iteration follows insertion order.
+/// let names: Vec<_> = versions.keys().copied().collect();
+/// assert_eq!(names, ["serde", "libc", "cfg-if"]);
+///
+/// // Updating a value keeps the position of its key.
+/// versions.insert("serde", "1.1");
+/// assert_eq!(versions.get_index(0), Some((&"serde", &"1.1")));
+/// assert_eq!(versions.get_index_of("libc"), Some(1));
+///
+/// // Sort to get an order that does not depend on insertion.
+/// versions.sort_keys();
+/// assert_eq!(format!("{versions:?}"), r#"{"cfg-if": "1.0", "libc": "0.2", "serde": "1.1"}"#);
+/// ```
+///
+/// [`HashMap`]: crate::collections::HashMap
+#[unstable(feature = "index_map", issue = "none")]
+pub struct IndexMap<K, V, S = RandomState> {
+    // The entries in order.
+    entries: Vec<Bucket<K, V>>,
+    // The indices in `entries`, hashed by the key of the entry they refer to.
+    indices: HashTable<usize>,
+    hash_builder: S,
+}
+
+/// An entry of an `IndexMap`, with the hash of its key so that the hash
+/// table can be rebuilt without hashing the keys again.
+#[derive(Clone)]
+struct Bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+impl<K, V> Bucket<K, V> {
+    fn refs(&self) -> (&K, &V) {
+        (&self.key, &self.value)
+    }
+
+    fn muts(&mut self) -> (&K, &mut V) {
+        (&self.key, &mut self.value)
+    }
+
+    fn into_pair(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+/// Returns the hasher of the hash table for `entries`.
+fn get_hash<K, V>(entries: &[Bucket<K, V>]) -> impl Fn(&usize) -> u64 + '_ {
+    move |&i| entries[i].hash
+}
+
+impl<K, V> IndexMap<K, V, RandomState> {
+    /// Creates an empty `IndexMap`.
+    ///
+    /// The map is initially created with a capacity of 0, so it will not
+    /// allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn new() -> IndexMap<K, V, RandomState> {
+        Default::default()
+    }
+
+    /// Creates an empty `IndexMap` with at least the specified capacity.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> IndexMap<K, V, RandomState> {
+        IndexMap::with_capacity_and_hasher(capacity, Default::default())
+    }
+}
+
+impl<K, V, S> IndexMap<K, V, S> {
+    /// Creates an empty `IndexMap` which will use the given hash builder to
+    /// hash keys.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_hasher(hash_builder: S) -> IndexMap<K, V, S> {
+        IndexMap { entries: Vec::new(), indices: HashTable::new(), hash_builder }
+    }
+
+    /// Creates an empty `IndexMap` with at least the specified capacity,
+    /// using `hash_builder` to hash the keys.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> IndexMap<K, V, S> {
+        IndexMap {
+            entries: Vec::with_capacity(capacity),
+            indices: HashTable::with_capacity(capacity),
+            hash_builder,
+        }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity().min(self.indices.capacity())
+    }
+
+    /// Returns a reference to the map's [`BuildHasher`].
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Returns the number of elements in the map.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated
+    /// memory for reuse.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.entries.clear();
+    }
+
+    /// Reserves capacity for at least `additional` more elements to be
+    /// inserted in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new allocation size overflows [`usize`].
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn reserve(&mut self, additional: usize) {
+        self.indices.reserve(additional, get_hash(&self.entries));
+        self.entries.reserve(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements to
+    /// be inserted in the map.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.indices
+            .try_reserve(additional, get_hash(&self.entries))
+            .map_err(map_try_reserve_error)?;
+        self.entries.try_reserve(additional)
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shrink_to_fit(&mut self) {
+        self.indices.shrink_to_fit(get_hash(&self.entries));
+        self.entries.shrink_to_fit();
+    }
+
+    /// An iterator visiting all key-value pairs in order.
+    /// The iterator element type is `(&'a K, &'a V)`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { base: self.entries.iter() }
+    }
+
+    /// An iterator visiting all key-value pairs in order, with mutable
+    /// references to the values.
+    /// The iterator element type is `(&'a K, &'a mut V)`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut { base: self.entries.iter_mut() }
+    }
+
+    /// An iterator visiting all keys in order.
+    /// The iterator element type is `&'a K`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values in order.
+    /// The iterator element type is `&'a V`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values mutably in order.
+    /// The iterator element type is `&'a mut V`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut { inner: self.iter_mut() }
+    }
+
+    /// Returns the key-value pair at position `index`, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let map = IndexMap::from([("a", 1), ("b", 2)]);
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.entries.get(index).map(Bucket::refs)
+    }
+
+    /// Returns the key and a mutable reference to the value at position
+    /// `index`, or `None` if `index` is out of bounds.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.entries.get_mut(index).map(Bucket::muts)
+    }
+
+    /// Returns the first key-value pair, or `None` if the map is empty.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.entries.first().map(Bucket::refs)
+    }
+
+    /// Returns the last key-value pair, or `None` if the map is empty.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.entries.last().map(Bucket::refs)
+    }
+
+    /// Removes the last key-value pair and returns it, or `None` if the map
+    /// is empty.
+    ///
+    /// This takes *O*(1) time.
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let index = self.len().checked_sub(1)?;
+        Some(self.swap_remove_index_unchecked(index))
+    }
+
+    /// Removes the key-value pair at position `index` and returns it, or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// The last key-value pair is moved into the position of the removed one,
+    /// like [`Vec::swap_remove`]. This takes *O*(1) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let mut map = IndexMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    /// assert_eq!(map.swap_remove_index(0), Some(("a", 1)));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), [&"c", &"b"]);
+    /// ```
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.swap_remove_index_unchecked(index))
+    }
+
+    /// Removes the key-value pair at position `index` and returns it, or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// The key-value pairs after the removed one are shifted to fill the gap,
+    /// like [`Vec::remove`]. This takes *O*(*n*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let mut map = IndexMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    /// assert_eq!(map.shift_remove_index(0), Some(("a", 1)));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), [&"b", &"c"]);
+    /// ```
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index >= self.len() {
+            return None;
+        }
+        self.erase_index(index);
+        let bucket = self.entries.remove(index);
+        // The entries after `index` moved one position to the front.
+        if index < self.len() {
+            for i in self.indices.iter_mut() {
+                if *i > index {
+                    *i -= 1;
+                }
+            }
+        }
+        Some(bucket.into_pair())
+    }
+
+    /// Retains only the elements specified by the predicate, keeping their
+    /// order.
+    ///
+    /// In other words, removes all pairs `(k, v)` for which `f(&k, &mut v)`
+    /// returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let mut map: IndexMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert_eq!(map.keys().copied().collect::<Vec<_>>(), [0, 2, 4, 6]);
+    /// ```
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let guard = RebuildOnDrop { map: self };
+        guard.map.entries.retain_mut(|bucket| f(&bucket.key, &mut bucket.value));
+    }
+
+    /// Sorts the map with a comparison function, keeping the order of equal
+    /// elements.
+    ///
+    /// The comparison function receives two key-value pairs to compare. This
+    /// takes *O*(*n* \* log(*n*)) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let mut map = IndexMap::from([("a", 3), ("b", 1), ("c", 2)]);
+    /// map.sort_by(|_, v1, _, v2| v1.cmp(v2));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), [&"b", &"c", &"a"]);
+    /// assert_eq!(map["a"], 3);
+    /// ```
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        let guard = RebuildOnDrop { map: self };
+        guard.map.entries.sort_by(|a, b| compare(&a.key, &a.value, &b.key, &b.value));
+    }
+
+    /// Sorts the map by its keys, keeping the order of equal keys.
+    ///
+    /// This takes *O*(*n* \* log(*n*)) time.
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.sort_by(|k1, _, k2, _| k1.cmp(k2));
+    }
+
+    /// Removes the entry at `index` from the hash table, leaving it in
+    /// `entries`.
+    fn erase_index(&mut self, index: usize) {
+        let hash = self.entries[index].hash;
+        match self.indices.find_entry(hash, |&i| i == index) {
+            Ok(entry) => {
+                entry.remove();
+            }
+            Err(_) => unreachable!("entry missing from the hash table"),
+        }
+    }
+
+    /// Removes the entry at `index`, which must be in bounds, moving the last
+    /// entry into its position.
+    fn swap_remove_index_unchecked(&mut self, index: usize) -> (K, V) {
+        self.erase_index(index);
+        let bucket = self.entries.swap_remove(index);
+        if let Some(moved) = self.entries.get(index) {
+            // The last entry moved from `self.len()` to `index`.
+            let last = self.entries.len();
+            let i = self.indices.find_mut(moved.hash, |&i| i == last);
+            *i.expect("entry missing from the hash table") = index;
+        }
+        bucket.into_pair()
+    }
+
+    /// Rebuilds the hash table after the entries were moved.
+    fn rebuild_indices(&mut self) {
+        self.indices.clear();
+        let entries = &self.entries;
+        for (i, bucket) in entries.iter().enumerate() {
+            self.indices.insert_unique(bucket.hash, i, get_hash(entries));
+        }
+    }
+}
+
+/// Rebuilds the hash table of the map, even if moving the entries panicked.
+struct RebuildOnDrop<'a, K, V, S> {
+    map: &'a mut IndexMap<K, V, S>,
+}
+
+impl<K, V, S> Drop for RebuildOnDrop<'_, K, V, S> {
+    fn drop(&mut self) {
+        self.map.rebuild_indices();
+    }
+}
+
+impl<K, V, S> IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, the pair is appended to the
+    /// end of the map and [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated and the
+    /// old value is returned. The key keeps its position and is not updated,
+    /// like for [`HashMap::insert`](crate::collections::HashMap::insert).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexMap;
+    ///
+    /// let mut map = IndexMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.insert(12, "b"), None);
+    /// assert_eq!(map.insert(37, "c"), Some("a"));
+    /// assert_eq!(map.get_index(0), Some((&37, &"c")));
+    /// ```
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_full(key, value).1
+    }
+
+    /// Inserts a key-value pair into the map, and returns its index along
+    /// with the old value, if any.
+    ///
+    /// See [`insert`](IndexMap::insert) for more.
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn insert_full(&mut self, key: K, value: V) -> (usize, Option<V>) {
+        let hash = self.hash_builder.hash_one(&key);
+        let entries = &self.entries;
+        let eq = |&i: &usize| entries[i].key == key;
+        match self.indices.entry(hash, eq, get_hash(entries)) {
+            hash_table::Entry::Occupied(entry) => {
+                let i = *entry.get();
+                (i, Some(mem::replace(&mut self.entries[i].value, value)))
+            }
+            hash_table::Entry::Vacant(entry) => {
+                let i = self.entries.len();
+                self.entries.push(Bucket { hash, key, value });
+                entry.insert(i);
+                (i, None)
+            }
+        }
+    }
+
+    /// Returns the index of `key`, if it is in the map.
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let hash = self.hash_builder.hash_one(key);
+        let entries = &self.entries;
+        self.indices.find(hash, |&i| entries[i].key.borrow() == key).copied()
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key).map(|i| &self.entries[i].value)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key).map(|i| &mut self.entries[i].value)
+    }
+
+    /// Returns the index, key and value of the entry for `key`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key).map(|i| (i, &self.entries[i].key, &self.entries[i].value))
+    }
+
+    /// Returns the index of the entry for `key`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(key)
+    }
+
+    /// Removes a key from the map, returning its value if the key was in the
+    /// map.
+    ///
+    /// The last key-value pair is moved into the position of the removed one,
+    /// like [`Vec::swap_remove`]. This takes *O*(1) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let index = self.find(key)?;
+        Some(self.swap_remove_index_unchecked(index).1)
+    }
+
+    /// Removes a key from the map, returning its value if the key was in the
+    /// map.
+    ///
+    /// The key-value pairs after the removed one are shifted to fill the gap,
+    /// like [`Vec::remove`]. This takes *O*(*n*) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let index = self.find(key)?;
+        self.shift_remove_index(index).map(|(_, value)| value)
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Clone for IndexMap<K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        IndexMap {
+            entries: self.entries.clone(),
+            indices: self.indices.clone(),
+            hash_builder: self.hash_builder.clone(),
+        }
+    }
+}
+
+/// Maps are equal if they contain the same key-value pairs, regardless of
+/// their order.
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> PartialEq for IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &IndexMap<K, V, S>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().all(|(key, value)| other.get(key).map_or(false, |v| *value == *v))
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Eq for IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Eq,
+    S: BuildHasher,
+{
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Debug for IndexMap<K, V, S>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Default for IndexMap<K, V, S>
+where
+    S: Default,
+{
+    /// Creates an empty `IndexMap<K, V, S>`, with the `Default` value for the hasher.
+    #[inline]
+    fn default() -> IndexMap<K, V, S> {
+        IndexMap::with_hasher(Default::default())
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, Q: ?Sized, V, S> Index<&Q> for IndexMap<K, V, S>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `IndexMap`.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Index<usize> for IndexMap<K, V, S> {
+    type Output = V;
+
+    /// Returns a reference to the value at the supplied index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index(&self, index: usize) -> &V {
+        &self.entries[index].value
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> IndexMut<usize> for IndexMap<K, V, S> {
+    /// Returns a mutable reference to the value at the supplied index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut V {
+        &mut self.entries[index].value
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, const N: usize> From<[(K, V); N]> for IndexMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+{
+    /// Converts a `[(K, V); N]` into an `IndexMap<K, V>`, keeping the order
+    /// of the array.
+    ///
+    /// If any entries in the array have equal keys, the last value is kept at
+    /// the position of the first key.
+    fn from(arr: [(K, V); N]) -> Self {
+        Self::from_iter(arr)
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> FromIterator<(K, V)> for IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    /// Constructs an `IndexMap<K, V>` from an iterator of key-value pairs,
+    /// keeping their order.
+    ///
+    /// If the iterator produces any pairs with equal keys, the last value is
+    /// kept at the position of the first key.
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> IndexMap<K, V, S> {
+        let mut map = IndexMap::with_hasher(Default::default());
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts all new key-values from the iterator and replaces values with existing
+/// keys with new values returned from the iterator.
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> Extend<(K, V)> for IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        // Like `HashMap`, only reserve for half of the elements if the map
+        // is not empty, as keys may already be present.
+        let reserve =
+            if self.is_empty() { iter.size_hint().0 } else { iter.size_hint().0.div_ceil(2) };
+        self.reserve(reserve);
+        iter.for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, K, V, S> Extend<(&'a K, &'a V)> for IndexMap<K, V, S>
+where
+    K: Eq + Hash + Copy,
+    V: Copy,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, K, V, S> IntoIterator for &'a IndexMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, K, V, S> IntoIterator for &'a mut IndexMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V, S> IntoIterator for IndexMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Creates a consuming iterator, that is, one that moves each key-value
+    /// pair out of the map in order.
+    #[inline]
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { base: self.entries.into_iter() }
+    }
+}
+
+/// An iterator over the entries of an `IndexMap`.
+///
+/// This `struct` is created by the [`iter`] method on [`IndexMap`]. See its
+/// documentation for more.
+///
+/// [`iter`]: IndexMap::iter
+#[unstable(feature = "index_map", issue = "none")]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    base: slice::Iter<'a, Bucket<K, V>>,
+}
+
+/// A mutable iterator over the entries of an `IndexMap`.
+///
+/// This `struct` is created by the [`iter_mut`] method on [`IndexMap`]. See
+/// its documentation for more.
+///
+/// [`iter_mut`]: IndexMap::iter_mut
+#[unstable(feature = "index_map", issue = "none")]
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    base: slice::IterMut<'a, Bucket<K, V>>,
+}
+
+/// An owning iterator over the entries of an `IndexMap`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`IndexMap`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[unstable(feature = "index_map", issue = "none")]
+pub struct IntoIter<K, V> {
+    base: vec::IntoIter<Bucket<K, V>>,
+}
+
+impl<K, V> IntoIter<K, V> {
+    /// Returns an iterator of references over the remaining items.
+    #[inline]
+    pub(super) fn iter(&self) -> Iter<'_, K, V> {
+        Iter { base: self.base.as_slice().iter() }
+    }
+}
+
+/// An iterator over the keys of an `IndexMap`.
+///
+/// This `struct` is created by the [`keys`] method on [`IndexMap`]. See its
+/// documentation for more.
+///
+/// [`keys`]: IndexMap::keys
+#[unstable(feature = "index_map", issue = "none")]
+pub struct Keys<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+/// An iterator over the values of an `IndexMap`.
+///
+/// This `struct` is created by the [`values`] method on [`IndexMap`]. See its
+/// documentation for more.
+///
+/// [`values`]: IndexMap::values
+#[unstable(feature = "index_map", issue = "none")]
+pub struct Values<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+/// A mutable iterator over the values of an `IndexMap`.
+///
+/// This `struct` is created by the [`values_mut`] method on [`IndexMap`]. See
+/// its documentation for more.
+///
+/// [`values_mut`]: IndexMap::values_mut
+#[unstable(feature = "index_map", issue = "none")]
+pub struct ValuesMut<'a, K: 'a, V: 'a> {
+    inner: IterMut<'a, K, V>,
+}
+
+// Implements the iterator traits for an iterator that maps the elements of
+// the wrapped iterator `$field` with `$map`.
+macro_rules! iterator {
+    ($name:ident<$($lt:lifetime,)? $($param:ident),*>, $field:ident, $item:ty, $map:expr) => {
+        #[unstable(feature = "index_map", issue = "none")]
+        impl<$($lt,)? $($param),*> Iterator for $name<$($lt,)? $($param),*> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<$item> {
+                self.$field.next().map($map)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.$field.size_hint()
+            }
+            #[inline]
+            fn count(self) -> usize {
+                self.$field.len()
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$item> {
+                self.$field.nth(n).map($map)
+            }
+            #[inline]
+            fn last(mut self) -> Option<$item> {
+                self.next_back()
+            }
+        }
+
+        #[unstable(feature = "index_map", issue = "none")]
+        impl<$($lt,)? $($param),*> DoubleEndedIterator for $name<$($lt,)? $($param),*> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$item> {
+                self.$field.next_back().map($map)
+            }
+        }
+
+        #[unstable(feature = "index_map", issue = "none")]
+        impl<$($lt,)? $($param),*> ExactSizeIterator for $name<$($lt,)? $($param),*> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.$field.len()
+            }
+        }
+
+        #[unstable(feature = "index_map", issue = "none")]
+        impl<$($lt,)? $($param),*> FusedIterator for $name<$($lt,)? $($param),*> {}
+    };
+}
+
+iterator!(Iter<'a, K, V>, base, (&'a K, &'a V), Bucket::refs);
+iterator!(IterMut<'a, K, V>, base, (&'a K, &'a mut V), Bucket::muts);
+iterator!(IntoIter<K, V>, base, (K, V), Bucket::into_pair);
+iterator!(Keys<'a, K, V>, inner, &'a K, |(key, _)| key);
+iterator!(Values<'a, K, V>, inner, &'a V, |(_, value)| value);
+iterator!(ValuesMut<'a, K, V>, inner, &'a mut V, |(_, value)| value);
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter { base: self.base.clone() }
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V> Clone for Keys<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Keys { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V> Clone for Values<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Values { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: Debug, V: Debug> fmt::Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: Debug, V: Debug> fmt::Debug for IterMut<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.base.as_slice().iter().map(Bucket::refs)).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: Debug, V: Debug> fmt::Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: Debug, V> fmt::Debug for Keys<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V: Debug> fmt::Debug for Values<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K, V: Debug> fmt::Debug for ValuesMut<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.inner.base.as_slice().iter().map(|bucket| &bucket.value))
+            .finish()
+    }
+}
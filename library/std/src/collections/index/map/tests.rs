@@ -0,0 +1,192 @@
+use rand::Rng;
+
+use super::IndexMap;
+use crate::cell::Cell;
+use crate::hash::{BuildHasherDefault, DefaultHasher};
+use crate::panic::{AssertUnwindSafe, catch_unwind};
+use crate::test_helpers::test_rng;
+
+#[test]
+fn test_insertion_order() {
+    let mut m = IndexMap::new();
+    for i in [5, 3, 9, 1, 7] {
+        assert_eq!(m.insert(i, i * 10), None);
+    }
+    assert_eq!(m.insert(9, 0), Some(90));
+    assert_eq!(m.len(), 5);
+
+    let pairs: Vec<_> = m.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(pairs, [(5, 50), (3, 30), (9, 0), (1, 10), (7, 70)]);
+    assert_eq!(m.keys().rev().copied().collect::<Vec<_>>(), [7, 1, 9, 3, 5]);
+    assert_eq!(m.into_iter().map(|(_, v)| v).collect::<Vec<_>>(), [50, 30, 0, 10, 70]);
+}
+
+#[test]
+fn test_lookup() {
+    let mut m: IndexMap<String, usize> = IndexMap::new();
+    assert_eq!(m.get("a"), None);
+    for (i, s) in ["a", "b", "c"].into_iter().enumerate() {
+        assert_eq!(m.insert_full(s.to_string(), i), (i, None));
+    }
+    assert_eq!(m.insert_full("b".to_string(), 5), (1, Some(1)));
+
+    assert!(m.contains_key("c"));
+    assert!(!m.contains_key("d"));
+    assert_eq!(m.get("b"), Some(&5));
+    assert_eq!(m.get_full("c"), Some((2, &"c".to_string(), &2)));
+    assert_eq!(m.get_index_of("a"), Some(0));
+    assert_eq!(m["c"], 2);
+    assert_eq!(m[0], 0);
+
+    *m.get_mut("a").unwrap() += 10;
+    m[2] += 20;
+    *m.get_index_mut(1).unwrap().1 += 30;
+    for v in m.values_mut() {
+        *v += 1;
+    }
+    assert_eq!(m.values().copied().collect::<Vec<_>>(), [11, 36, 23]);
+    assert_eq!(m.first(), Some((&"a".to_string(), &11)));
+    assert_eq!(m.last(), Some((&"c".to_string(), &23)));
+}
+
+#[test]
+fn test_swap_remove() {
+    let mut m: IndexMap<_, _> = (0..5).map(|i| (i, i)).collect();
+    assert_eq!(m.swap_remove(&1), Some(1));
+    assert_eq!(m.swap_remove(&1), None);
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 4, 2, 3]);
+    assert_eq!(m.get_index_of(&4), Some(1));
+    assert_eq!(m.get_index_of(&3), Some(3));
+
+    assert_eq!(m.swap_remove_index(3), Some((3, 3)));
+    assert_eq!(m.swap_remove_index(3), None);
+    assert_eq!(m.pop(), Some((2, 2)));
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 4]);
+    assert_eq!(m[&4], 4);
+}
+
+#[test]
+fn test_shift_remove() {
+    let mut m: IndexMap<_, _> = (0..5).map(|i| (i, i)).collect();
+    assert_eq!(m.shift_remove(&1), Some(1));
+    assert_eq!(m.shift_remove(&1), None);
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 2, 3, 4]);
+    for (i, k) in [0, 2, 3, 4].into_iter().enumerate() {
+        assert_eq!(m.get_index_of(&k), Some(i));
+    }
+
+    assert_eq!(m.shift_remove_index(0), Some((0, 0)));
+    assert_eq!(m.shift_remove_index(3), None);
+    assert_eq!(m.get_index_of(&4), Some(2));
+}
+
+#[test]
+fn test_sort_and_retain() {
+    let mut m = IndexMap::from([("d", 1), ("b", 3), ("a", 3), ("c", 2)]);
+    m.sort_by(|_, v1, _, v2| v2.cmp(v1));
+    // The sort is stable.
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), ["b", "a", "c", "d"]);
+    assert_eq!(m.get_index_of("d"), Some(3));
+
+    m.sort_keys();
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+    assert_eq!(m.get_full("c"), Some((2, &"c", &2)));
+
+    m.retain(|_, v| *v != 3);
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), ["c", "d"]);
+    assert_eq!(m.get_index_of("d"), Some(1));
+    assert_eq!(m.get("a"), None);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_sort_panic() {
+    let mut m: IndexMap<_, _> = (0..20).map(|i| (i, ())).collect();
+    let calls = Cell::new(0);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        m.sort_by(|k1, _, k2, _| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 10 {
+                panic!("comparison panicked");
+            }
+            k2.cmp(k1)
+        })
+    }));
+    assert!(result.is_err());
+
+    // Whatever the order is, every key can still be found at its index.
+    assert_eq!(m.len(), 20);
+    for (i, k) in m.keys().enumerate() {
+        assert_eq!(m.get_index_of(k), Some(i));
+    }
+}
+
+#[test]
+fn test_eq_and_debug() {
+    let a = IndexMap::from([(1, 'a'), (2, 'b')]);
+    let b = IndexMap::from([(2, 'b'), (1, 'a')]);
+    // Equality does not depend on the order.
+    assert_eq!(a, b);
+    assert_ne!(a, IndexMap::from([(1, 'a')]));
+    assert_eq!(format!("{a:?}"), "{1: 'a', 2: 'b'}");
+    assert_eq!(format!("{b:?}"), "{2: 'b', 1: 'a'}");
+    assert_eq!(format!("{:?}", b.iter()), "[(2, 'b'), (1, 'a')]");
+    assert_eq!(format!("{:?}", b.clone().into_iter()), "[(2, 'b'), (1, 'a')]");
+}
+
+#[test]
+fn test_capacity() {
+    type IM = IndexMap<i32, i32, BuildHasherDefault<DefaultHasher>>;
+
+    let mut m = IM::default();
+    assert_eq!(m.capacity(), 0);
+    m.reserve(10);
+    assert!(m.capacity() >= 10);
+    m.extend((0..10).map(|i| (i, i)));
+    m.clear();
+    assert!(m.is_empty());
+    assert!(m.capacity() >= 10);
+    m.shrink_to_fit();
+    assert_eq!(m.capacity(), 0);
+    assert!(m.try_reserve(usize::MAX).is_err());
+}
+
+#[test]
+fn test_random_ops() {
+    let mut rng = test_rng();
+    let mut m = IndexMap::new();
+    // The expected entries, in order.
+    let mut model: Vec<(u32, u32)> = Vec::new();
+    let ops = if cfg!(miri) { 200 } else { 5000 };
+
+    for _ in 0..ops {
+        let k = rng.gen_range(0..64);
+        let position = model.iter().position(|&(key, _)| key == k);
+        match rng.gen_range(0..4) {
+            0 | 1 => {
+                let v = rng.gen_range(0..1000);
+                let old = match position {
+                    Some(i) => Some(crate::mem::replace(&mut model[i].1, v)),
+                    None => {
+                        model.push((k, v));
+                        None
+                    }
+                };
+                assert_eq!(m.insert(k, v), old);
+            }
+            2 => {
+                let old = position.map(|i| model.swap_remove(i).1);
+                assert_eq!(m.swap_remove(&k), old);
+            }
+            _ => {
+                let old = position.map(|i| model.remove(i).1);
+                assert_eq!(m.shift_remove(&k), old);
+            }
+        }
+
+        assert_eq!(m.len(), model.len());
+        for (i, &(k, v)) in model.iter().enumerate() {
+            assert_eq!(m.get_full(&k), Some((i, &k, &v)));
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Insertion-ordered containers, implemented as a vector of entries indexed
+//! by a hash table
+
+pub mod map;
+pub mod set;
@@ -0,0 +1,626 @@
+#[cfg(test)]
+mod tests;
+
+use super::map::{self, IndexMap};
+use crate::borrow::Borrow;
+use crate::cmp::Ordering;
+use crate::collections::TryReserveError;
+use crate::fmt;
+use crate::hash::{BuildHasher, Hash, RandomState};
+use crate::iter::FusedIterator;
+use crate::ops::Index;
+
+/// A hash set that remembers the order in which its values were inserted,
+/// implemented as an `IndexMap` where the value is `()`.
+///
+/// Like a [`HashSet`], an `IndexSet` checks, inserts and removes values in
+/// *O*(1) expected time. Unlike a `HashSet`, it iterates over its values in
+/// the order in which they were first inserted, unless the set is
+/// [sorted](IndexSet::sort) or values are
+/// [swap-removed](IndexSet::swap_remove). Each value also has an index, its
+/// position in that order. See [`IndexMap`] for more.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(index_map)]
+///
+/// use std::collections::IndexSet;
+///
+/// let mut features = IndexSet::new();
+/// features.insert("std");
+/// features.insert("serde");
+/// features.insert("std");
+///
+/// assert_eq!(features.len(), 2);
+/// assert_eq!(features.iter().copied().collect::<Vec<_>>(), ["std", "serde"]);
+/// assert_eq!(features.get_index_of("serde"), Some(1));
+/// assert_eq!(features[0], "std");
+/// ```
+///
+/// [`HashSet`]: crate::collections::HashSet
+#[unstable(feature = "index_map", issue = "none")]
+pub struct IndexSet<T, S = RandomState> {
+    map: IndexMap<T, (), S>,
+}
+
+impl<T> IndexSet<T, RandomState> {
+    /// Creates an empty `IndexSet`.
+    ///
+    /// The set is initially created with a capacity of 0, so it will not
+    /// allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn new() -> IndexSet<T, RandomState> {
+        Default::default()
+    }
+
+    /// Creates an empty `IndexSet` with at least the specified capacity.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> IndexSet<T, RandomState> {
+        IndexSet { map: IndexMap::with_capacity(capacity) }
+    }
+}
+
+impl<T, S> IndexSet<T, S> {
+    /// Creates an empty `IndexSet` which will use the given hash builder to
+    /// hash values.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_hasher(hasher: S) -> IndexSet<T, S> {
+        IndexSet { map: IndexMap::with_hasher(hasher) }
+    }
+
+    /// Creates an empty `IndexSet` with at least the specified capacity,
+    /// using `hasher` to hash the values.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> IndexSet<T, S> {
+        IndexSet { map: IndexMap::with_capacity_and_hasher(capacity, hasher) }
+    }
+
+    /// Returns the number of elements the set can hold without reallocating.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns a reference to the set's [`BuildHasher`].
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the set, removing all values.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Reserves capacity for at least `additional` more elements to be
+    /// inserted in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new allocation size overflows [`usize`].
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional)
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements to
+    /// be inserted in the set.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.map.try_reserve(additional)
+    }
+
+    /// Shrinks the capacity of the set as much as possible.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit()
+    }
+
+    /// An iterator visiting all elements in order.
+    /// The iterator element type is `&'a T`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { base: self.map.keys() }
+    }
+
+    /// Returns the value at position `index`, or `None` if `index` is out of
+    /// bounds.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.map.get_index(index).map(|(value, _)| value)
+    }
+
+    /// Returns the first value, or `None` if the set is empty.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(value, _)| value)
+    }
+
+    /// Returns the last value, or `None` if the set is empty.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(value, _)| value)
+    }
+
+    /// Removes the last value and returns it, or `None` if the set is empty.
+    ///
+    /// This takes *O*(1) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn pop(&mut self) -> Option<T> {
+        self.map.pop().map(|(value, ())| value)
+    }
+
+    /// Removes the value at position `index` and returns it, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// The last value is moved into the position of the removed one, like
+    /// [`Vec::swap_remove`]. This takes *O*(1) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<T> {
+        self.map.swap_remove_index(index).map(|(value, ())| value)
+    }
+
+    /// Removes the value at position `index` and returns it, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// The values after the removed one are shifted to fill the gap, like
+    /// [`Vec::remove`]. This takes *O*(*n*) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<T> {
+        self.map.shift_remove_index(index).map(|(value, ())| value)
+    }
+
+    /// Retains only the elements specified by the predicate, keeping their
+    /// order.
+    ///
+    /// In other words, removes all elements `e` for which `f(&e)` returns
+    /// `false`.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|value, _| f(value));
+    }
+
+    /// Sorts the set with a comparison function, keeping the order of equal
+    /// elements.
+    ///
+    /// This takes *O*(*n* \* log(*n*)) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.map.sort_by(|a, _, b, _| compare(a, b));
+    }
+
+    /// Sorts the set, keeping the order of equal elements.
+    ///
+    /// This takes *O*(*n* \* log(*n*)) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(index_map)]
+    ///
+    /// use std::collections::IndexSet;
+    ///
+    /// let mut set = IndexSet::from([3, 1, 2]);
+    /// set.sort();
+    /// assert_eq!(set.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// assert!(set.contains(&3));
+    /// ```
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.map.sort_keys();
+    }
+}
+
+impl<T, S> IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain this value, it is appended to
+    ///   the end of the set and `true` is returned.
+    /// - If the set already contained this value, `false` is returned, and
+    ///   the set is not modified: original value is not replaced, and the
+    ///   value passed as argument is dropped.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Adds a value to the set, and returns its index along with whether it
+    /// was newly inserted.
+    ///
+    /// See [`insert`](IndexSet::insert) for more.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn insert_full(&mut self, value: T) -> (usize, bool) {
+        let (index, old) = self.map.insert_full(value, ());
+        (index, old.is_none())
+    }
+
+    /// Returns `true` if the set contains a value.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns a reference to the value in the set, if any, that is equal to
+    /// the given value.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.get_full(value).map(|(_, value, _)| value)
+    }
+
+    /// Returns the index of the value in the set, if any, that is equal to
+    /// the given value.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn get_index_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.get_index_of(value)
+    }
+
+    /// Removes a value from the set. Returns whether the value was present
+    /// in the set.
+    ///
+    /// The last value is moved into the position of the removed one, like
+    /// [`Vec::swap_remove`]. This takes *O*(1) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn swap_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.swap_remove(value).is_some()
+    }
+
+    /// Removes a value from the set. Returns whether the value was present
+    /// in the set.
+    ///
+    /// The values after the removed one are shifted to fill the gap, like
+    /// [`Vec::remove`]. This takes *O*(*n*) time.
+    #[inline]
+    #[unstable(feature = "index_map", issue = "none")]
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.shift_remove(value).is_some()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> Clone for IndexSet<T, S>
+where
+    T: Clone,
+    S: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { map: self.map.clone() }
+    }
+}
+
+/// Sets are equal if they contain the same values, regardless of their
+/// order.
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> PartialEq for IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &IndexSet<T, S>) -> bool {
+        self.map == other.map
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> Eq for IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> fmt::Debug for IndexSet<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> Default for IndexSet<T, S>
+where
+    S: Default,
+{
+    /// Creates an empty `IndexSet<T, S>` with the `Default` value for the hasher.
+    #[inline]
+    fn default() -> IndexSet<T, S> {
+        IndexSet { map: IndexMap::default() }
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> Index<usize> for IndexSet<T, S> {
+    type Output = T;
+
+    /// Returns a reference to the value at the supplied index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get_index(index).expect("IndexSet: index out of bounds")
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, const N: usize> From<[T; N]> for IndexSet<T, RandomState>
+where
+    T: Eq + Hash,
+{
+    /// Converts a `[T; N]` into an `IndexSet<T>`, keeping the order of the
+    /// array.
+    ///
+    /// If the array contains any equal values, all but the first one are
+    /// dropped.
+    fn from(arr: [T; N]) -> Self {
+        Self::from_iter(arr)
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> FromIterator<T> for IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> IndexSet<T, S> {
+        let mut set = IndexSet::with_hasher(Default::default());
+        set.extend(iter);
+        set
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> Extend<T> for IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, T, S> Extend<&'a T> for IndexSet<T, S>
+where
+    T: 'a + Eq + Hash + Copy,
+    S: BuildHasher,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, T, S> IntoIterator for &'a IndexSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<T, S> IntoIterator for IndexSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out
+    /// of the set in order.
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { base: self.map.into_iter() }
+    }
+}
+
+/// An iterator over the items of an `IndexSet`.
+///
+/// This `struct` is created by the [`iter`] method on [`IndexSet`]. See its
+/// documentation for more.
+///
+/// [`iter`]: IndexSet::iter
+#[unstable(feature = "index_map", issue = "none")]
+pub struct Iter<'a, K: 'a> {
+    base: map::Keys<'a, K, ()>,
+}
+
+/// An owning iterator over the items of an `IndexSet`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`IndexSet`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[unstable(feature = "index_map", issue = "none")]
+pub struct IntoIter<K> {
+    base: map::IntoIter<K, ()>,
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a K> {
+        self.base.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+    #[inline]
+    fn count(self) -> usize {
+        self.base.len()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> DoubleEndedIterator for Iter<'_, K> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> ExactSizeIterator for Iter<'_, K> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> FusedIterator for Iter<'_, K> {}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> Clone for Iter<'_, K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter { base: self.base.clone() }
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: fmt::Debug> fmt::Debug for Iter<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> Iterator for IntoIter<K> {
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<K> {
+        self.base.next().map(|(k, ())| k)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+    #[inline]
+    fn count(self) -> usize {
+        self.base.len()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> DoubleEndedIterator for IntoIter<K> {
+    #[inline]
+    fn next_back(&mut self) -> Option<K> {
+        self.base.next_back().map(|(k, ())| k)
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> ExactSizeIterator for IntoIter<K> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K> FusedIterator for IntoIter<K> {}
+
+#[unstable(feature = "index_map", issue = "none")]
+impl<K: fmt::Debug> fmt::Debug for IntoIter<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.base.iter().map(|(k, _)| k)).finish()
+    }
+}
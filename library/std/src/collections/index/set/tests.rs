@@ -0,0 +1,65 @@
+use super::IndexSet;
+
+#[test]
+fn test_insert_and_remove() {
+    let mut s = IndexSet::new();
+    assert!(s.insert(3));
+    assert!(s.insert(1));
+    assert!(s.insert(2));
+    assert!(!s.insert(3));
+    assert_eq!(s.insert_full(1), (1, false));
+    assert_eq!(s.insert_full(4), (3, true));
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), [3, 1, 2, 4]);
+
+    assert!(s.contains(&2));
+    assert_eq!(s.get(&2), Some(&2));
+    assert_eq!(s.get_index_of(&4), Some(3));
+    assert_eq!(s.get_index(0), Some(&3));
+    assert_eq!(s[1], 1);
+    assert_eq!(s.first(), Some(&3));
+    assert_eq!(s.last(), Some(&4));
+
+    assert!(s.swap_remove(&3));
+    assert!(!s.swap_remove(&3));
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), [4, 1, 2]);
+    assert!(s.shift_remove(&4));
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(s.get_index_of(&2), Some(1));
+    assert_eq!(s.pop(), Some(2));
+    assert_eq!(s.swap_remove_index(0), Some(1));
+    assert_eq!(s.shift_remove_index(0), None);
+    assert!(s.is_empty());
+}
+
+#[test]
+fn test_sort_and_retain() {
+    let mut s = IndexSet::from(["pear", "apple", "fig", "banana"]);
+    s.sort();
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), ["apple", "banana", "fig", "pear"]);
+    s.sort_by(|a, b| a.len().cmp(&b.len()));
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), ["fig", "pear", "apple", "banana"]);
+    assert_eq!(s.get_index_of("apple"), Some(2));
+
+    s.retain(|fruit| fruit.len() > 3);
+    assert_eq!(s.into_iter().collect::<Vec<_>>(), ["pear", "apple", "banana"]);
+}
+
+#[test]
+fn test_eq_and_debug() {
+    let a: IndexSet<_> = [1, 2, 3].into_iter().collect();
+    let b = IndexSet::from([3, 2, 1]);
+    assert_eq!(a, b);
+    assert_ne!(a, IndexSet::from([1, 2]));
+    assert_eq!(format!("{b:?}"), "{3, 2, 1}");
+    assert_eq!(format!("{:?}", b.iter()), "[3, 2, 1]");
+    assert_eq!(format!("{:?}", b.into_iter()), "[3, 2, 1]");
+}
+
+#[test]
+fn test_extend() {
+    let mut s = IndexSet::new();
+    s.extend([5, 6, 5]);
+    s.extend(&[7, 6]);
+    assert_eq!(s.len(), 3);
+    assert_eq!(s.iter().copied().collect::<Vec<_>>(), [5, 6, 7]);
+}
@@ -432,6 +432,12 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(inline)]
 pub use self::hash_set::HashSet;
+#[unstable(feature = "index_map", issue = "none")]
+#[doc(inline)]
+pub use self::index_map::IndexMap;
+#[unstable(feature = "index_map", issue = "none")]
+#[doc(inline)]
+pub use self::index_set::IndexSet;
 #[stable(feature = "rust1", since = "1.0.0")]
 // FIXME(#82080) The deprecation here is only theoretical, and does not actually produce a warning.
 #[deprecated(note = "moved to `std::ops::Bound`", since = "1.26.0")]
@@ -439,6 +445,7 @@
 pub use crate::ops::Bound;
 
 mod hash;
+mod index;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub mod hash_map {
@@ -457,3 +464,17 @@ pub mod hash_set {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub use super::hash::set::*;
 }
+
+#[unstable(feature = "index_map", issue = "none")]
+pub mod index_map {
+    //! A hash map that preserves the insertion order of its keys.
+    #[unstable(feature = "index_map", issue = "none")]
+    pub use super::index::map::*;
+}
+
+#[unstable(feature = "index_map", issue = "none")]
+pub mod index_set {
+    //! An insertion-ordered hash set implemented as an `IndexMap` where the value is `()`.
+    #[unstable(feature = "index_map", issue = "none")]
+    pub use super::index::set::*;
+}
@@ -41,7 +41,7 @@
 ))]
 mod tests;
 
-use crate::ffi::OsString;
+use crate::ffi::{OsStr, OsString};
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
 use crate::path::{Path, PathBuf};
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner, fs as fs_imp};
@@ -233,6 +233,182 @@ pub struct Dir {
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct DirEntry(fs_imp::DirEntry);
 
+/// Recursive iterator over the entries in a directory tree.
+///
+/// This iterator is returned from the [`walk_dir`] function of this module
+/// and will yield instances of <code>[io::Result]<[DirEntry]></code> for every
+/// file and directory below the root directory, but not for the root itself.
+/// Its builder methods configure how the tree is walked, and should be called
+/// before the iteration starts.
+///
+/// By default, each directory is returned before its contents, symlinks are
+/// not followed, and the order of entries within a directory is platform and
+/// filesystem dependent, as with [`read_dir`].
+///
+/// # Errors
+///
+/// An [`Err`] is returned if a directory cannot be read, and iteration
+/// continues with the next entry, so that a single unreadable directory does
+/// not end the walk. In particular, an error is returned for a directory
+/// whose contents cannot be read right after the directory itself, or right
+/// before it if directories are returned after their contents.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Debug)]
+pub struct WalkDir {
+    /// The root directory, until it has been opened.
+    root: Option<PathBuf>,
+    max_depth: usize,
+    follow_symlinks: bool,
+    sort_by_file_name: bool,
+    contents_first: bool,
+    /// The directories currently being read, innermost last.
+    stack: Vec<WalkDirFrame>,
+    /// An error reading the directory that was returned last.
+    deferred_error: Option<io::Error>,
+    /// Whether `skip_current_dir` should skip the rest of the directory on
+    /// top of the stack, rather than just a deferred error.
+    skip_top: bool,
+}
+
+#[derive(Debug)]
+struct WalkDirFrame {
+    entries: WalkDirEntries,
+    /// The directory being read, if it is returned after its contents.
+    dir: Option<DirEntry>,
+    /// The canonical path of the directory, if symlinks are followed.
+    canonical_path: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+enum WalkDirEntries {
+    Unsorted(ReadDir),
+    Sorted(crate::vec::IntoIter<io::Result<DirEntry>>),
+    Done,
+}
+
+/// A file that atomically replaces another file once it is complete.
+///
+/// This is returned by [`File::create_atomic`]. Its contents are written to a
+/// temporary file in the same directory as the target, which is only moved
+/// to the target path by [`persist`](AtomicFile::persist), after it has been
+/// flushed to disk. Until then, the target keeps its previous contents, and
+/// other processes never see a partially written file, even if the program
+/// or the system crashes in between.
+///
+/// If the `AtomicFile` is dropped without being persisted, the temporary
+/// file is removed and the target is left untouched.
+///
+/// # Platform-specific behavior
+///
+/// On Linux, the temporary file is created with `O_TMPFILE` where the file
+/// system supports it, so that it doesn't appear in the directory before it
+/// is persisted. Elsewhere, it is a hidden file next to the target, which may
+/// be left behind if the process is killed before it can be removed.
+///
+/// Note that this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+#[unstable(feature = "fs_atomic_write", issue = "none")]
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    /// The path the file is moved to when it is persisted.
+    path: PathBuf,
+    /// The path of the temporary file, if it has a name.
+    temp_path: Option<PathBuf>,
+}
+
+/// A uniquely named file that is removed when it is dropped.
+///
+/// The file is created with a random name, by default in the directory
+/// returned by [`env::temp_dir`]. Creating it fails rather than opening a
+/// file that already exists, so another process cannot make the program
+/// write to a file of its choosing by guessing the name ahead of time.
+///
+/// The file is opened for reading and writing, and can be used through its
+/// [`Read`], [`Write`] and [`Seek`] implementations or [`as_file`]. When the
+/// `TempFile` is dropped, the file is closed and removed, unless it was kept
+/// with [`into_path`].
+///
+/// Note that the file may be left behind if the process exits without
+/// running destructors, for example because it is killed or aborts.
+///
+/// # Platform-specific behavior
+///
+/// On Unix platforms, the file is created with `O_EXCL` and is only
+/// accessible by its owner.
+///
+/// [`env::temp_dir`]: crate::env::temp_dir
+/// [`as_file`]: TempFile::as_file
+/// [`into_path`]: TempFile::into_path
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::TempFile;
+/// use std::io::{Read, Seek, SeekFrom, Write};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut file = TempFile::new()?;
+///     file.write_all(b"scratch data")?;
+///     file.seek(SeekFrom::Start(0))?;
+///
+///     let mut contents = String::new();
+///     file.read_to_string(&mut contents)?;
+///     assert_eq!(contents, "scratch data");
+///     Ok(())
+///     // The file is removed here.
+/// }
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct TempFile {
+    file: File,
+    /// The path of the file, until it is kept with `into_path`.
+    path: Option<PathBuf>,
+}
+
+/// A uniquely named directory that is removed with its contents when it is
+/// dropped.
+///
+/// The directory is created with a random name, by default in the directory
+/// returned by [`env::temp_dir`]. Creating it fails rather than using a
+/// directory that already exists. When the `TempDir` is dropped, the
+/// directory is removed with [`remove_dir_all`], unless it was kept with
+/// [`into_path`].
+///
+/// Note that the directory may be left behind if the process exits without
+/// running destructors, or if removing it fails.
+///
+/// # Platform-specific behavior
+///
+/// On Unix platforms, the directory is only accessible by its owner.
+///
+/// [`env::temp_dir`]: crate::env::temp_dir
+/// [`into_path`]: TempDir::into_path
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::{self, TempDir};
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = TempDir::new()?;
+///     fs::write(dir.path().join("input.txt"), "1 2 3")?;
+///     fs::create_dir(dir.path().join("output"))?;
+///     Ok(())
+///     // The directory and everything in it is removed here.
+/// }
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct TempDir {
+    /// The path of the directory, until it is kept with `into_path`.
+    path: Option<PathBuf>,
+}
+
 /// Options and flags which can be used to configure how a file is opened.
 ///
 /// This builder exposes the ability to configure how a [`File`] is opened and
@@ -310,6 +486,93 @@ pub struct DirBuilder {
     recursive: bool,
 }
 
+/// Options and flags which can be used to configure how files and directory
+/// trees are copied.
+///
+/// The [`copy_dir_all`] function of this module copies a directory tree with
+/// the default options. `CopyOptions` allows changing whether files are
+/// cloned, what happens to files that already exist at the destination, and
+/// which metadata is preserved, and reports the progress of a copy.
+///
+/// By default:
+///
+/// * files are [cloned](Reflink) where the file system supports it,
+/// * copying a file to a path that already exists fails with an
+///   [`AlreadyExists`] error,
+/// * symlinks are copied as symlinks rather than followed,
+/// * the permissions and the access and modification times of files and
+///   directories are preserved.
+///
+/// [`AlreadyExists`]: io::ErrorKind::AlreadyExists
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_dir)]
+/// use std::fs::{CopyOptions, Reflink};
+///
+/// fn main() -> std::io::Result<()> {
+///     let copied = CopyOptions::new()
+///         .reflink(Reflink::Never)
+///         .overwrite(true)
+///         .copy_dir_all("assets", "build/assets")?;
+///     println!("copied {copied} bytes");
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct CopyOptions {
+    reflink: Reflink,
+    overwrite: bool,
+    skip_existing: bool,
+    preserve_times: bool,
+    follow_symlinks: bool,
+}
+
+/// Whether [`CopyOptions`] clones files rather than copying their data.
+///
+/// A clone, also known as a reflink, shares its data with the original file
+/// until either of them is modified. This makes copying fast and doesn't use
+/// any additional space, but is only supported by some file systems, and only
+/// within a single file system.
+///
+/// # Platform-specific behavior
+///
+/// Files are cloned with the `FICLONE` ioctl on Linux and `fclonefileat` on
+/// Apple platforms. Other platforms do not support cloning. Note that
+/// [`copy`] may also clone files, or otherwise share their data, where the
+/// platform does so when copying.
+///
+/// Note that this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Reflink {
+    /// Clone files where possible, and copy their data otherwise.
+    #[default]
+    Auto,
+    /// Always clone files, and fail with an error where they cannot be
+    /// cloned.
+    Always,
+    /// Always copy the data of files, so that copies never share it with
+    /// the original.
+    Never,
+}
+
+/// The progress of a copy, reported by
+/// [`CopyOptions::copy_dir_all_with_progress`] after each entry that was
+/// copied.
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Debug)]
+pub struct CopyProgress<'a> {
+    from: &'a Path,
+    to: &'a Path,
+    bytes_copied: u64,
+    total_bytes_copied: u64,
+}
+
 /// Reads the entire contents of a file into a bytes vector.
 ///
 /// This is a convenience function for using [`File::open`] and [`read_to_end`]
@@ -423,6 +686,38 @@ fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
     inner(path.as_ref(), contents.as_ref())
 }
 
+/// Atomically replaces the contents of a file with a slice.
+///
+/// Unlike [`write`], this never leaves the file partially written: it either
+/// has its previous contents or `contents`, even if the program or the system
+/// crashes in between. The file is created if it does not exist.
+///
+/// This is a convenience function for using [`File::create_atomic`],
+/// [`write_all`] and [`AtomicFile::persist`] with fewer imports.
+///
+/// [`write_all`]: Write::write_all
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_atomic_write)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::write_atomic("config.toml", "verbose = true\n")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_atomic_write", issue = "none")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut file = File::create_atomic(path)?;
+        file.write_all(contents)?;
+        file.persist()
+    }
+    inner(path.as_ref(), contents.as_ref())
+}
+
 /// Changes the timestamps of the file or directory at the specified path.
 ///
 /// This function will attempt to set the access and modification times
@@ -712,6 +1007,45 @@ pub fn create_new<P: AsRef<Path>>(path: P) -> io::Result<File> {
         OpenOptions::new().read(true).write(true).create_new(true).open(path.as_ref())
     }
 
+    /// Creates a file in write-only mode that atomically replaces the file at
+    /// `path` once it is [persisted](AtomicFile::persist).
+    ///
+    /// The file is empty and created in the same directory as `path`, which
+    /// must exist. The file at `path` does not need to exist, and is not
+    /// changed until the returned [`AtomicFile`] is persisted.
+    ///
+    /// The new file is created with the default permissions for new files.
+    /// If a file exists at `path` when the new one is persisted, its
+    /// permissions are copied over first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`] error if `path` does not name a file, and
+    /// otherwise the same errors as [`File::create_new`] for the temporary
+    /// file.
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_atomic_write)]
+    /// use std::fs::File;
+    /// use std::io::Write;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut f = File::create_atomic("state.json")?;
+    ///     writeln!(f, "{{\"count\": 1}}")?;
+    ///     // Until here, "state.json" still has its previous contents.
+    ///     f.persist()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_atomic_write", issue = "none")]
+    pub fn create_atomic<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        AtomicFile::new(path.as_ref())
+    }
+
     /// Returns a new OpenOptions object.
     ///
     /// This function returns a new OpenOptions object that you can use to
@@ -1542,6 +1876,395 @@ fn stream_position(&mut self) -> io::Result<u64> {
 }
 impl crate::io::IoHandle for File {}
 
+impl AtomicFile {
+    fn new(path: &Path) -> io::Result<AtomicFile> {
+        let Some(file_name) = path.file_name() else {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "path does not name a file"));
+        };
+        let dir = atomic_file_dir(path);
+
+        if let Some(file) = fs_imp::open_tmpfile(dir)? {
+            let file = File::from_inner(file);
+            return Ok(AtomicFile { file, path: path.to_path_buf(), temp_path: None });
+        }
+
+        let prefix = atomic_file_prefix(file_name);
+        let (file, temp_path) = create_unique(dir, &prefix, ".tmp", |temp_path| {
+            OpenOptions::new().write(true).create_new(true).open(temp_path)
+        })?;
+        Ok(AtomicFile { file, path: path.to_path_buf(), temp_path: Some(temp_path) })
+    }
+
+    /// Returns the path of the file that this file replaces when it is
+    /// persisted.
+    #[must_use]
+    #[unstable(feature = "fs_atomic_write", issue = "none")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a reference to the underlying temporary file.
+    #[must_use]
+    #[unstable(feature = "fs_atomic_write", issue = "none")]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the underlying temporary file.
+    #[must_use]
+    #[unstable(feature = "fs_atomic_write", issue = "none")]
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Flushes the file to disk and atomically moves it to its target path,
+    /// replacing any file that is there.
+    ///
+    /// Once this returns successfully, the new contents are on disk, and the
+    /// directory entry pointing to them has been flushed as well where the
+    /// platform supports it.
+    ///
+    /// # Errors
+    ///
+    /// If this returns an error, the temporary file is removed and the file
+    /// at the target path is left untouched, unless the error occurred while
+    /// flushing the directory after the file was already moved.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_atomic_write)]
+    /// use std::fs::File;
+    /// use std::io::Write;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut f = File::create_atomic("data.bin")?;
+    ///     f.write_all(&[1, 2, 3])?;
+    ///     f.persist()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_atomic_write", issue = "none")]
+    pub fn persist(mut self) -> io::Result<()> {
+        // Keep the permissions of the file being replaced, rather than
+        // silently resetting them to the defaults.
+        if let Ok(metadata) = metadata(&self.path)
+            && metadata.is_file()
+        {
+            self.file.set_permissions(metadata.permissions())?;
+        }
+        self.file.sync_all()?;
+
+        let dir = atomic_file_dir(&self.path);
+        if self.temp_path.is_none() {
+            let prefix = atomic_file_prefix(self.path.file_name().unwrap());
+            let file = &self.file;
+            let ((), temp_path) = create_unique(dir, &prefix, ".tmp", |temp_path| {
+                fs_imp::link_tmpfile(file.as_inner(), temp_path)
+            })?;
+            self.temp_path = Some(temp_path);
+        }
+
+        // On failure, the temporary file is removed when `self` is dropped.
+        rename(self.temp_path.as_ref().unwrap(), &self.path)?;
+        self.temp_path = None;
+        fs_imp::sync_dir(dir)
+    }
+}
+
+/// Returns the directory in which the temporary file for an [`AtomicFile`]
+/// replacing `path` is created.
+fn atomic_file_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Returns the prefix of the temporary file names for an [`AtomicFile`]
+/// replacing the file `file_name`, which hides them on Unix platforms.
+fn atomic_file_prefix(file_name: &OsStr) -> OsString {
+    // The full name, with the dots and the 16 random digits and ".tmp" added
+    // by `create_unique`, must fit in the 255 bytes most file systems allow.
+    const MAX_LEN: usize = 255 - 2 - 16 - ".tmp".len();
+
+    let mut prefix = OsString::from(".");
+    if file_name.len() <= MAX_LEN {
+        prefix.push(file_name);
+    } else {
+        // The name only has to be unique, so it doesn't matter if it's lossy.
+        let file_name = file_name.to_string_lossy();
+        prefix.push(&file_name[..file_name.floor_char_boundary(MAX_LEN)]);
+    }
+    prefix.push(".");
+    prefix
+}
+
+/// Creates an entry with a random name in `dir` using `create`, which must
+/// fail with `AlreadyExists` if the entry exists, and retries with a new name
+/// if it does.
+fn create_unique<T>(
+    dir: &Path,
+    prefix: &OsStr,
+    suffix: &str,
+    mut create: impl FnMut(&Path) -> io::Result<T>,
+) -> io::Result<(T, PathBuf)> {
+    const ATTEMPTS: u32 = 64;
+
+    for _ in 0..ATTEMPTS {
+        let mut name = prefix.to_os_string();
+        name.push(format!("{:016x}{suffix}", crate::random::random::<u64>(..)));
+        let path = dir.join(name);
+        match create(&path) {
+            Ok(t) => return Ok((t, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::const_error!(io::ErrorKind::AlreadyExists, "too many temporary files exist"))
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "none")]
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if let Some(temp_path) = &self.temp_path {
+            let _ = remove_file(temp_path);
+        }
+    }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "none")]
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.file.is_write_vectored()
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "none")]
+impl Seek for AtomicFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.file.stream_position()
+    }
+}
+
+impl TempFile {
+    /// Creates a new, empty file in the directory returned by
+    /// [`env::temp_dir`](crate::env::temp_dir).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created, for example because
+    /// the temporary directory does not exist or is not writable.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<TempFile> {
+        TempFile::new_in(crate::env::temp_dir())
+    }
+
+    /// Creates a new, empty file in the directory `dir`.
+    ///
+    /// This is useful to create the file on the same file system as its
+    /// final destination, so that it can be [renamed](rename) there once it
+    /// is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created, for example because
+    /// `dir` does not exist or is not writable.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<TempFile> {
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+        #[cfg(unix)]
+        options.as_inner_mut().mode(0o600);
+
+        let (file, path) =
+            create_unique(dir.as_ref(), OsStr::new(".tmp"), "", |path| options.open(path))?;
+        Ok(TempFile { file, path: Some(path) })
+    }
+
+    /// Returns the path of the file.
+    #[must_use]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().unwrap()
+    }
+
+    /// Returns a reference to the underlying file.
+    #[must_use]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the underlying file.
+    #[must_use]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Closes the file and returns its path, without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_temp)]
+    /// use std::fs::{self, TempFile};
+    /// use std::io::Write;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut file = TempFile::new()?;
+    ///     file.write_all(b"report")?;
+    ///     let path = file.into_path();
+    ///     assert_eq!(fs::read(&path)?, b"report");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use = "the file is not removed, and can only be found again through the returned path"]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn into_path(mut self) -> PathBuf {
+        self.path.take().unwrap()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = remove_file(path);
+        }
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Read for TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.file.read_vectored(bufs)
+    }
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        self.file.read_buf(cursor)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.file.is_read_vectored()
+    }
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.file.read_to_end(buf)
+    }
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.file.read_to_string(buf)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.file.is_write_vectored()
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Seek for TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+    fn stream_len(&mut self) -> io::Result<u64> {
+        self.file.stream_len()
+    }
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.file.stream_position()
+    }
+}
+
+impl TempDir {
+    /// Creates a new, empty directory in the directory returned by
+    /// [`env::temp_dir`](crate::env::temp_dir).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created, for example
+    /// because the temporary directory does not exist or is not writable.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<TempDir> {
+        TempDir::new_in(crate::env::temp_dir())
+    }
+
+    /// Creates a new, empty directory in the directory `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created, for example
+    /// because `dir` does not exist or is not writable.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<TempDir> {
+        let mut builder = DirBuilder::new();
+        #[cfg(unix)]
+        builder.as_inner_mut().set_mode(0o700);
+
+        let ((), path) =
+            create_unique(dir.as_ref(), OsStr::new(".tmp"), "", |path| builder.create(path))?;
+        Ok(TempDir { path: Some(path) })
+    }
+
+    /// Returns the path of the directory.
+    #[must_use]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().unwrap()
+    }
+
+    /// Returns the path of the directory, without removing it.
+    #[must_use = "the directory is not removed, and can only be found again through the returned path"]
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn into_path(mut self) -> PathBuf {
+        self.path.take().unwrap()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = remove_dir_all(path);
+        }
+    }
+}
+
 impl Dir {
     /// Attempts to open a directory at `path` in read-only mode.
     ///
@@ -2626,6 +3349,253 @@ fn as_inner(&self) -> &fs_imp::DirEntry {
     }
 }
 
+impl WalkDir {
+    /// Sets the maximum depth of entries to return.
+    ///
+    /// Entries directly inside the root directory have a depth of 1, so a
+    /// maximum depth of 1 returns the same entries as [`read_dir`]. Directories
+    /// at the maximum depth are returned, but not read. By default the depth is
+    /// not limited.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symlinks to directories are followed.
+    ///
+    /// When set, the contents of a directory a symlink points to are
+    /// returned as if the symlink was the directory itself. If a symlink
+    /// points to one of the directories containing it, an error of kind
+    /// [`FilesystemLoop`] is returned for it instead of walking the same
+    /// directories forever.
+    ///
+    /// When not set, which is the default, symlinks are returned like any
+    /// other file. The root directory is always followed if it is a symlink.
+    ///
+    /// [`FilesystemLoop`]: io::ErrorKind::FilesystemLoop
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_symlinks(mut self, follow: bool) -> WalkDir {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Sets whether the entries of each directory are returned sorted by
+    /// their [file name](DirEntry::file_name).
+    ///
+    /// This reads all entries of a directory before returning any of them.
+    /// Errors reading a directory are returned before its entries.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by_file_name(mut self, sort: bool) -> WalkDir {
+        self.sort_by_file_name = sort;
+        self
+    }
+
+    /// Sets whether each directory is returned after its contents, rather
+    /// than before them.
+    ///
+    /// This is useful to process a directory tree bottom-up, for example to
+    /// remove directories once they are empty.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn contents_first(mut self, contents_first: bool) -> WalkDir {
+        self.contents_first = contents_first;
+        self
+    }
+
+    /// Skips the remaining contents of a directory.
+    ///
+    /// If the entry returned last is a directory, its contents are skipped.
+    /// Otherwise, the remaining entries of the directory containing it are
+    /// skipped. This makes it possible to prune subtrees that are not of
+    /// interest without reading them.
+    ///
+    /// When returning directories after their contents, the contents of a
+    /// directory have already been returned, so only the remaining entries of
+    /// the directory being read are skipped. The directory itself is still
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut walk = fs::walk_dir(".");
+    ///     while let Some(entry) = walk.next() {
+    ///         let entry = entry?;
+    ///         if entry.file_name() == ".git" {
+    ///             walk.skip_current_dir();
+    ///             continue;
+    ///         }
+    ///         println!("{}", entry.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn skip_current_dir(&mut self) {
+        self.deferred_error = None;
+        if self.skip_top
+            && let Some(frame) = self.stack.last_mut()
+        {
+            frame.entries = WalkDirEntries::Done;
+        }
+    }
+
+    fn push_dir(
+        &mut self,
+        dir: fs_imp::ReadDir,
+        entry: Option<DirEntry>,
+        canonical_path: Option<PathBuf>,
+    ) {
+        let dir = ReadDir(dir);
+        let entries = if self.sort_by_file_name {
+            let mut entries: Vec<_> = dir.collect();
+            entries.sort_by_cached_key(|entry| entry.as_ref().ok().map(DirEntry::file_name));
+            WalkDirEntries::Sorted(entries.into_iter())
+        } else {
+            WalkDirEntries::Unsorted(dir)
+        };
+        self.stack.push(WalkDirFrame { entries, dir: entry, canonical_path });
+    }
+
+    /// Opens `entry` if it is a directory that should be walked, and pushes
+    /// it onto the stack.
+    ///
+    /// Returns what the iterator should return next, or `None` if the entry
+    /// is returned after its contents.
+    fn descend(&mut self, entry: DirEntry) -> Option<io::Result<DirEntry>> {
+        // Unless the entry turns out to be a directory, skipping means
+        // skipping the rest of the directory containing it.
+        self.skip_top = true;
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(e) => return self.defer_error(entry, e),
+        };
+
+        let parent_path = self.stack.last().and_then(|frame| frame.canonical_path.as_deref());
+        let (follow, canonical_path) = if file_type.is_dir() {
+            (false, parent_path.map(|parent| parent.join(entry.file_name())))
+        } else if file_type.is_symlink() && self.follow_symlinks {
+            // Symlinks to anything but a directory, including dangling ones,
+            // are returned like files.
+            if !metadata(entry.path()).is_ok_and(|metadata| metadata.is_dir()) {
+                return Some(Ok(entry));
+            }
+            (true, None)
+        } else {
+            return Some(Ok(entry));
+        };
+
+        if self.stack.len() >= self.max_depth {
+            self.skip_top = false;
+            return Some(Ok(entry));
+        }
+        let canonical_path = if follow {
+            let target = match canonicalize(entry.path()) {
+                Ok(target) => target,
+                Err(e) => return self.defer_error(entry, e),
+            };
+            if self.stack.iter().any(|frame| frame.canonical_path.as_ref() == Some(&target)) {
+                let e = io::const_error!(
+                    io::ErrorKind::FilesystemLoop,
+                    "symlink points to a directory containing it",
+                );
+                return self.defer_error(entry, e);
+            }
+            Some(target)
+        } else {
+            canonical_path
+        };
+
+        match fs_imp::read_dir_at(&entry.0, follow) {
+            Ok(dir) if self.contents_first => {
+                self.push_dir(dir, Some(entry), canonical_path);
+                None
+            }
+            Ok(dir) => {
+                self.push_dir(dir, None, canonical_path);
+                Some(Ok(entry))
+            }
+            Err(e) => self.defer_error(entry, e),
+        }
+    }
+
+    /// Returns `entry` and then `error`, or the other way around if
+    /// directories are returned after their contents.
+    fn defer_error(&mut self, entry: DirEntry, error: io::Error) -> Option<io::Result<DirEntry>> {
+        if self.contents_first {
+            let frame = WalkDirFrame {
+                entries: WalkDirEntries::Done,
+                dir: Some(entry),
+                canonical_path: None,
+            };
+            self.stack.push(frame);
+            Some(Err(error))
+        } else {
+            self.skip_top = false;
+            self.deferred_error = Some(error);
+            Some(Ok(entry))
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDir {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        if let Some(e) = self.deferred_error.take() {
+            return Some(Err(e));
+        }
+        if let Some(root) = self.root.take() {
+            if self.max_depth == 0 {
+                return None;
+            }
+            let canonical_path = if self.follow_symlinks {
+                match canonicalize(&root) {
+                    Ok(path) => Some(path),
+                    Err(e) => return Some(Err(e)),
+                }
+            } else {
+                None
+            };
+            match fs_imp::read_dir(&root) {
+                Ok(dir) => self.push_dir(dir, None, canonical_path),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let next = match &mut frame.entries {
+                WalkDirEntries::Unsorted(dir) => dir.next(),
+                WalkDirEntries::Sorted(entries) => entries.next(),
+                WalkDirEntries::Done => None,
+            };
+            match next {
+                Some(Ok(entry)) => {
+                    if let Some(next) = self.descend(entry) {
+                        return Some(next);
+                    }
+                }
+                Some(Err(e)) => {
+                    self.skip_top = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    let frame = self.stack.pop().unwrap();
+                    if let Some(dir) = frame.dir {
+                        self.skip_top = true;
+                        return Some(Ok(dir));
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Removes a file from the filesystem.
 ///
 /// Note that there is no
@@ -2853,6 +3823,48 @@ pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
     fs_imp::copy(from.as_ref(), to.as_ref())
 }
 
+/// Recursively copies the directory `from` and all of its contents to `to`.
+///
+/// `to` is created if it does not exist, and the contents of `from` are
+/// merged into it if it does. Symlinks are copied as symlinks, and the
+/// permissions and timestamps of files and directories are preserved. On
+/// success, the total number of bytes copied is returned.
+///
+/// This is a convenience function for [`CopyOptions::copy_dir_all`] with the
+/// default options. Use [`CopyOptions`] to change how files are copied, or
+/// to be notified of the progress.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `from` is not a directory.
+/// * `to` is inside of `from`.
+/// * A file or symlink in `from` already exists in `to`.
+/// * The current process does not have the permission rights to read
+///   `from` or write `to`.
+///
+/// The copy stops at the first error, leaving behind whatever was copied
+/// until then.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::copy_dir_all("template", "project")?;
+///     Ok(())
+/// }
+/// ```
+#[doc(alias = "cp")]
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    CopyOptions::new().copy_dir_all(from, to)
+}
+
 /// Creates a new hard link on the filesystem.
 ///
 /// The `link` path will be a link pointing to the `original` path. Note that
@@ -3284,6 +4296,63 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::read_dir(path.as_ref()).map(ReadDir)
 }
 
+/// Returns an iterator over all entries below a directory, recursively.
+///
+/// The returned [`WalkDir`] reads the directory at `path` and every directory
+/// inside it. Its builder methods limit the depth of the walk, follow
+/// symlinks, sort entries by name, return directories after their contents,
+/// and prune subtrees. Nothing is read until the iteration starts, and the
+/// root directory itself is not returned.
+///
+/// # Platform-specific behavior
+///
+/// On most Unix platforms, each directory is opened relative to the directory
+/// containing it, with `openat` and `fdopendir`, like [`remove_dir_all`]
+/// does. A directory that is replaced by a symlink while the tree is walked is
+/// therefore never followed unless symlinks are followed anyway. On other
+/// platforms, directories are opened by their path.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// The walk continues after errors, which are returned by the iterator as
+/// described for [`WalkDir`]. If `path` does not exist or is not a directory,
+/// the iterator returns that error and nothing else.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     // Print all Rust files below `src`, at most three levels deep, in a
+///     // reproducible order.
+///     for entry in fs::walk_dir("src").max_depth(3).sort_by_file_name(true) {
+///         let path = entry?.path();
+///         if path.extension().is_some_and(|ext| ext == "rs") {
+///             println!("{}", path.display());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir {
+        root: Some(path.as_ref().to_path_buf()),
+        max_depth: usize::MAX,
+        follow_symlinks: false,
+        sort_by_file_name: false,
+        contents_first: false,
+        stack: Vec::new(),
+        deferred_error: None,
+        skip_top: false,
+    }
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
@@ -3471,6 +4540,334 @@ fn as_inner_mut(&mut self) -> &mut fs_imp::DirBuilder {
     }
 }
 
+impl CopyOptions {
+    /// Creates a blank new set of options with the defaults described in
+    /// [`CopyOptions`].
+    #[must_use]
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn new() -> CopyOptions {
+        CopyOptions {
+            reflink: Reflink::Auto,
+            overwrite: false,
+            skip_existing: false,
+            preserve_times: true,
+            follow_symlinks: false,
+        }
+    }
+
+    /// Sets whether files are cloned rather than copied.
+    ///
+    /// The default is [`Reflink::Auto`].
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn reflink(&mut self, reflink: Reflink) -> &mut Self {
+        self.reflink = reflink;
+        self
+    }
+
+    /// Sets whether files and symlinks that already exist at the destination
+    /// are replaced.
+    ///
+    /// Existing files are removed before they are replaced, so that the copy
+    /// does not write through hard links or symlinks at the destination.
+    /// This takes precedence over [`skip_existing`](Self::skip_existing).
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn overwrite(&mut self, overwrite: bool) -> &mut Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Sets whether files and symlinks that already exist at the destination
+    /// are left as they are, rather than causing an error.
+    ///
+    /// Skipped files are not included in the number of bytes copied.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn skip_existing(&mut self, skip_existing: bool) -> &mut Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// Sets whether the access and modification times of files and
+    /// directories are preserved.
+    ///
+    /// Otherwise, copies have the time at which they were created. The times
+    /// of symlinks are never preserved.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn preserve_times(&mut self, preserve_times: bool) -> &mut Self {
+        self.preserve_times = preserve_times;
+        self
+    }
+
+    /// Sets whether symlinks inside of the source directory are followed,
+    /// copying what they point to rather than the symlinks themselves.
+    ///
+    /// The source directory itself is always followed if it is a symlink.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn follow_symlinks(&mut self, follow_symlinks: bool) -> &mut Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Copies the file `from` to `to` with these options, and returns the
+    /// number of bytes copied.
+    ///
+    /// Like [`copy`], this follows `from` if it is a symlink. If `to` exists
+    /// and is skipped, zero is returned.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`copy`], and an [`AlreadyExists`]
+    /// error if `to` exists and is neither overwritten nor skipped.
+    ///
+    /// [`AlreadyExists`]: io::ErrorKind::AlreadyExists
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+        Ok(self.copy_entry(from.as_ref(), to.as_ref(), false)?.unwrap_or(0))
+    }
+
+    /// Recursively copies the directory `from` and all of its contents to
+    /// `to` with these options, and returns the total number of bytes
+    /// copied.
+    ///
+    /// See [`copy_dir_all`](crate::fs::copy_dir_all) for details.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+        self.copy_dir_all_inner(from.as_ref(), to.as_ref(), &mut |_| {})
+    }
+
+    /// Like [`copy_dir_all`](Self::copy_dir_all), but calls `progress` after
+    /// each file, directory and symlink that was copied.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_copy_dir)]
+    /// use std::fs::CopyOptions;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     CopyOptions::new().copy_dir_all_with_progress("photos", "backup/photos", |p| {
+    ///         println!("{} ({} bytes so far)", p.to().display(), p.total_bytes_copied());
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn copy_dir_all_with_progress<P, Q, F>(
+        &self,
+        from: P,
+        to: Q,
+        mut progress: F,
+    ) -> io::Result<u64>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(&CopyProgress<'_>),
+    {
+        self.copy_dir_all_inner(from.as_ref(), to.as_ref(), &mut progress)
+    }
+
+    fn copy_dir_all_inner(
+        &self,
+        from: &Path,
+        to: &Path,
+        progress: &mut dyn FnMut(&CopyProgress<'_>),
+    ) -> io::Result<u64> {
+        let from_metadata = metadata(from)?;
+        if !from_metadata.is_dir() {
+            return Err(io::const_error!(
+                io::ErrorKind::NotADirectory,
+                "source is not a directory"
+            ));
+        }
+        let created = create_dir_for_copy(to)?;
+        if canonicalize(to)?.starts_with(canonicalize(from)?) {
+            if created {
+                let _ = remove_dir(to);
+            }
+            return Err(io::const_error!(
+                io::ErrorKind::InvalidInput,
+                "cannot copy a directory into itself",
+            ));
+        }
+
+        // The permissions and times of directories are set once their
+        // contents have been copied, so that read-only directories can be
+        // filled and copying into them doesn't change their times.
+        let mut dirs = vec![(from_metadata, to.to_path_buf())];
+        let mut total_bytes_copied = 0;
+        for entry in walk_dir(from).follow_symlinks(self.follow_symlinks) {
+            let entry = entry?;
+            let entry_from = entry.path();
+            let entry_to = to.join(entry_from.strip_prefix(from).unwrap());
+
+            let mut file_type = entry.file_type()?;
+            let mut entry_metadata = None;
+            if file_type.is_symlink() && self.follow_symlinks {
+                let followed = metadata(&entry_from)?;
+                file_type = followed.file_type();
+                entry_metadata = Some(followed);
+            }
+
+            let bytes_copied = if file_type.is_dir() {
+                create_dir_for_copy(&entry_to)?;
+                let entry_metadata = match entry_metadata {
+                    Some(entry_metadata) => entry_metadata,
+                    None => entry.metadata()?,
+                };
+                dirs.push((entry_metadata, entry_to.clone()));
+                0
+            } else {
+                let copy_symlink = file_type.is_symlink();
+                match self.copy_entry(&entry_from, &entry_to, copy_symlink)? {
+                    Some(bytes_copied) => bytes_copied,
+                    None => continue,
+                }
+            };
+
+            total_bytes_copied += bytes_copied;
+            progress(&CopyProgress {
+                from: &entry_from,
+                to: &entry_to,
+                bytes_copied,
+                total_bytes_copied,
+            });
+        }
+
+        for (dir_metadata, dir) in dirs.iter().rev() {
+            set_permissions(dir, dir_metadata.permissions())?;
+            if self.preserve_times {
+                set_times(dir, copy_times(dir_metadata))?;
+            }
+        }
+        Ok(total_bytes_copied)
+    }
+
+    /// Copies the file or symlink `from` to `to`, and returns the number of
+    /// bytes copied, or `None` if `to` already exists and was skipped.
+    fn copy_entry(&self, from: &Path, to: &Path, copy_symlink: bool) -> io::Result<Option<u64>> {
+        match symlink_metadata(to) {
+            Ok(to_metadata) if self.overwrite => {
+                // Don't remove the file that is about to be copied.
+                if !to_metadata.is_symlink()
+                    && matches!((canonicalize(from), canonicalize(to)), (Ok(a), Ok(b)) if a == b)
+                {
+                    return Err(io::const_error!(
+                        io::ErrorKind::InvalidInput,
+                        "source and destination are the same file",
+                    ));
+                }
+                remove_file(to)?
+            }
+            Ok(_) if self.skip_existing => return Ok(None),
+            Ok(_) => {
+                return Err(io::const_error!(
+                    io::ErrorKind::AlreadyExists,
+                    "destination already exists",
+                ));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        if copy_symlink {
+            fs_imp::copy_symlink(from, to)?;
+            return Ok(Some(0));
+        }
+
+        let bytes_copied = match self.reflink {
+            // Rather than guessing which errors mean that the file system
+            // can't clone files, fall back to copying on any error. A genuine
+            // error will occur again.
+            Reflink::Auto => {
+                fs_imp::clone_file(from, to).or_else(|_| copy_to_new_file(from, to))?
+            }
+            Reflink::Always => fs_imp::clone_file(from, to)?,
+            Reflink::Never => copy_to_new_file(from, to)?,
+        };
+        if self.preserve_times {
+            set_times(to, copy_times(&metadata(from)?))?;
+        }
+        Ok(Some(bytes_copied))
+    }
+}
+
+/// Copies the contents and permissions of the file `from` to the new file
+/// `to`, without sharing their data.
+///
+/// Like cloning, this fails if `to` exists, so that neither an existing file
+/// nor the target of a symlink is written to.
+fn copy_to_new_file(from: &Path, to: &Path) -> io::Result<u64> {
+    let mut reader = File::open(from)?;
+    let reader_metadata = reader.metadata()?;
+    if !reader_metadata.is_file() {
+        return Err(fs_imp::common::NOT_FILE_ERROR);
+    }
+    let mut writer = OpenOptions::new().write(true).create_new(true).open(to)?;
+    writer.set_permissions(reader_metadata.permissions())?;
+    // Not `io::copy`, which may share the data of the files.
+    io::copy::generic_copy(&mut reader, &mut writer)
+}
+
+/// Creates the directory `path` for a copy, unless it is an existing
+/// directory, and returns whether it was created.
+fn create_dir_for_copy(path: &Path) -> io::Result<bool> {
+    match create_dir(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists && metadata(path)?.is_dir() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the times of a file with `metadata` that are preserved by a copy.
+fn copy_times(metadata: &Metadata) -> FileTimes {
+    let mut times = FileTimes::new();
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    times
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl Default for CopyOptions {
+    fn default() -> CopyOptions {
+        CopyOptions::new()
+    }
+}
+
+impl CopyProgress<'_> {
+    /// Returns the path of the entry that was copied.
+    #[must_use]
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn from(&self) -> &Path {
+        self.from
+    }
+
+    /// Returns the path the entry was copied to.
+    #[must_use]
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn to(&self) -> &Path {
+        self.to
+    }
+
+    /// Returns the number of bytes copied for this entry, which is zero for
+    /// directories and symlinks.
+    #[must_use]
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn bytes_copied(&self) -> u64 {
+        self.bytes_copied
+    }
+
+    /// Returns the total number of bytes copied so far, including this
+    /// entry.
+    #[must_use]
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn total_bytes_copied(&self) -> u64 {
+        self.total_bytes_copied
+    }
+}
+
 /// Returns `Ok(true)` if the path points at an existing entity.
 ///
 /// This function will traverse symbolic links to query information about the
@@ -1,7 +1,7 @@
 use rand::RngCore;
 
 use super::Dir;
-use crate::fs::{self, File, FileTimes, OpenOptions, TryLockError};
+use crate::fs::{self, CopyOptions, File, FileTimes, OpenOptions, Reflink, TryLockError};
 use crate::io::prelude::*;
 use crate::io::{BorrowedBuf, ErrorKind, SeekFrom};
 use crate::mem::MaybeUninit;
@@ -887,6 +887,134 @@ fn recursive_mkdir_empty() {
     check!(fs::create_dir_all(Path::new("")));
 }
 
+fn walked_paths(walk: fs::WalkDir, root: &Path) -> Vec<String> {
+    walk.map(|entry| match entry {
+        Ok(entry) => {
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap();
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_str().unwrap())
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+        Err(e) => format!("error: {:?}", e.kind()),
+    })
+    .collect()
+}
+
+#[test]
+fn walk_dir() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("root");
+    check!(fs::create_dir_all(root.join("a/b/c")));
+    check!(fs::create_dir_all(root.join("d")));
+    check!(fs::write(root.join("a/f1"), ""));
+    check!(fs::write(root.join("a/b/f2"), ""));
+    check!(fs::write(root.join("z"), ""));
+
+    let walk = || fs::walk_dir(&root).sort_by_file_name(true);
+    assert_eq!(walked_paths(walk(), &root), ["a", "a/b", "a/b/c", "a/b/f2", "a/f1", "d", "z"]);
+    assert_eq!(
+        walked_paths(walk().contents_first(true), &root),
+        ["a/b/c", "a/b/f2", "a/b", "a/f1", "a", "d", "z"]
+    );
+    assert_eq!(walked_paths(walk().max_depth(1), &root), ["a", "d", "z"]);
+    assert_eq!(walked_paths(walk().max_depth(2), &root), ["a", "a/b", "a/f1", "d", "z"]);
+    assert_eq!(walked_paths(walk().max_depth(0), &root), Vec::<String>::new());
+
+    let mut unsorted = walked_paths(fs::walk_dir(&root), &root);
+    unsorted.sort();
+    assert_eq!(unsorted, ["a", "a/b", "a/b/c", "a/b/f2", "a/f1", "d", "z"]);
+
+    // Skipping a directory skips its contents, skipping a file skips the
+    // rest of its directory.
+    let mut walk = walk();
+    let mut paths = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "b" || entry.file_name() == "d" {
+            walk.skip_current_dir();
+        }
+        paths.push(entry.file_name().into_string().unwrap());
+    }
+    assert_eq!(paths, ["a", "b", "f1", "d", "z"]);
+
+    let mut walk = walk();
+    let mut paths = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "c" {
+            walk.skip_current_dir();
+            walk.skip_current_dir();
+        } else if entry.file_name() == "f1" {
+            walk.skip_current_dir();
+        }
+        paths.push(entry.file_name().into_string().unwrap());
+    }
+    assert_eq!(paths, ["a", "b", "c", "f2", "f1", "d", "z"]);
+
+    assert_eq!(walked_paths(fs::walk_dir(tmpdir.join("missing")), &root), ["error: NotFound"]);
+    let mut walk = fs::walk_dir(root.join("z"));
+    assert!(walk.next().unwrap().is_err());
+    assert!(walk.next().is_none());
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let root = tmpdir.join("root");
+    check!(fs::create_dir_all(root.join("a/b")));
+    check!(fs::create_dir_all(root.join("d")));
+    check!(fs::write(root.join("d/f"), ""));
+    check!(symlink_dir(&root, root.join("a/loop")));
+    check!(symlink_dir(root.join("d"), root.join("a/b/to_d")));
+    check!(symlink_file(root.join("missing"), root.join("d/dangling")));
+
+    let walk = || fs::walk_dir(&root).sort_by_file_name(true);
+    assert_eq!(
+        walked_paths(walk(), &root),
+        ["a", "a/b", "a/b/to_d", "a/loop", "d", "d/dangling", "d/f"]
+    );
+    assert_eq!(
+        walked_paths(walk().follow_symlinks(true), &root),
+        [
+            "a",
+            "a/b",
+            "a/b/to_d",
+            "a/b/to_d/dangling",
+            "a/b/to_d/f",
+            "a/loop",
+            "error: FilesystemLoop",
+            "d",
+            "d/dangling",
+            "d/f",
+        ]
+    );
+    assert_eq!(
+        walked_paths(walk().follow_symlinks(true).contents_first(true), &root),
+        [
+            "a/b/to_d/dangling",
+            "a/b/to_d/f",
+            "a/b/to_d",
+            "a/b",
+            "error: FilesystemLoop",
+            "a/loop",
+            "a",
+            "d/dangling",
+            "d/f",
+            "d",
+        ]
+    );
+}
+
 #[test]
 #[cfg_attr(
     all(windows, target_arch = "aarch64"),
@@ -2565,3 +2693,267 @@ fn test_dir_metadata() {
     let metadata = check!(dir.metadata());
     assert!(metadata.is_dir());
 }
+
+#[test]
+fn write_atomic() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("config.toml");
+
+    check!(fs::write_atomic(&path, b"first"));
+    assert_eq!(check!(fs::read(&path)), b"first");
+    check!(fs::write_atomic(&path, b"second"));
+    assert_eq!(check!(fs::read(&path)), b"second");
+
+    // No temporary files are left behind.
+    let names: Vec<_> =
+        check!(fs::read_dir(tmpdir.path())).map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(names, ["config.toml"]);
+}
+
+#[test]
+fn create_atomic_drop_without_persist() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("state");
+    check!(fs::write(&path, b"old"));
+
+    let mut f = check!(File::create_atomic(&path));
+    assert_eq!(f.path(), path);
+    check!(f.write_all(b"new"));
+    assert_eq!(check!(fs::read(&path)), b"old");
+    drop(f);
+
+    assert_eq!(check!(fs::read(&path)), b"old");
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn create_atomic_persist() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("data");
+
+    let mut f = check!(File::create_atomic(&path));
+    check!(f.write_all(b"hello world"));
+    check!(f.seek(SeekFrom::Start(6)));
+    check!(f.write_all(b"there"));
+    assert!(!path.exists());
+    check!(f.persist());
+
+    assert_eq!(check!(fs::read(&path)), b"hello there");
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+#[cfg(unix)]
+fn write_atomic_keeps_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("secret");
+    check!(fs::write(&path, b"old"));
+    check!(fs::set_permissions(&path, fs::Permissions::from_mode(0o600)));
+
+    check!(fs::write_atomic(&path, b"new"));
+    assert_eq!(check!(fs::read(&path)), b"new");
+    assert_eq!(check!(fs::metadata(&path)).permissions().mode() & 0o777, 0o600);
+}
+
+#[test]
+fn write_atomic_long_name() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("a".repeat(250));
+
+    check!(fs::write_atomic(&path, b"data"));
+    assert_eq!(check!(fs::read(&path)), b"data");
+}
+
+#[test]
+fn create_atomic_errors() {
+    let tmpdir = tmpdir();
+    let err = File::create_atomic(tmpdir.join("..")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(File::create_atomic(tmpdir.join("missing").join("file")).is_err());
+}
+
+#[test]
+fn temp_file() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::TempFile::new_in(tmpdir.path()));
+    let path = file.path().to_path_buf();
+    assert_eq!(path.parent(), Some(tmpdir.path()));
+    assert!(path.is_file());
+
+    check!(file.write_all(b"scratch"));
+    check!(file.seek(SeekFrom::Start(0)));
+    let mut contents = String::new();
+    check!(file.read_to_string(&mut contents));
+    assert_eq!(contents, "scratch");
+
+    let other = check!(fs::TempFile::new_in(tmpdir.path()));
+    assert_ne!(other.path(), path);
+
+    drop(file);
+    assert!(!path.exists());
+    drop(other);
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 0);
+}
+
+#[test]
+fn temp_file_into_path() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::TempFile::new_in(tmpdir.path()));
+    check!(file.write_all(b"kept"));
+    let path = file.into_path();
+    assert_eq!(check!(fs::read(&path)), b"kept");
+}
+
+#[test]
+#[cfg(unix)]
+fn temp_file_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let file = check!(fs::TempFile::new_in(tmpdir.path()));
+    assert_eq!(check!(file.as_file().metadata()).permissions().mode() & 0o777, 0o600);
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    assert_eq!(check!(dir.path().metadata()).permissions().mode() & 0o777, 0o700);
+}
+
+#[test]
+fn temp_dir() {
+    let tmpdir = tmpdir();
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    let path = dir.path().to_path_buf();
+    assert!(path.is_dir());
+
+    check!(fs::create_dir(path.join("sub")));
+    check!(fs::write(path.join("sub").join("file"), b"contents"));
+    drop(dir);
+    assert!(!path.exists());
+
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    let path = dir.into_path();
+    assert!(path.is_dir());
+
+    assert!(fs::TempDir::new_in(tmpdir.join("missing")).is_err());
+}
+
+#[test]
+fn copy_dir_all() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    check!(fs::create_dir_all(from.join("a").join("b")));
+    check!(fs::write(from.join("top.txt"), b"top"));
+    check!(fs::write(from.join("a").join("b").join("deep.txt"), b"deep contents"));
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    check!(fs::set_times(from.join("top.txt"), FileTimes::new().set_modified(modified)));
+    #[cfg(unix)]
+    check!(symlink_file("top.txt", from.join("link")));
+
+    let to = tmpdir.join("to");
+    assert_eq!(check!(fs::copy_dir_all(&from, &to)), 16);
+    assert_eq!(check!(fs::read(to.join("top.txt"))), b"top");
+    assert_eq!(check!(fs::read(to.join("a").join("b").join("deep.txt"))), b"deep contents");
+    assert_eq!(check!(check!(fs::metadata(to.join("top.txt"))).modified()), modified);
+    #[cfg(unix)]
+    {
+        assert!(check!(fs::symlink_metadata(to.join("link"))).is_symlink());
+        assert_eq!(check!(fs::read_link(to.join("link"))), Path::new("top.txt"));
+    }
+
+    // Files that exist already are an error by default.
+    let err = fs::copy_dir_all(&from, &to).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+}
+
+#[test]
+fn copy_dir_all_into_itself() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    check!(fs::create_dir(&from));
+    check!(fs::write(from.join("file"), b"contents"));
+
+    let err = fs::copy_dir_all(&from, from.join("inner")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!from.join("inner").exists());
+    let err = fs::copy_dir_all(&from, &from).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(fs::copy_dir_all(from.join("file"), tmpdir.join("to")).is_err());
+}
+
+#[test]
+fn copy_options_existing() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    let to = tmpdir.join("to");
+    check!(fs::create_dir(&from));
+    check!(fs::create_dir(&to));
+    check!(fs::write(from.join("a"), b"new a"));
+    check!(fs::write(from.join("b"), b"new b"));
+    check!(fs::write(to.join("a"), b"old a"));
+
+    let copied = check!(CopyOptions::new().skip_existing(true).copy_dir_all(&from, &to));
+    assert_eq!(copied, 5);
+    assert_eq!(check!(fs::read(to.join("a"))), b"old a");
+    assert_eq!(check!(fs::read(to.join("b"))), b"new b");
+
+    let copied = check!(CopyOptions::new().overwrite(true).copy_dir_all(&from, &to));
+    assert_eq!(copied, 10);
+    assert_eq!(check!(fs::read(to.join("a"))), b"new a");
+
+    let err = CopyOptions::new().overwrite(true).copy_file(from.join("a"), from.join("a"));
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(check!(fs::read(from.join("a"))), b"new a");
+}
+
+#[test]
+fn copy_options_progress() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    check!(fs::create_dir_all(from.join("dir")));
+    check!(fs::write(from.join("one"), b"1"));
+    check!(fs::write(from.join("dir").join("two"), b"22"));
+
+    let to = tmpdir.join("to");
+    let mut reported = Vec::new();
+    let copied = check!(CopyOptions::new().reflink(Reflink::Never).copy_dir_all_with_progress(
+        &from,
+        &to,
+        |progress| {
+            assert!(progress.to().exists());
+            reported.push((
+                progress.from().strip_prefix(&from).unwrap().to_path_buf(),
+                progress.bytes_copied(),
+            ));
+        },
+    ));
+    assert_eq!(copied, 3);
+    reported.sort();
+    assert_eq!(
+        reported,
+        [(Path::new("dir").into(), 0), (Path::new("dir").join("two"), 2), ("one".into(), 1)]
+    );
+}
+
+#[test]
+fn copy_options_reflink() {
+    let tmpdir = tmpdir();
+    let from = tmpdir.join("from");
+    check!(fs::write(&from, b"contents"));
+
+    let to = tmpdir.join("never");
+    assert_eq!(check!(CopyOptions::new().reflink(Reflink::Never).copy_file(&from, &to)), 8);
+    assert_eq!(check!(fs::read(&to)), b"contents");
+
+    // Whether files can be cloned depends on the file system.
+    let to = tmpdir.join("always");
+    match CopyOptions::new().reflink(Reflink::Always).copy_file(&from, &to) {
+        Ok(_) => assert_eq!(check!(fs::read(&to)), b"contents"),
+        // A failed clone leaves nothing behind, so copying can be retried.
+        Err(_) => {
+            assert!(!check!(to.try_exists()));
+            let copied = check!(CopyOptions::new().reflink(Reflink::Auto).copy_file(&from, &to));
+            assert_eq!(copied, 8);
+            assert_eq!(check!(fs::read(&to)), b"contents");
+        }
+    }
+}
@@ -316,6 +316,9 @@
 pub use self::error::const_error;
 #[stable(feature = "anonymous_pipe", since = "1.87.0")]
 pub use self::pipe::{PipeReader, PipeWriter, pipe};
+#[cfg(unix)]
+#[unstable(feature = "io_poller", issue = "none")]
+pub use self::poller::{Event, Events, Interest, Poller};
 #[stable(feature = "is_terminal", since = "1.70.0")]
 pub use self::stdio::IsTerminal;
 pub(crate) use self::stdio::attempt_print_to_stderr;
@@ -342,6 +345,8 @@
 mod error;
 mod impls;
 mod pipe;
+#[cfg(unix)]
+mod poller;
 pub mod prelude;
 mod stdio;
 mod util;
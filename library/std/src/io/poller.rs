@@ -0,0 +1,341 @@
+#[cfg(test)]
+mod tests;
+
+use crate::os::fd::AsFd;
+use crate::sys::io as imp;
+use crate::time::Duration;
+use crate::{fmt, io, ops};
+
+/// Waits for readiness events on several sources at once.
+///
+/// A `Poller` multiplexes any number of sources, such as [`TcpStream`]s,
+/// [`UnixStream`]s or [`ChildStdout`]s, on a single thread. Each source is
+/// [registered](Poller::register) with a token that identifies it and the
+/// [`Interest`] it is polled for. [`poll`](Poller::poll) then blocks until at
+/// least one of the sources is ready, and reports which ones are.
+///
+/// Readiness is level-triggered: a source is reported as long as it is ready,
+/// for example for as long as there is unread data in a socket. The sources
+/// are usually put into non-blocking mode, so that they can be read from or
+/// written to until they return a [`WouldBlock`] error.
+///
+/// A `Poller` does not take ownership of the sources registered with it. They
+/// must be [deregistered](Poller::deregister) before they are closed.
+///
+/// # Platform-specific behavior
+///
+/// This currently uses `epoll` on Linux and Android, `kqueue` on Apple
+/// platforms, FreeBSD, NetBSD and OpenBSD, and `poll` on other Unix platforms.
+/// Note that this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+/// [`ChildStdout`]: crate::process::ChildStdout
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`UnixStream`]: crate::os::unix::net::UnixStream
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Examples
+///
+/// An echo server that handles all of its connections on one thread:
+///
+/// ```no_run
+/// #![feature(io_poller)]
+///
+/// use std::collections::HashMap;
+/// use std::io::{self, Events, Interest, Poller, Read, Write};
+/// use std::net::TcpListener;
+///
+/// fn main() -> io::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:8080")?;
+///     listener.set_nonblocking(true)?;
+///
+///     let poller = Poller::new()?;
+///     poller.register(&listener, 0, Interest::READABLE)?;
+///
+///     let mut connections = HashMap::new();
+///     let mut next_token = 1;
+///     let mut events = Events::with_capacity(128);
+///     let mut buf = [0; 4096];
+///     loop {
+///         poller.poll(&mut events, None)?;
+///         for event in events.iter() {
+///             if event.token() == 0 {
+///                 // Accept all pending connections.
+///                 while let Ok((stream, _)) = listener.accept() {
+///                     stream.set_nonblocking(true)?;
+///                     poller.register(&stream, next_token, Interest::READABLE)?;
+///                     connections.insert(next_token, stream);
+///                     next_token += 1;
+///                 }
+///             } else if let Some(stream) = connections.get_mut(&event.token()) {
+///                 match stream.read(&mut buf) {
+///                     Ok(0) | Err(_) => {
+///                         poller.deregister(stream)?;
+///                         connections.remove(&event.token());
+///                     }
+///                     Ok(n) => stream.write_all(&buf[..n])?,
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[unstable(feature = "io_poller", issue = "none")]
+pub struct Poller {
+    inner: imp::Poller,
+}
+
+/// The kinds of readiness a source is polled for.
+///
+/// Interests can be combined with `|`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[unstable(feature = "io_poller", issue = "none")]
+pub struct Interest(u8);
+
+/// A readiness event reported by [`Poller::poll`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[unstable(feature = "io_poller", issue = "none")]
+pub struct Event {
+    pub(crate) token: usize,
+    pub(crate) readable: bool,
+    pub(crate) writable: bool,
+    pub(crate) error: bool,
+}
+
+/// A buffer for the events reported by [`Poller::poll`].
+#[derive(Clone, Debug)]
+#[unstable(feature = "io_poller", issue = "none")]
+pub struct Events {
+    events: Vec<Event>,
+    capacity: usize,
+}
+
+impl Interest {
+    /// Interest in the source becoming readable.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const READABLE: Interest = Interest(0b01);
+
+    /// Interest in the source becoming writable.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const WRITABLE: Interest = Interest(0b10);
+
+    /// Returns `true` if this includes interest in readability.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const fn is_readable(self) -> bool {
+        self.0 & Interest::READABLE.0 != 0
+    }
+
+    /// Returns `true` if this includes interest in writability.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const fn is_writable(self) -> bool {
+        self.0 & Interest::WRITABLE.0 != 0
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl ops::BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, rhs: Interest) -> Interest {
+        Interest(self.0 | rhs.0)
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl ops::BitOrAssign for Interest {
+    fn bitor_assign(&mut self, rhs: Interest) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl fmt::Debug for Interest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.is_readable(), self.is_writable()) {
+            (true, true) => f.write_str("READABLE | WRITABLE"),
+            (true, false) => f.write_str("READABLE"),
+            (false, true) => f.write_str("WRITABLE"),
+            (false, false) => f.write_str("(empty)"),
+        }
+    }
+}
+
+impl Event {
+    /// Returns the token the source was registered with.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn token(&self) -> usize {
+        self.token
+    }
+
+    /// Returns `true` if the source is readable.
+    ///
+    /// This includes the case where the other end was closed, in which case a
+    /// read returns end of file.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Returns `true` if the source is writable.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Returns `true` if an error is pending on the source.
+    ///
+    /// For sockets, the error can be retrieved with `take_error`.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn is_error(&self) -> bool {
+        self.error
+    }
+}
+
+impl Events {
+    /// Creates a buffer that can hold up to `capacity` events.
+    ///
+    /// [`Poller::poll`] reports at most this many events per call. Any others
+    /// are reported by the next call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> Events {
+        assert!(capacity > 0, "`Events` must have a non-zero capacity");
+        Events { events: Vec::with_capacity(capacity), capacity }
+    }
+
+    /// Returns the maximum number of events this buffer can hold.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of events in this buffer.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if this buffer holds no events.
+    #[must_use]
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns an iterator over the events in this buffer.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn iter(&self) -> crate::slice::Iter<'_, Event> {
+        self.events.iter()
+    }
+
+    /// Removes all events from this buffer.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl<'a> IntoIterator for &'a Events {
+    type Item = &'a Event;
+    type IntoIter = crate::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Poller {
+    /// Creates a new poller without any registered sources.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn new() -> io::Result<Poller> {
+        imp::Poller::new().map(|inner| Poller { inner })
+    }
+
+    /// Registers `source` with this poller, to be polled for `interest`.
+    ///
+    /// Events for the source are reported with `token`, which is usually an
+    /// index or key to find the source again.
+    ///
+    /// # Errors
+    ///
+    /// A source must not be registered twice. Depending on the platform, this
+    /// either returns an [`AlreadyExists`](io::ErrorKind::AlreadyExists) error
+    /// or replaces the earlier registration.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn register<S: AsFd + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(source.as_fd(), token, interest.is_readable(), interest.is_writable())
+    }
+
+    /// Changes the token and interest of a registered source.
+    ///
+    /// # Errors
+    ///
+    /// Depending on the platform, reregistering a source that is not
+    /// registered either returns a [`NotFound`](io::ErrorKind::NotFound) error
+    /// or registers it.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn reregister<S: AsFd + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(source.as_fd(), token, interest.is_readable(), interest.is_writable())
+    }
+
+    /// Removes a source from this poller.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NotFound`](io::ErrorKind::NotFound) error if the source is
+    /// not registered.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn deregister<S: AsFd + ?Sized>(&self, source: &S) -> io::Result<()> {
+        self.inner.deregister(source.as_fd())
+    }
+
+    /// Waits until at least one registered source is ready, or the timeout
+    /// elapses, and stores the events in `events`.
+    ///
+    /// Any events already in `events` are cleared first. Returns the number of
+    /// events, which is zero if the timeout elapsed. A timeout of `None` waits
+    /// indefinitely.
+    ///
+    /// This may also return without any events if the wait is interrupted by
+    /// a signal. The same source may be reported in several events, for
+    /// example once for readability and once for writability.
+    ///
+    /// If sources are registered while another thread is waiting in `poll`,
+    /// they may only be polled from the next call on.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<usize> {
+        events.clear();
+        self.inner.poll(&mut events.events, events.capacity, timeout)?;
+        Ok(events.len())
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl fmt::Debug for Poller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
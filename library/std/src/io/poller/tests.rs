@@ -0,0 +1,140 @@
+use crate::io::{ErrorKind, Events, Interest, Poller, Read, Write, pipe};
+use crate::net::test::LOCALHOST_IP4;
+use crate::net::{TcpListener, TcpStream};
+use crate::os::unix::net::UnixStream;
+use crate::time::Duration;
+
+#[test]
+fn interest() {
+    let both = Interest::READABLE | Interest::WRITABLE;
+    assert!(both.is_readable() && both.is_writable());
+    assert!(!Interest::READABLE.is_writable());
+    assert!(!Interest::WRITABLE.is_readable());
+
+    let mut interest = Interest::READABLE;
+    interest |= Interest::WRITABLE;
+    assert_eq!(interest, both);
+    assert_eq!(format!("{both:?}"), "READABLE | WRITABLE");
+}
+
+#[test]
+#[should_panic]
+fn events_zero_capacity() {
+    let _ = Events::with_capacity(0);
+}
+
+#[test]
+fn poll_pipe() {
+    let (mut rx, mut tx) = pipe().unwrap();
+    let poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(4);
+
+    poller.register(&rx, 7, Interest::READABLE).unwrap();
+    assert_eq!(poller.poll(&mut events, Some(Duration::from_millis(10))).unwrap(), 0);
+    assert!(events.is_empty());
+
+    tx.write_all(b"hello").unwrap();
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), 7);
+    assert!(event.is_readable());
+    assert!(!event.is_writable());
+
+    // Readiness is level-triggered.
+    assert_eq!(poller.poll(&mut events, Some(Duration::ZERO)).unwrap(), 1);
+
+    let mut buf = [0; 5];
+    rx.read_exact(&mut buf).unwrap();
+    assert_eq!(poller.poll(&mut events, Some(Duration::ZERO)).unwrap(), 0);
+
+    // Closing the writer makes the reader readable, reading end of file.
+    drop(tx);
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    assert!(events.iter().next().unwrap().is_readable());
+    assert_eq!(rx.read(&mut buf).unwrap(), 0);
+
+    poller.deregister(&rx).unwrap();
+    assert_eq!(poller.poll(&mut events, Some(Duration::ZERO)).unwrap(), 0);
+}
+
+#[test]
+fn reregister() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    let poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(4);
+
+    poller.register(&a, 1, Interest::READABLE).unwrap();
+    assert_eq!(poller.poll(&mut events, Some(Duration::ZERO)).unwrap(), 0);
+
+    // Readability is no longer reported once it is dropped from the interest.
+    b.write_all(b"hello").unwrap();
+    poller.reregister(&a, 2, Interest::WRITABLE).unwrap();
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), 2);
+    assert!(event.is_writable());
+    assert!(!event.is_readable());
+
+    poller.deregister(&a).unwrap();
+}
+
+#[test]
+fn closed_pipe_read_end() {
+    // Like the stdout of a child that has exited, which is only readable.
+    let (mut rx, tx) = pipe().unwrap();
+    drop(tx);
+    let poller = Poller::new().unwrap();
+    let mut events = Events::with_capacity(4);
+
+    poller.register(&rx, 3, Interest::READABLE).unwrap();
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), 3);
+    assert!(event.is_readable());
+    assert_eq!(rx.read(&mut [0; 4]).unwrap(), 0);
+
+    poller.reregister(&rx, 4, Interest::READABLE).unwrap();
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    assert_eq!(events.iter().next().unwrap().token(), 4);
+
+    poller.deregister(&rx).unwrap();
+    assert_eq!(poller.poll(&mut events, Some(Duration::ZERO)).unwrap(), 0);
+}
+
+#[test]
+fn deregister_unregistered() {
+    let (rx, _tx) = pipe().unwrap();
+    let poller = Poller::new().unwrap();
+    assert_eq!(poller.deregister(&rx).unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn poll_sockets() {
+    let listener = TcpListener::bind(LOCALHOST_IP4).unwrap();
+    listener.set_nonblocking(true).unwrap();
+
+    let poller = Poller::new().unwrap();
+    poller.register(&listener, 0, Interest::READABLE).unwrap();
+    let mut events = Events::with_capacity(4);
+
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    assert_eq!(poller.poll(&mut events, None).unwrap(), 1);
+    assert_eq!(events.iter().next().unwrap().token(), 0);
+
+    let (mut server, _) = listener.accept().unwrap();
+    poller.register(&server, 1, Interest::READABLE).unwrap();
+
+    client.write_all(b"ping").unwrap();
+    let mut readable = false;
+    while !readable {
+        poller.poll(&mut events, None).unwrap();
+        readable = events.iter().any(|event| event.token() == 1 && event.is_readable());
+    }
+
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ping");
+
+    poller.deregister(&server).unwrap();
+    poller.deregister(&listener).unwrap();
+}
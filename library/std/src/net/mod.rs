@@ -8,6 +8,7 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`TcpSocket`] and [`UdpSocketBuilder`] set socket options before binding
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
@@ -33,10 +34,16 @@
 pub use self::socket_addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[unstable(feature = "tcplistener_into_incoming", issue = "88373")]
 pub use self::tcp::IntoIncoming;
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::tcp::TcpSocket;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
+#[unstable(feature = "udp_batch", issue = "none")]
+pub use self::udp::RecvMeta;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::udp::UdpSocketBuilder;
 
 mod hostname;
 mod ip_addr;
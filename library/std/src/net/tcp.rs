@@ -1165,3 +1165,155 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A builder for TCP sockets, used to set socket options that only take effect
+/// before the socket is bound.
+///
+/// [`TcpListener::bind`] and [`TcpStream::connect`] create and bind the socket
+/// in one go. A `TcpSocket` collects options like `SO_REUSEADDR`,
+/// `SO_REUSEPORT`, `IPV6_V6ONLY`, the buffer sizes and the listen backlog
+/// first, applies them to the socket before binding it, and then returns the
+/// usual [`TcpListener`] or [`TcpStream`].
+///
+/// Options that are not set are left at the platform default, except for
+/// `SO_REUSEADDR` on listeners, which defaults to the same value
+/// [`TcpListener::bind`] uses.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+///
+/// use std::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let listener = TcpSocket::new()
+///         .reuse_address(true)
+///         .only_v6(false)
+///         .backlog(1024)
+///         .listen("[::]:8080")?;
+///     for stream in listener.incoming() {
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct TcpSocket {
+    opts: net_imp::SocketOptions,
+    backlog: Option<u32>,
+    local_addr: Option<SocketAddr>,
+}
+
+impl TcpSocket {
+    /// Creates a new builder with no options set.
+    #[must_use]
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new() -> TcpSocket {
+        TcpSocket::default()
+    }
+
+    /// Sets the `SO_REUSEADDR` option, which allows binding to an address that
+    /// is still in use by sockets in the `TIME_WAIT` state.
+    ///
+    /// Listeners enable this by default on all platforms but Windows, where it
+    /// additionally allows binding to addresses that are actively in use.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_address(&mut self, reuse: bool) -> &mut TcpSocket {
+        self.opts.reuse_address = Some(reuse);
+        self
+    }
+
+    /// Sets the `SO_REUSEPORT` option, which allows several sockets to bind to
+    /// the same address and port.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is only available on Linux, Android, the BSDs and Apple
+    /// platforms. Elsewhere, binding fails with [`io::ErrorKind::Unsupported`]
+    /// if it is set.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_port(&mut self, reuse: bool) -> &mut TcpSocket {
+        self.opts.reuse_port = Some(reuse);
+        self
+    }
+
+    /// Sets the `IPV6_V6ONLY` option, which restricts a socket bound to an IPv6
+    /// address to IPv6 traffic.
+    ///
+    /// This is ignored for sockets bound to IPv4 addresses.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut TcpSocket {
+        self.opts.only_v6 = Some(only_v6);
+        self
+    }
+
+    /// Sets the size of the receive buffer (`SO_RCVBUF`) in bytes.
+    ///
+    /// The operating system may adjust the size, for example by doubling it
+    /// on Linux.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut TcpSocket {
+        self.opts.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the send buffer (`SO_SNDBUF`) in bytes.
+    ///
+    /// The operating system may adjust the size, for example by doubling it
+    /// on Linux.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut TcpSocket {
+        self.opts.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the maximum number of pending connections of listeners created by
+    /// [`listen`](TcpSocket::listen).
+    ///
+    /// If not set, the same backlog as for [`TcpListener::bind`] is used.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn backlog(&mut self, backlog: u32) -> &mut TcpSocket {
+        self.backlog = Some(backlog);
+        self
+    }
+
+    /// Sets the local address streams created by [`connect`](TcpSocket::connect)
+    /// are bound to before connecting.
+    ///
+    /// If not set, the operating system picks the local address when connecting.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn local_addr(&mut self, addr: SocketAddr) -> &mut TcpSocket {
+        self.local_addr = Some(addr);
+        self
+    }
+
+    /// Creates a socket with the options of this builder, binds it to `addr`
+    /// and starts listening for connections.
+    ///
+    /// Like [`TcpListener::bind`], each address yielded by `addr` is tried
+    /// until one succeeds, and the error of the last attempt is returned if
+    /// none does.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn listen<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        net_imp::each_addr(addr, |addr| {
+            net_imp::TcpListener::bind_with(addr, &self.opts, self.backlog).map(TcpListener)
+        })
+    }
+
+    /// Creates a socket with the options of this builder and connects it to
+    /// `addr`, binding it to the [local address](TcpSocket::local_addr) first
+    /// if one was set.
+    ///
+    /// Like [`TcpStream::connect`], each address yielded by `addr` is tried
+    /// until one succeeds, and the error of the last attempt is returned if
+    /// none does.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        net_imp::each_addr(addr, |addr| {
+            net_imp::TcpStream::connect_with(addr, self.local_addr.as_ref(), &self.opts)
+                .map(TcpStream)
+        })
+    }
+}
@@ -950,3 +950,47 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_os = "wasi", ignore)] // no threads
+fn socket_builder() {
+    for addr in [LOCALHOST_IP4, LOCALHOST_IP6] {
+        let listener = t!(TcpSocket::new()
+            .reuse_address(true)
+            .only_v6(true)
+            .recv_buffer_size(64 * 1024)
+            .backlog(16)
+            .listen(addr));
+        let server_addr = t!(listener.local_addr());
+
+        let _t = thread::spawn(move || {
+            let mut stream = t!(TcpSocket::new()
+                .send_buffer_size(64 * 1024)
+                .local_addr(SocketAddr::new(addr.ip(), 0))
+                .connect(server_addr));
+            t!(stream.write(&[42]));
+        });
+
+        let (mut stream, peer_addr) = t!(listener.accept());
+        assert_eq!(peer_addr.ip(), addr.ip());
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert_eq!(buf[0], 42);
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn socket_builder_reuse_port() {
+    let mut builder = TcpSocket::new();
+    builder.reuse_port(true);
+    let first = t!(builder.listen(LOCALHOST_IP4));
+    let second = t!(builder.listen(t!(first.local_addr())));
+    assert_eq!(t!(first.local_addr()), t!(second.local_addr()));
+
+    // Without the option, the port is still taken.
+    match TcpListener::bind(t!(first.local_addr())) {
+        Ok(..) => panic!(),
+        Err(e) => assert_eq!(e.kind(), ErrorKind::AddrInUse),
+    }
+}
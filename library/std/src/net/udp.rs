@@ -11,7 +11,7 @@
 mod tests;
 
 use crate::fmt;
-use crate::io::{self, ErrorKind};
+use crate::io::{self, ErrorKind, IoSlice, IoSliceMut};
 use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use crate::sys::{AsInner, FromInner, IntoInner, net as net_imp};
 use crate::time::Duration;
@@ -212,6 +212,95 @@ pub fn send_to<A: ToSocketAddrs>(&self, buf: &[u8], addr: A) -> io::Result<usize
         }
     }
 
+    /// Receives several datagrams on the socket at once.
+    ///
+    /// The `i`th datagram is written into `bufs[i]`, and its length and origin
+    /// are stored in `meta[i]`. This blocks until at least one datagram is
+    /// available, and then receives as many of the waiting datagrams as fit into
+    /// `min(bufs.len(), meta.len())` slots, returning their number. As with
+    /// [`recv_from`](UdpSocket::recv_from), excess bytes of a datagram that is
+    /// too long for its buffer may be discarded.
+    ///
+    /// An error is only returned if no datagram was received. If a later
+    /// datagram fails, the ones before it are returned, and that datagram is
+    /// lost along with any others the same system call took off the socket.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This uses a single `recvmmsg` system call on Linux and Android, which
+    /// receives at most 32 datagrams. On other platforms, it receives one
+    /// datagram per call with [`recv_from`].
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_batch)]
+    ///
+    /// use std::io::IoSliceMut;
+    /// use std::net::{RecvMeta, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// let mut storage = [[0; 1500]; 32];
+    /// let mut bufs = storage.each_mut().map(|buf| IoSliceMut::new(buf));
+    /// let mut meta = [RecvMeta::default(); 32];
+    /// let n = socket.recv_many(&mut bufs, &mut meta).expect("didn't receive data");
+    /// for (buf, meta) in bufs.iter().zip(&meta).take(n) {
+    ///     println!("{} bytes from {}: {:?}", meta.len(), meta.addr(), &buf[..meta.len()]);
+    /// }
+    /// ```
+    #[unstable(feature = "udp_batch", issue = "none")]
+    pub fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        self.0.recv_many(bufs, meta)
+    }
+
+    /// Sends several datagrams on the socket at once, sending `bufs[i]` to
+    /// `addrs[i]`.
+    ///
+    /// Returns the number of datagrams that were sent, which may be less than
+    /// `bufs.len()`. An error is only returned if not even the first datagram
+    /// could be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`](ErrorKind::InvalidInput) error if `bufs`
+    /// and `addrs` have different lengths.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This uses a single `sendmmsg` system call on Linux and Android, which
+    /// sends at most 32 datagrams. On other platforms, it calls
+    /// [`send_to`](UdpSocket::send_to) for each datagram.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_batch)]
+    ///
+    /// use std::io::IoSlice;
+    /// use std::net::{SocketAddr, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// let dst = SocketAddr::from(([127, 0, 0, 1], 4242));
+    /// let bufs = [IoSlice::new(b"one"), IoSlice::new(b"two"), IoSlice::new(b"three")];
+    /// let sent = socket.send_many(&bufs, &[dst; 3]).expect("couldn't send data");
+    /// ```
+    #[unstable(feature = "udp_batch", issue = "none")]
+    pub fn send_many(&self, bufs: &[IoSlice<'_>], addrs: &[SocketAddr]) -> io::Result<usize> {
+        if bufs.len() != addrs.len() {
+            return Err(io::const_error!(
+                ErrorKind::InvalidInput,
+                "the number of buffers and addresses differ",
+            ));
+        }
+        self.0.send_many(bufs, addrs)
+    }
+
     /// Returns the socket address of the remote peer this socket was connected to.
     ///
     /// # Examples
@@ -829,6 +918,51 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
 // `AsSocket`/`From<OwnedSocket>`/`Into<OwnedSocket>` and
 // `AsRawSocket`/`IntoRawSocket`/`FromRawSocket` on Windows.
 
+/// The length and origin of a datagram received by [`UdpSocket::recv_many`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[unstable(feature = "udp_batch", issue = "none")]
+pub struct RecvMeta {
+    pub(crate) len: usize,
+    pub(crate) addr: SocketAddr,
+    pub(crate) segment_size: usize,
+}
+
+impl RecvMeta {
+    /// Returns the number of bytes that were received.
+    #[must_use]
+    #[unstable(feature = "udp_batch", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the address the datagram was sent from.
+    #[must_use]
+    #[unstable(feature = "udp_batch", issue = "none")]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the size of the datagrams the received data consists of.
+    ///
+    /// This is the same as [`len`](RecvMeta::len), unless generic receive
+    /// offload (GRO) is enabled on Linux, in which case the kernel may
+    /// coalesce several datagrams from the same origin into one buffer. All of
+    /// these datagrams have this size, except for the last one, which may be
+    /// shorter.
+    #[must_use]
+    #[unstable(feature = "udp_batch", issue = "none")]
+    pub fn segment_size(&self) -> usize {
+        self.segment_size
+    }
+}
+
+#[unstable(feature = "udp_batch", issue = "none")]
+impl Default for RecvMeta {
+    fn default() -> RecvMeta {
+        RecvMeta { len: 0, addr: SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0), segment_size: 0 }
+    }
+}
+
 impl AsInner<net_imp::UdpSocket> for UdpSocket {
     #[inline]
     fn as_inner(&self) -> &net_imp::UdpSocket {
@@ -854,3 +988,104 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A builder for UDP sockets, used to set socket options that only take effect
+/// before the socket is bound.
+///
+/// This is the UDP counterpart of [`TcpSocket`]: the options are applied to
+/// the socket before it is bound, and the result is a regular [`UdpSocket`].
+/// Options that are not set are left at the platform default.
+///
+/// [`TcpSocket`]: crate::net::TcpSocket
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+///
+/// use std::net::UdpSocketBuilder;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = UdpSocketBuilder::new()
+///         .reuse_address(true)
+///         .recv_buffer_size(1 << 20)
+///         .bind("0.0.0.0:5353")?;
+///     let mut buf = [0; 1500];
+///     let (len, src) = socket.recv_from(&mut buf)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct UdpSocketBuilder {
+    opts: net_imp::SocketOptions,
+}
+
+impl UdpSocketBuilder {
+    /// Creates a new builder with no options set.
+    #[must_use]
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new() -> UdpSocketBuilder {
+        UdpSocketBuilder::default()
+    }
+
+    /// Sets the `SO_REUSEADDR` option.
+    ///
+    /// See [`TcpSocket::reuse_address`](crate::net::TcpSocket::reuse_address).
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_address(&mut self, reuse: bool) -> &mut UdpSocketBuilder {
+        self.opts.reuse_address = Some(reuse);
+        self
+    }
+
+    /// Sets the `SO_REUSEPORT` option.
+    ///
+    /// See [`TcpSocket::reuse_port`](crate::net::TcpSocket::reuse_port).
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_port(&mut self, reuse: bool) -> &mut UdpSocketBuilder {
+        self.opts.reuse_port = Some(reuse);
+        self
+    }
+
+    /// Sets the `IPV6_V6ONLY` option.
+    ///
+    /// See [`TcpSocket::only_v6`](crate::net::TcpSocket::only_v6).
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut UdpSocketBuilder {
+        self.opts.only_v6 = Some(only_v6);
+        self
+    }
+
+    /// Sets the size of the receive buffer (`SO_RCVBUF`) in bytes.
+    ///
+    /// The operating system may adjust the size, for example by doubling it
+    /// on Linux.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut UdpSocketBuilder {
+        self.opts.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the send buffer (`SO_SNDBUF`) in bytes.
+    ///
+    /// The operating system may adjust the size, for example by doubling it
+    /// on Linux.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut UdpSocketBuilder {
+        self.opts.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Creates a socket with the options of this builder and binds it to
+    /// `addr`.
+    ///
+    /// Like [`UdpSocket::bind`], each address yielded by `addr` is tried until
+    /// one succeeds, and the error of the last attempt is returned if none
+    /// does.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind<A: ToSocketAddrs>(&self, addr: A) -> io::Result<UdpSocket> {
+        net_imp::each_addr(addr, |addr| {
+            net_imp::UdpSocket::bind_with(addr, &self.opts).map(UdpSocket)
+        })
+    }
+}
@@ -368,3 +368,73 @@ fn set_nonblocking() {
         }
     })
 }
+
+#[test]
+fn socket_builder() {
+    for addr in [LOCALHOST_IP4, LOCALHOST_IP6] {
+        let mut builder = UdpSocketBuilder::new();
+        builder.reuse_address(true).only_v6(true).recv_buffer_size(64 * 1024);
+        let server = t!(builder.bind(addr));
+        let client = t!(builder.send_buffer_size(64 * 1024).bind(addr));
+
+        t!(client.send_to(&[42], t!(server.local_addr())));
+        let mut buf = [0];
+        let (len, src) = t!(server.recv_from(&mut buf));
+        assert_eq!((len, buf[0]), (1, 42));
+        assert_eq!(src, t!(client.local_addr()));
+    }
+}
+
+#[test]
+fn send_many_recv_many() {
+    use crate::io::{IoSlice, IoSliceMut};
+
+    each_ip(&mut |server, client| {
+        let dst = t!(server.local_addr());
+        let msgs = [&b"one"[..], b"two", b"three"];
+        let bufs = msgs.map(IoSlice::new);
+        assert_eq!(t!(client.send_many(&bufs, &[dst; 3])), 3);
+
+        let mut storage = [[0; 16]; 8];
+        let mut bufs = storage.each_mut().map(|buf| IoSliceMut::new(buf));
+        let mut meta = [RecvMeta::default(); 8];
+        let mut received = Vec::new();
+        while received.len() < msgs.len() {
+            let n = t!(server.recv_many(&mut bufs, &mut meta));
+            assert!(n > 0);
+            for (buf, meta) in bufs.iter().zip(&meta).take(n) {
+                assert!(compare_ignore_zoneid(&meta.addr(), &t!(client.local_addr())));
+                assert_eq!(meta.segment_size(), meta.len());
+                received.push(buf[..meta.len()].to_vec());
+            }
+        }
+        assert_eq!(received, msgs);
+
+        assert_eq!(t!(server.recv_many(&mut [], &mut meta)), 0);
+
+        // Larger batches may be split up, but nothing is lost.
+        let msgs: Vec<[u8; 1]> = (0..40).map(|i| [i]).collect();
+        let bufs: Vec<_> = msgs.iter().map(|msg| IoSlice::new(msg)).collect();
+        let mut sent = 0;
+        while sent < bufs.len() {
+            let n = t!(client.send_many(&bufs[sent..], &[dst; 40][sent..]));
+            assert!(n > 0);
+            sent += n;
+        }
+        let mut storage = [[0; 16]; 64];
+        let mut bufs = storage.each_mut().map(|buf| IoSliceMut::new(buf));
+        let mut meta = [RecvMeta::default(); 64];
+        let mut received = Vec::new();
+        while received.len() < msgs.len() {
+            let n = t!(server.recv_many(&mut bufs, &mut meta));
+            assert!(n > 0);
+            for buf in bufs.iter().take(n) {
+                received.push([buf[0]]);
+            }
+        }
+        assert_eq!(received, msgs);
+
+        let err = client.send_many(&[IoSlice::new(b"one")], &[dst; 2]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    })
+}
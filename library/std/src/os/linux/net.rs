@@ -8,3 +8,5 @@
 pub use crate::os::net::linux_ext::socket::UnixSocketExt;
 #[stable(feature = "tcp_quickack", since = "1.89.0")]
 pub use crate::os::net::linux_ext::tcp::TcpStreamExt;
+#[unstable(feature = "udp_batch", issue = "none")]
+pub use crate::os::net::linux_ext::udp::UdpSocketExt;
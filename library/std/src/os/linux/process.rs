@@ -4,12 +4,14 @@
 
 #![unstable(feature = "linux_pidfd", issue = "82971")]
 
-use crate::io::Result;
+use crate::io::{self, Result};
 use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use crate::process::{self, ExitStatus};
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner};
 #[cfg(not(doc))]
 use crate::sys::{fd::FileDesc, process::PidFd as InnerPidFd};
+use crate::time::Duration;
+use crate::{slice, vec};
 
 #[cfg(doc)]
 struct InnerPidFd;
@@ -214,3 +216,165 @@ fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
         self
     }
 }
+
+/// A set of child processes that can be waited on together.
+///
+/// A `ChildSet` owns the [`Child`] handles added to it, and
+/// [`wait_any`] blocks until any one of them exits. This makes it possible to
+/// supervise many child processes from a single thread, without polling them
+/// in turn or spawning a thread per child.
+///
+/// Only children that were spawned with a [`PidFd`] can be added to the set,
+/// see [`create_pidfd`].
+///
+/// Example:
+/// ```no_run
+/// #![feature(linux_pidfd, linux_child_set)]
+/// use std::os::linux::process::{ChildSet, CommandExt};
+/// use std::process::Command;
+///
+/// let mut children = ChildSet::new();
+/// for secs in ["3", "1", "2"] {
+///     let child = Command::new("sleep").arg(secs).create_pidfd(true).spawn()?;
+///     children.insert(child).expect("pidfds are not supported");
+/// }
+///
+/// while !children.is_empty() {
+///     let (child, status) = children.wait_any()?;
+///     println!("child {} exited with {status}", child.id());
+/// }
+/// # std::io::Result::Ok(())
+/// ```
+///
+/// [`Child`]: process::Child
+/// [`wait_any`]: ChildSet::wait_any
+/// [`create_pidfd`]: CommandExt::create_pidfd
+#[unstable(feature = "linux_child_set", issue = "none")]
+#[derive(Debug, Default)]
+pub struct ChildSet {
+    children: Vec<process::Child>,
+}
+
+impl ChildSet {
+    /// Creates an empty set.
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn new() -> ChildSet {
+        ChildSet { children: Vec::new() }
+    }
+
+    /// Adds a child to the set.
+    ///
+    /// If the child has no [`PidFd`], either because it was not requested
+    /// with [`create_pidfd`] or because it could not be created, the child
+    /// is returned as the error instead.
+    ///
+    /// [`create_pidfd`]: CommandExt::create_pidfd
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn insert(&mut self, child: process::Child) -> crate::result::Result<(), process::Child> {
+        if child.pidfd().is_err() {
+            return Err(child);
+        }
+        self.children.push(child);
+        Ok(())
+    }
+
+    /// Returns the number of children in the set.
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if the set contains no children.
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns an iterator over the children in the set, in the order they
+    /// were added.
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn iter(&self) -> slice::Iter<'_, process::Child> {
+        self.children.iter()
+    }
+
+    /// Returns an iterator that allows modifying the children in the set,
+    /// for example to [`kill`] them.
+    ///
+    /// [`kill`]: process::Child::kill
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, process::Child> {
+        self.children.iter_mut()
+    }
+
+    /// Waits for any child in the set to exit, removes it from the set, and
+    /// returns it along with the status it exited with.
+    ///
+    /// If several children have exited, the one added to the set first is
+    /// returned. Like [`Child::wait`], this closes the stdin handle of the
+    /// returned child before reaping it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`] if the set is empty, as
+    /// waiting would otherwise block forever.
+    ///
+    /// [`Child::wait`]: process::Child::wait
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn wait_any(&mut self) -> Result<(process::Child, ExitStatus)> {
+        if self.children.is_empty() {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "the child set is empty"));
+        }
+        match self.wait_inner(None)? {
+            Some(exited) => Ok(exited),
+            None => unreachable!("waiting without a timeout should not time out"),
+        }
+    }
+
+    /// Waits for any child in the set to exit for at most `timeout`.
+    ///
+    /// Returns `Ok(None)` if no child exited before the timeout elapsed, or
+    /// if the set is empty. Otherwise the child is removed from the set and
+    /// returned as with [`wait_any`].
+    ///
+    /// [`wait_any`]: ChildSet::wait_any
+    #[unstable(feature = "linux_child_set", issue = "none")]
+    pub fn wait_any_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(process::Child, ExitStatus)>> {
+        if self.children.is_empty() {
+            return Ok(None);
+        }
+        self.wait_inner(Some(timeout))
+    }
+
+    fn wait_inner(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<Option<(process::Child, ExitStatus)>> {
+        let pidfds = self
+            .children
+            .iter()
+            .map(|child| child.pidfd().map(AsInner::as_inner))
+            .collect::<Result<Vec<_>>>()?;
+        let Some(index) = crate::sys::process::poll_exited(&pidfds, timeout)? else {
+            return Ok(None);
+        };
+        // The child has exited, so this does not block. It is only removed
+        // from the set once it has been reaped, so that it is not lost if
+        // `wait` fails.
+        let status = self.children[index].wait()?;
+        Ok(Some((self.children.remove(index), status)))
+    }
+}
+
+#[unstable(feature = "linux_child_set", issue = "none")]
+impl IntoIterator for ChildSet {
+    type Item = process::Child;
+    type IntoIter = vec::IntoIter<process::Child>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
@@ -11,5 +11,9 @@
 #[stable(feature = "tcp_quickack", since = "1.89.0")]
 pub(crate) mod tcp;
 
+#[cfg(any(target_os = "linux", doc))]
+#[unstable(feature = "udp_batch", issue = "none")]
+pub(crate) mod udp;
+
 #[cfg(test)]
 mod tests;
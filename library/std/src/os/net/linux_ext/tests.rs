@@ -54,3 +54,45 @@ macro_rules! t {
     stream.set_deferaccept(zero).expect("set_deferaccept failed");
     assert_eq!(stream.deferaccept().unwrap(), zero);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn udp_gro_gso() {
+    use crate::io::IoSliceMut;
+    use crate::net::test::LOCALHOST_IP4;
+    use crate::net::{RecvMeta, UdpSocket};
+    use crate::os::net::linux_ext::udp::UdpSocketExt;
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        };
+    }
+
+    let server = t!(UdpSocket::bind(LOCALHOST_IP4));
+    let client = t!(UdpSocket::bind(LOCALHOST_IP4));
+
+    t!(server.set_gro(true));
+    assert_eq!(true, t!(server.gro()));
+    t!(client.set_gso_segment_size(4));
+    assert_eq!(4, t!(client.gso_segment_size()));
+
+    t!(client.send_to(b"abcdefghij", t!(server.local_addr())));
+
+    // The datagrams are either coalesced again by GRO or received one by one.
+    let mut storage = [[0; 64]; 4];
+    let mut bufs = storage.each_mut().map(|buf| IoSliceMut::new(buf));
+    let mut meta = [RecvMeta::default(); 4];
+    let mut received = Vec::new();
+    while received.len() < 10 {
+        let n = t!(server.recv_many(&mut bufs, &mut meta));
+        for (buf, meta) in bufs.iter().zip(&meta).take(n) {
+            assert_eq!(meta.segment_size(), 4.min(meta.len()));
+            received.extend_from_slice(&buf[..meta.len()]);
+        }
+    }
+    assert_eq!(received, b"abcdefghij");
+}
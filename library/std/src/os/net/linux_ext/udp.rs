@@ -0,0 +1,96 @@
+//! Linux-specific udp extensions to primitives in the [`std::net`] module.
+//!
+//! [`std::net`]: crate::net
+
+use crate::sys::AsInner;
+use crate::{io, net};
+
+/// Os-specific extensions for [`UdpSocket`]
+///
+/// [`UdpSocket`]: net::UdpSocket
+#[unstable(feature = "udp_batch", issue = "none")]
+pub impl(self) trait UdpSocketExt {
+    /// Enable or disable generic receive offload (`UDP_GRO`).
+    ///
+    /// With GRO enabled, the kernel may coalesce several datagrams from the
+    /// same origin into one buffer. [`UdpSocket::recv_many`] reports the size
+    /// of the individual datagrams in [`RecvMeta::segment_size`]; other ways of
+    /// receiving do not, so GRO should only be enabled when using `recv_many`.
+    ///
+    /// See [`man 7 udp`](https://man7.org/linux/man-pages/man7/udp.7.html) for
+    /// more information.
+    ///
+    /// [`UdpSocket::recv_many`]: net::UdpSocket::recv_many
+    /// [`RecvMeta::segment_size`]: net::RecvMeta::segment_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_batch)]
+    /// use std::net::UdpSocket;
+    /// use std::os::linux::net::UdpSocketExt;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_gro(true).expect("set_gro call failed");
+    /// ```
+    #[unstable(feature = "udp_batch", issue = "none")]
+    fn set_gro(&self, gro: bool) -> io::Result<()>;
+
+    /// Gets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocketExt::set_gro`].
+    #[unstable(feature = "udp_batch", issue = "none")]
+    fn gro(&self) -> io::Result<bool>;
+
+    /// Sets the segment size for generic segmentation offload (`UDP_SEGMENT`).
+    ///
+    /// With a non-zero segment size, every buffer that is sent is split into
+    /// datagrams of `segment_size` bytes, the last of which may be shorter. This
+    /// lets the kernel and network card do the splitting, which is much cheaper
+    /// than sending the datagrams one by one. Setting it to 0 disables
+    /// segmentation.
+    ///
+    /// See [`man 7 udp`](https://man7.org/linux/man-pages/man7/udp.7.html) for
+    /// more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_batch)]
+    /// use std::net::UdpSocket;
+    /// use std::os::linux::net::UdpSocketExt;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_gso_segment_size(1200).expect("set_gso_segment_size call failed");
+    /// // Sent as ten datagrams of 1200 bytes each.
+    /// socket.send_to(&[0; 12000], "127.0.0.1:4242").expect("couldn't send data");
+    /// ```
+    #[unstable(feature = "udp_batch", issue = "none")]
+    fn set_gso_segment_size(&self, segment_size: u16) -> io::Result<()>;
+
+    /// Gets the value of the `UDP_SEGMENT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketExt::set_gso_segment_size`].
+    #[unstable(feature = "udp_batch", issue = "none")]
+    fn gso_segment_size(&self) -> io::Result<u16>;
+}
+
+#[unstable(feature = "udp_batch", issue = "none")]
+impl UdpSocketExt for net::UdpSocket {
+    fn set_gro(&self, gro: bool) -> io::Result<()> {
+        self.as_inner().socket().set_udp_gro(gro)
+    }
+
+    fn gro(&self) -> io::Result<bool> {
+        self.as_inner().socket().udp_gro()
+    }
+
+    fn set_gso_segment_size(&self, segment_size: u16) -> io::Result<()> {
+        self.as_inner().socket().set_udp_segment(segment_size)
+    }
+
+    fn gso_segment_size(&self) -> io::Result<u16> {
+        self.as_inner().socket().udp_segment()
+    }
+}
@@ -7,7 +7,7 @@
 #[allow(deprecated)]
 use crate::os::unix::raw::pthread_t;
 use crate::sys::{AsInner, IntoInner};
-use crate::thread::JoinHandle;
+use crate::thread::{Builder, JoinHandle};
 
 #[stable(feature = "thread_extensions", since = "1.9.0")]
 #[allow(deprecated)]
@@ -44,3 +44,81 @@ fn into_pthread_t(self) -> RawPthread {
         self.into_inner().into_id() as RawPthread
     }
 }
+
+/// A scheduling policy of a thread, which determines how the operating
+/// system's scheduler picks the threads to run.
+///
+/// See the `sched(7)` manual page on Linux for details on the policies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[unstable(feature = "thread_sched_policy", issue = "none")]
+pub enum SchedPolicy {
+    /// The default time-sharing policy, `SCHED_OTHER`.
+    Other,
+    /// A real-time first-in, first-out policy, `SCHED_FIFO`.
+    Fifo,
+    /// A real-time round-robin policy, `SCHED_RR`.
+    RoundRobin,
+}
+
+/// Unix-specific extensions to [`Builder`].
+#[unstable(feature = "thread_sched_policy", issue = "none")]
+pub trait BuilderExt {
+    /// Sets the scheduling policy of the thread-to-be.
+    ///
+    /// If no [priority](BuilderExt::priority) is set, the lowest priority of
+    /// the policy is used. Real-time policies usually require privileges,
+    /// without which [`Builder::spawn`] returns an error of kind
+    /// [`PermissionDenied`].
+    ///
+    /// This is currently only supported on Linux. On other platforms,
+    /// [`Builder::spawn`] returns an error of kind [`Unsupported`] if this is
+    /// set.
+    ///
+    /// [`PermissionDenied`]: crate::io::ErrorKind::PermissionDenied
+    /// [`Unsupported`]: crate::io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_sched_policy)]
+    ///
+    /// use std::os::unix::thread::{BuilderExt, SchedPolicy};
+    /// use std::thread;
+    ///
+    /// let handler = thread::Builder::new()
+    ///     .sched_policy(SchedPolicy::Fifo)
+    ///     .priority(10)
+    ///     .spawn(|| {
+    ///         // latency-critical work
+    ///     })
+    ///     .unwrap();
+    ///
+    /// handler.join().unwrap();
+    /// ```
+    #[unstable(feature = "thread_sched_policy", issue = "none")]
+    fn sched_policy(self, policy: SchedPolicy) -> Self;
+
+    /// Sets the scheduling priority of the thread-to-be.
+    ///
+    /// The valid priorities depend on the [scheduling policy]; with the
+    /// default [`SchedPolicy::Other`] on Linux, the only valid priority is
+    /// `0`. Otherwise, [`Builder::spawn`] returns an error.
+    ///
+    /// [scheduling policy]: BuilderExt::sched_policy
+    #[unstable(feature = "thread_sched_policy", issue = "none")]
+    fn priority(self, priority: i32) -> Self;
+}
+
+#[unstable(feature = "thread_sched_policy", issue = "none")]
+impl BuilderExt for Builder {
+    fn sched_policy(mut self, policy: SchedPolicy) -> Builder {
+        self.attrs.sched_policy = Some(policy);
+        self
+    }
+
+    fn priority(mut self, priority: i32) -> Builder {
+        self.attrs.sched_priority = Some(priority);
+        self
+    }
+}
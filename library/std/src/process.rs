@@ -168,6 +168,7 @@
 use crate::num::NonZero;
 use crate::path::Path;
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner, process as imp};
+use crate::time::Duration;
 use crate::{fmt, format_args_nl, fs, str};
 
 /// Representation of a running or exited child process.
@@ -2435,6 +2436,52 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, returning the status
+    /// that it exited with if it did.
+    ///
+    /// This behaves like [`wait`] if the child exits before the timeout
+    /// elapses, and like [`try_wait`] returning `Ok(None)` otherwise, in which
+    /// case the child keeps running and can be waited on again. A zero
+    /// `timeout` makes this equivalent to [`try_wait`].
+    ///
+    /// Note that unlike `wait`, this function will not attempt to drop stdin.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux this waits on a pidfd for the child. On Windows it uses
+    /// `WaitForSingleObject`, and on Fuchsia `zx_object_wait_one`. On other
+    /// Unix platforms, and on Linux kernels without pidfd support, the child
+    /// is polled with increasing intervals, so it may be reaped up to 50
+    /// milliseconds after it exited. No `SIGCHLD` handler is installed on any
+    /// platform. This may change in the future.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn()?;
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1))? {
+    ///     Some(status) => println!("exited with: {status}"),
+    ///     None => {
+    ///         println!("still running after a second, killing it");
+    ///         child.kill()?;
+    ///         child.wait()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    ///
+    /// [`wait`]: Child::wait
+    /// [`try_wait`]: Child::try_wait
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_timeout(timeout)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
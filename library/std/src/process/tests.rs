@@ -667,3 +667,40 @@ fn terminate_exited_process() {
     assert!(p.kill().is_ok());
     assert!(p.kill().is_ok());
 }
+
+#[test]
+#[cfg_attr(
+    any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
+    ignore = "no shell available"
+)]
+fn wait_timeout() {
+    use crate::time::{Duration, Instant};
+
+    let mut p = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("exit 1").spawn().unwrap()
+    };
+    let status = p.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert_eq!(status.code(), Some(1));
+    assert_eq!(p.wait_timeout(Duration::ZERO).unwrap(), Some(status));
+
+    // This child keeps running until its stdin is closed.
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "set /p line="]);
+        cmd
+    } else {
+        let mut cmd = shell_cmd();
+        cmd.args(&["-c", "read line"]);
+        cmd
+    };
+    let mut p = cmd.stdin(Stdio::piped()).spawn().unwrap();
+    assert_eq!(p.wait_timeout(Duration::ZERO).unwrap(), None);
+    let start = Instant::now();
+    assert_eq!(p.wait_timeout(Duration::from_millis(100)).unwrap(), None);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    drop(p.stdin.take());
+    assert!(p.wait_timeout(Duration::from_secs(60)).unwrap().is_some());
+}
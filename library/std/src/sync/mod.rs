@@ -180,6 +180,8 @@
 pub use alloc_crate::sync::UniqueArc;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::sync::{Arc, Weak};
+#[unstable(feature = "atomic_arc", issue = "none")]
+pub use alloc_crate::sync::{AtomicArc, AtomicOptionArc};
 
 #[unstable(feature = "mpmc_channel", issue = "126840")]
 pub mod mpmc;
@@ -201,6 +203,7 @@
 mod lazy_lock;
 mod once_lock;
 mod reentrant_lock;
+mod semaphore;
 
 // These exist only in one flavor: no poisoning.
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -211,6 +214,8 @@
 pub use self::once_lock::OnceLock;
 #[unstable(feature = "reentrant_lock", issue = "121440")]
 pub use self::reentrant_lock::{ReentrantLock, ReentrantLockGuard};
+#[unstable(feature = "sync_semaphore", issue = "none")]
+pub use self::semaphore::{Semaphore, SemaphorePermit};
 
 // Note: in the future we will change the default version in `std::sync` to the non-poisoning
 // version over an edition.
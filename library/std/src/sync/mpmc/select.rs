@@ -1,3 +1,13 @@
+//! Selection over multiple channel operations.
+
+use super::context::Context;
+use super::error::*;
+use super::utils::shuffle;
+use super::{Receiver, Sender};
+use crate::marker::PhantomData;
+use crate::time::{Duration, Instant};
+use crate::{fmt, mem, thread};
+
 /// Temporary data that gets initialized during a blocking operation, and is consumed by
 /// `read` or `write`.
 ///
@@ -69,3 +79,1021 @@ fn into(self) -> usize {
         }
     }
 }
+
+/// A receive or send operation that can be selected.
+///
+/// This trait is implemented by the flavor-specific channel handles and is used by the selection
+/// machinery to attempt, block on, and complete operations without knowing the message type.
+pub(crate) trait SelectHandle {
+    /// Attempts to select the operation and returns `true` on success.
+    fn try_select(&self, token: &mut Token) -> bool;
+
+    /// Registers an operation for execution and returns `true` if it is now ready.
+    fn register(&self, oper: Operation, cx: &Context) -> bool;
+
+    /// Unregisters an operation for execution.
+    fn unregister(&self, oper: Operation);
+
+    /// Attempts to select an operation the current thread got woken up for.
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool;
+
+    /// Returns `true` if the operation could be selected without blocking.
+    fn is_ready(&self) -> bool;
+}
+
+/// How long a selection is allowed to block.
+#[derive(Debug, Clone, Copy)]
+enum Timeout {
+    /// Try selecting an operation without blocking.
+    Now,
+
+    /// Block forever.
+    Never,
+
+    /// Block until the given instant.
+    At(Instant),
+}
+
+/// Runs until one of the operations is selected, potentially blocking the current thread.
+///
+/// Successful receive operations will have to be followed up by `Receiver::read()` and successful
+/// send operations by `Sender::write()`.
+fn run_select(
+    handles: &mut [(&dyn SelectHandle, usize, *const u8)],
+    timeout: Timeout,
+    is_biased: bool,
+) -> Option<(Token, usize, *const u8)> {
+    if handles.is_empty() {
+        // Wait until the timeout and return.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => loop {
+                thread::park();
+            },
+            Timeout::At(when) => {
+                thread::sleep_until(when);
+                return None;
+            }
+        }
+    }
+
+    if !is_biased {
+        // Shuffle the operations for fairness.
+        shuffle(handles);
+    }
+
+    // Create a token, which serves as a temporary variable that gets initialized in this function
+    // and is later used by a call to `read` or `write` that completes the selected operation.
+    let mut token = Token::default();
+
+    // Try selecting one of the operations without blocking.
+    for &(handle, i, ptr) in handles.iter() {
+        if handle.try_select(&mut token) {
+            return Some((token, i, ptr));
+        }
+    }
+
+    loop {
+        // Prepare for blocking.
+        let res = Context::with(|cx| {
+            let mut sel = Selected::Waiting;
+            let mut registered_count = 0;
+            let mut index_ready = None;
+
+            if let Timeout::Now = timeout {
+                cx.try_select(Selected::Aborted).unwrap();
+            }
+
+            // Register all operations.
+            for (handle, i, _) in handles.iter_mut() {
+                registered_count += 1;
+
+                // If registration returns `true`, that means the operation has just become ready.
+                if handle.register(Operation::hook::<&dyn SelectHandle>(handle), cx) {
+                    // Try aborting select.
+                    sel = match cx.try_select(Selected::Aborted) {
+                        Ok(()) => {
+                            index_ready = Some(*i);
+                            Selected::Aborted
+                        }
+                        Err(s) => s,
+                    };
+                    break;
+                }
+
+                // If another thread has already selected one of the operations, stop registration.
+                sel = cx.selected();
+                if sel != Selected::Waiting {
+                    break;
+                }
+            }
+
+            if sel == Selected::Waiting {
+                let deadline = match timeout {
+                    Timeout::Now => unreachable!(),
+                    Timeout::Never => None,
+                    Timeout::At(when) => Some(when),
+                };
+
+                // Block the current thread.
+                // SAFETY: the context belongs to the current thread.
+                sel = unsafe { cx.wait_until(deadline) };
+            }
+
+            // Unregister all registered operations.
+            for (handle, _, _) in handles.iter_mut().take(registered_count) {
+                handle.unregister(Operation::hook::<&dyn SelectHandle>(handle));
+            }
+
+            match sel {
+                Selected::Waiting => unreachable!(),
+                Selected::Aborted => {
+                    // If an operation became ready during registration, try selecting it.
+                    if let Some(index_ready) = index_ready {
+                        for &(handle, i, ptr) in handles.iter() {
+                            if i == index_ready && handle.try_select(&mut token) {
+                                return Some((i, ptr));
+                            }
+                        }
+                    }
+                }
+                Selected::Disconnected => {}
+                Selected::Operation(_) => {
+                    // Find the selected operation.
+                    for (handle, i, ptr) in handles.iter_mut() {
+                        // Is this the selected operation?
+                        if sel == Selected::Operation(Operation::hook::<&dyn SelectHandle>(handle))
+                        {
+                            // Try selecting this operation.
+                            if handle.accept(&mut token, cx) {
+                                return Some((*i, *ptr));
+                            }
+                        }
+                    }
+                }
+            }
+
+            None
+        });
+
+        // Return if an operation was selected.
+        if let Some((i, ptr)) = res {
+            return Some((token, i, ptr));
+        }
+
+        // Try selecting one of the operations without blocking.
+        for &(handle, i, ptr) in handles.iter() {
+            if handle.try_select(&mut token) {
+                return Some((token, i, ptr));
+            }
+        }
+
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {}
+            Timeout::At(when) => {
+                if Instant::now() >= when {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+pub(crate) mod sealed {
+    use super::super::{Receiver, Sender};
+
+    /// Gives access to the underlying `mpmc` sender of a channel.
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub trait AsSender<T> {
+        fn as_sender(&self) -> &Sender<T>;
+    }
+
+    /// Gives access to the underlying `mpmc` receiver of a channel.
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub trait AsReceiver<T> {
+        fn as_receiver(&self) -> &Receiver<T>;
+    }
+}
+
+/// The sending half of a channel, which can be used in a [`Select`].
+///
+/// This trait is sealed and implemented for [`mpmc::Sender`], [`mpsc::Sender`] and
+/// [`mpsc::SyncSender`].
+///
+/// [`mpmc::Sender`]: Sender
+/// [`mpsc::Sender`]: crate::sync::mpsc::Sender
+/// [`mpsc::SyncSender`]: crate::sync::mpsc::SyncSender
+#[unstable(feature = "mpmc_select", issue = "none")]
+pub trait SelectSender<T>: sealed::AsSender<T> {}
+
+/// The receiving half of a channel, which can be used in a [`Select`].
+///
+/// This trait is sealed and implemented for [`mpmc::Receiver`] and [`mpsc::Receiver`].
+///
+/// [`mpmc::Receiver`]: Receiver
+/// [`mpsc::Receiver`]: crate::sync::mpsc::Receiver
+#[unstable(feature = "mpmc_select", issue = "none")]
+pub trait SelectReceiver<T>: sealed::AsReceiver<T> {}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> sealed::AsSender<T> for Sender<T> {
+    fn as_sender(&self) -> &Sender<T> {
+        self
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> SelectSender<T> for Sender<T> {}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> sealed::AsReceiver<T> for Receiver<T> {
+    fn as_receiver(&self) -> &Receiver<T> {
+        self
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> SelectReceiver<T> for Receiver<T> {}
+
+/// Selects from a set of channel operations.
+///
+/// `Select` allows you to define a set of channel operations, wait until any one of them becomes
+/// ready, and finally execute it. If multiple operations are ready at the same time, a random one
+/// among them is selected.
+///
+/// An operation is considered to be ready if it doesn't have to block. Note that it is ready even
+/// when it will simply return an error because the channel is disconnected.
+///
+/// Both the channels of this module and those of [`mpsc`] can be used, and the two can be
+/// mixed in the same selection. The [`select!`] macro is a convenience wrapper around `Select`.
+/// However, it cannot select over a dynamically created list of channel operations.
+///
+/// Once a list of operations has been built with `Select`, an operation is selected with
+/// [`try_select`], [`select`], [`select_timeout`], or [`select_deadline`]. If successful, the
+/// returned selected operation has already begun and **must** be completed. If we don't complete
+/// it, a panic will occur.
+///
+/// [`mpsc`]: crate::sync::mpsc
+/// [`select!`]: super::select
+/// [`try_select`]: Select::try_select
+/// [`select`]: Select::select
+/// [`select_timeout`]: Select::select_timeout
+/// [`select_deadline`]: Select::select_deadline
+///
+/// # Examples
+///
+/// Receive a message from a list of receivers:
+///
+/// ```
+/// #![feature(mpmc_channel, mpmc_select)]
+///
+/// use std::sync::mpmc::{Receiver, RecvError, Select};
+///
+/// fn recv_multiple<T>(rs: &[Receiver<T>]) -> Result<T, RecvError> {
+///     // Build a list of operations.
+///     let mut sel = Select::new();
+///     for r in rs {
+///         sel.recv(r);
+///     }
+///
+///     // Complete the selected operation.
+///     let oper = sel.select();
+///     let index = oper.index();
+///     oper.recv(&rs[index])
+/// }
+/// ```
+///
+/// Channels of [`mpsc`] can be used as well:
+///
+/// ```
+/// #![feature(mpmc_channel, mpmc_select)]
+///
+/// use std::sync::mpmc::Select;
+/// use std::sync::mpsc;
+///
+/// let (tx1, rx1) = mpsc::channel::<&str>();
+/// let (tx2, rx2) = mpsc::sync_channel(1);
+/// tx2.send("hello").unwrap();
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&rx1);
+/// let oper2 = sel.recv(&rx2);
+///
+/// let oper = sel.select();
+/// assert_eq!(oper.index(), oper2);
+/// assert_eq!(oper.recv(&rx2), Ok("hello"));
+/// # drop((tx1, oper1));
+/// ```
+#[unstable(feature = "mpmc_select", issue = "none")]
+pub struct Select<'a> {
+    /// A list of senders and receivers participating in selection.
+    handles: Vec<(&'a dyn SelectHandle, usize, *const u8)>,
+
+    /// The next index to assign to an operation.
+    next_index: usize,
+
+    /// Whether to always prefer the operation that was added first.
+    is_biased: bool,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations for selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::Select;
+    ///
+    /// let mut sel = Select::new();
+    ///
+    /// // The list of operations is empty, which means no operation can be selected.
+    /// assert!(sel.try_select().is_err());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::with_capacity(4), next_index: 0, is_biased: false }
+    }
+
+    /// Creates an empty list of channel operations with biased selection.
+    ///
+    /// When multiple operations are ready at the same time, the one that was added first is
+    /// selected, instead of a random one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s1, r1) = channel();
+    /// let (s2, r2) = channel();
+    /// s1.send(1).unwrap();
+    /// s2.send(2).unwrap();
+    ///
+    /// let mut sel = Select::new_biased();
+    /// let oper1 = sel.recv(&r1);
+    /// let _oper2 = sel.recv(&r2);
+    ///
+    /// // Both operations are ready, but the first one is always preferred.
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper1);
+    /// assert_eq!(oper.recv(&r1), Ok(1));
+    /// ```
+    #[must_use]
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn new_biased() -> Select<'a> {
+        Select { is_biased: true, ..Select::new() }
+    }
+
+    /// Adds a send operation.
+    ///
+    /// Returns the index of the added operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s, r) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.send(&s);
+    /// # let _ = r;
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn send<T: 'a, S: SelectSender<T>>(&mut self, s: &'a S) -> usize {
+        let s = s.as_sender();
+        let i = self.next_index;
+        let ptr = s as *const Sender<T> as *const u8;
+        self.handles.push((s, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Adds a receive operation.
+    ///
+    /// Returns the index of the added operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s, r) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&r);
+    /// # let _ = s;
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn recv<T: 'a, R: SelectReceiver<T>>(&mut self, r: &'a R) -> usize {
+        let r = r.as_receiver();
+        let i = self.next_index;
+        let ptr = r as *const Receiver<T> as *const u8;
+        self.handles.push((r, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Removes a previously added operation.
+    ///
+    /// This is useful when an operation is selected because the channel got disconnected and we
+    /// want to try again to select a different operation instead.
+    ///
+    /// If new operations are added after removing some, the indices of removed operations will not
+    /// be reused.
+    ///
+    /// # Panics
+    ///
+    /// An attempt to remove a non-existing or already removed operation will panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s1, r1) = channel::<i32>();
+    /// let (_, r2) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&r1);
+    /// let oper2 = sel.recv(&r2);
+    ///
+    /// // Only the second operation is ready, because its channel is disconnected.
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper2);
+    /// assert!(oper.recv(&r2).is_err());
+    /// sel.remove(oper2);
+    ///
+    /// s1.send(10).unwrap();
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper1);
+    /// assert_eq!(oper.recv(&r1), Ok(10));
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        assert!(index < self.next_index, "index out of bounds; {index} >= {}", self.next_index);
+
+        let i = self
+            .handles
+            .iter()
+            .position(|&(_, i, _)| i == index)
+            .expect("no operation with this index");
+
+        self.handles.swap_remove(i);
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// If an operation is ready, it is selected and returned. If multiple operations are ready at
+    /// the same time, a random one among them is selected. If none of the operations are ready, an
+    /// error is returned.
+    ///
+    /// An operation is considered to be ready if it doesn't have to block. Note that it is ready
+    /// even when it will simply return an error because the channel is disconnected.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`]
+    /// or [`SelectedOperation::recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s1, r1) = channel();
+    /// let (s2, r2) = channel();
+    ///
+    /// s1.send(10).unwrap();
+    /// s2.send(20).unwrap();
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&r1);
+    /// let oper2 = sel.recv(&r2);
+    ///
+    /// // Both operations are initially ready, so a random one will be executed.
+    /// let oper = sel.try_select();
+    /// match oper {
+    ///     Err(_) => panic!("both operations should be ready"),
+    ///     Ok(oper) => match oper.index() {
+    ///         i if i == oper1 => assert_eq!(oper.recv(&r1), Ok(10)),
+    ///         i if i == oper2 => assert_eq!(oper.recv(&r2), Ok(20)),
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        match run_select(&mut self.handles, Timeout::Now, self.is_biased) {
+            None => Err(TrySelectError),
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+        }
+    }
+
+    /// Blocks until one of the operations becomes ready and selects it.
+    ///
+    /// Once an operation becomes ready, it is selected and returned. If multiple operations are
+    /// ready at the same time, a random one among them is selected.
+    ///
+    /// An operation is considered to be ready if it doesn't have to block. Note that it is ready
+    /// even when it will simply return an error because the channel is disconnected.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`]
+    /// or [`SelectedOperation::recv`].
+    ///
+    /// If the list of operations is empty, this method blocks forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let (s1, r1) = channel();
+    /// let (s2, r2) = channel::<i32>();
+    ///
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(100));
+    ///     s1.send(10).unwrap();
+    /// });
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_secs(10));
+    ///     let _ = s2.send(20);
+    /// });
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&r1);
+    /// let oper2 = sel.recv(&r2);
+    ///
+    /// // The first operation will be selected because it becomes ready first.
+    /// let oper = sel.select();
+    /// match oper.index() {
+    ///     i if i == oper1 => assert_eq!(oper.recv(&r1), Ok(10)),
+    ///     i if i == oper2 => assert_eq!(oper.recv(&r2), Ok(20)),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        let (token, index, ptr) = run_select(&mut self.handles, Timeout::Never, self.is_biased)
+            .expect("selection without a timeout cannot fail");
+        SelectedOperation::new(token, index, ptr)
+    }
+
+    /// Blocks for a limited time until one of the operations becomes ready and selects it.
+    ///
+    /// If an operation becomes ready, it is selected and returned. If multiple operations are
+    /// ready at the same time, a random one among them is selected. If none of the operations
+    /// become ready for the specified duration, an error is returned.
+    ///
+    /// An operation is considered to be ready if it doesn't have to block. Note that it is ready
+    /// even when it will simply return an error because the channel is disconnected.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`]
+    /// or [`SelectedOperation::recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    /// use std::time::Duration;
+    ///
+    /// let (_s, r) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&r);
+    ///
+    /// // Nothing is sent, so the selection times out.
+    /// assert!(sel.select_timeout(Duration::from_millis(10)).is_err());
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.select_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Ok(self.select()),
+        }
+    }
+
+    /// Blocks until a given deadline, or until one of the operations becomes ready and selects
+    /// it.
+    ///
+    /// If an operation becomes ready, it is selected and returned. If multiple operations are
+    /// ready at the same time, a random one among them is selected. If none of the operations
+    /// become ready before the given deadline, an error is returned.
+    ///
+    /// An operation is considered to be ready if it doesn't have to block. Note that it is ready
+    /// even when it will simply return an error because the channel is disconnected.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`]
+    /// or [`SelectedOperation::recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (s, r) = channel();
+    /// s.send(1).unwrap();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&r);
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(100);
+    /// let oper = sel.select_deadline(deadline).unwrap();
+    /// assert_eq!(oper.recv(&r), Ok(1));
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match run_select(&mut self.handles, Timeout::At(deadline), self.is_biased) {
+            None => Err(SelectTimeoutError),
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<'a> Clone for Select<'a> {
+    fn clone(&self) -> Select<'a> {
+        Select {
+            handles: self.handles.clone(),
+            next_index: self.next_index,
+            is_biased: self.is_biased,
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<'a> Default for Select<'a> {
+    fn default() -> Select<'a> {
+        Select::new()
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").finish_non_exhaustive()
+    }
+}
+
+/// A selected operation that needs to be completed.
+///
+/// To complete the operation, call [`send`] or [`recv`].
+///
+/// # Panics
+///
+/// Forgetting to complete the operation is an error and might lead to deadlocks. If a
+/// `SelectedOperation` is dropped without completion, a panic occurs.
+///
+/// [`send`]: SelectedOperation::send
+/// [`recv`]: SelectedOperation::recv
+#[must_use]
+#[unstable(feature = "mpmc_select", issue = "none")]
+pub struct SelectedOperation<'a> {
+    /// Token needed to complete the operation.
+    token: Token,
+
+    /// The index of the selected operation.
+    index: usize,
+
+    /// The address of the selected `Sender` or `Receiver`.
+    ptr: *const u8,
+
+    /// Indicates that `Sender`s and `Receiver`s are borrowed.
+    _marker: PhantomData<&'a ()>,
+}
+
+impl SelectedOperation<'_> {
+    fn new(token: Token, index: usize, ptr: *const u8) -> Self {
+        SelectedOperation { token, index, ptr, _marker: PhantomData }
+    }
+
+    /// Returns the index of the selected operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select};
+    ///
+    /// let (s1, r1) = channel::<()>();
+    /// let (s2, r2) = channel::<()>();
+    /// let (s3, r3) = channel::<()>();
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&r1);
+    /// let oper2 = sel.recv(&r2);
+    /// let oper3 = sel.recv(&r3);
+    ///
+    /// // Only the second operation will be ready.
+    /// s2.send(()).unwrap();
+    ///
+    /// // The index of the selected operation should be the index of the second operation.
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper2);
+    /// assert_ne!(oper.index(), oper1);
+    /// assert_ne!(oper.index(), oper3);
+    /// oper.recv(&r2).unwrap();
+    /// # drop((s1, s3));
+    /// ```
+    #[must_use]
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Completes the send operation.
+    ///
+    /// The passed [`Sender`] reference must be the same one that was used in
+    /// [`Select::send`] when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Sender`] reference is passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select, SendError};
+    ///
+    /// let (s, r) = channel::<i32>();
+    /// drop(r);
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.send(&s);
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper1);
+    /// assert_eq!(oper.send(&s, 10), Err(SendError(10)));
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn send<T, S: SelectSender<T>>(mut self, s: &S, msg: T) -> Result<(), SendError<T>> {
+        let s = s.as_sender();
+        assert!(
+            s as *const Sender<T> as *const u8 == self.ptr,
+            "passed a sender that wasn't selected",
+        );
+        // SAFETY: the operation was selected on this very sender.
+        let res = unsafe { s.write(&mut self.token, msg) };
+        mem::forget(self);
+        res.map_err(SendError)
+    }
+
+    /// Completes the receive operation.
+    ///
+    /// The passed [`Receiver`] reference must be the same one that was used in
+    /// [`Select::recv`] when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Receiver`] reference is passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel, mpmc_select)]
+    ///
+    /// use std::sync::mpmc::{channel, Select, RecvError};
+    ///
+    /// let (s, r) = channel::<i32>();
+    /// drop(s);
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&r);
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper1);
+    /// assert_eq!(oper.recv(&r), Err(RecvError));
+    /// ```
+    #[unstable(feature = "mpmc_select", issue = "none")]
+    pub fn recv<T, R: SelectReceiver<T>>(mut self, r: &R) -> Result<T, RecvError> {
+        let r = r.as_receiver();
+        assert!(
+            r as *const Receiver<T> as *const u8 == self.ptr,
+            "passed a receiver that wasn't selected",
+        );
+        // SAFETY: the operation was selected on this very receiver.
+        let res = unsafe { r.read(&mut self.token) };
+        mem::forget(self);
+        res.map_err(|_| RecvError)
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl Drop for SelectedOperation<'_> {
+    fn drop(&mut self) {
+        // Don't turn a panic while completing the operation into an abort.
+        if !thread::panicking() {
+            panic!("dropped `SelectedOperation` without completing the operation");
+        }
+    }
+}
+
+/// Selects from a set of channel operations.
+///
+/// This macro allows you to define a set of channel operations, wait until any one of them
+/// becomes ready, and finally execute it. If multiple operations are ready at the same time, a
+/// random one among them is selected.
+///
+/// It is also possible to define a `default` case that gets executed if none of the operations
+/// are ready, either right away or for a certain duration of time.
+///
+/// An operation is considered to be ready if it doesn't have to block. Note that it is ready even
+/// when it will simply return an error because the channel is disconnected.
+///
+/// The `select!` macro is a convenience wrapper around [`Select`]. However, it cannot select over
+/// a dynamically created list of channel operations.
+///
+/// # Syntax
+///
+/// Each case is one of:
+///
+/// * `recv(receiver) -> result => body`, where `result` is a pattern matched against the
+///   `Result<T, RecvError>` of the receive operation,
+/// * `send(sender, message) -> result => body`, where `result` is a pattern matched against the
+///   `Result<(), SendError<T>>` of the send operation. The `message` expression is only evaluated
+///   if the operation is selected,
+/// * `default => body`, which is executed if no operation is ready right away,
+/// * `default(timeout) => body`, which is executed if no operation becomes ready within the
+///   [`Duration`] `timeout`.
+///
+/// There can be at most one `default` case, and it must come last. The channels can be those of
+/// this module or of [`mpsc`](crate::sync::mpsc). Without a `default` case and without any
+/// operations, `select!` blocks forever.
+///
+/// # Examples
+///
+/// Block until a message can be received from either of two channels:
+///
+/// ```
+/// #![feature(mpmc_channel, mpmc_select)]
+///
+/// use std::sync::mpmc::{channel, select};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (s1, r1) = channel();
+/// let (s2, r2) = channel();
+///
+/// thread::spawn(move || s1.send(10).unwrap());
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(500));
+///     s2.send(20).unwrap();
+/// });
+///
+/// // At most one of these two receive operations will be executed.
+/// select! {
+///     recv(r1) -> msg => assert_eq!(msg, Ok(10)),
+///     recv(r2) -> msg => assert_eq!(msg, Ok(20)),
+/// }
+/// ```
+///
+/// Send a message, or give up after a timeout:
+///
+/// ```
+/// #![feature(mpmc_channel, mpmc_select)]
+///
+/// use std::sync::mpmc::{select, sync_channel};
+/// use std::time::Duration;
+///
+/// let (s, r) = sync_channel(0);
+///
+/// // Nobody is receiving from the zero-capacity channel, so the send cannot complete.
+/// let sent = select! {
+///     send(s, "hello") -> res => res.is_ok(),
+///     default(Duration::from_millis(10)) => false,
+/// };
+/// assert!(!sent);
+/// # drop(r);
+/// ```
+///
+/// [`Duration`]: crate::time::Duration
+#[unstable(feature = "mpmc_select", issue = "none")]
+#[allow_internal_unstable(mpmc_channel, mpmc_select)]
+#[rustc_macro_transparency = "semiopaque"]
+pub macro select {
+    // Each case is recorded as `(method channel index (expr) (extra) (pattern) (body))`. The
+    // `channel` and `index` identifiers are created by the expansion that parses the case, so
+    // hygiene keeps them apart from those of the other cases.
+    (@parse [$($cases:tt)*] [] recv($r:expr) -> $res:pat => $body:block $($tail:tt)*) => {
+        $crate::sync::mpmc::select!(
+            @parse [$($cases)* (recv chan index ($r) () ($res) ($body))] [] $($tail)*
+        )
+    },
+    (@parse [$($cases:tt)*] [] recv($r:expr) -> $res:pat => $body:expr $(, $($tail:tt)*)?) => {
+        $crate::sync::mpmc::select!(
+            @parse [$($cases)* (recv chan index ($r) () ($res) ($body))] [] $($($tail)*)?
+        )
+    },
+    (
+        @parse [$($cases:tt)*] []
+        send($s:expr, $m:expr $(,)?) -> $res:pat => $body:block $($tail:tt)*
+    ) => {
+        $crate::sync::mpmc::select!(
+            @parse [$($cases)* (send chan index ($s) (, $m) ($res) ($body))] [] $($tail)*
+        )
+    },
+    (
+        @parse [$($cases:tt)*] []
+        send($s:expr, $m:expr $(,)?) -> $res:pat => $body:expr $(, $($tail:tt)*)?
+    ) => {
+        $crate::sync::mpmc::select!(
+            @parse [$($cases)* (send chan index ($s) (, $m) ($res) ($body))] [] $($($tail)*)?
+        )
+    },
+    (@parse $cases:tt [] default => $body:expr $(,)?) => {
+        $crate::sync::mpmc::select!(@parse $cases [(now) ($body)])
+    },
+    (@parse $cases:tt [] default($timeout:expr $(,)?) => $body:expr $(,)?) => {
+        $crate::sync::mpmc::select!(@parse $cases [(timeout $timeout) ($body)])
+    },
+    // A block body may be followed by a comma.
+    (@parse $cases:tt [] , $($tail:tt)*) => {
+        $crate::sync::mpmc::select!(@parse $cases [] $($tail)*)
+    },
+    // All cases have been parsed.
+    (@parse [$(($method:ident $chan:ident $index:ident ($ch:expr) $extra:tt $res:tt $body:tt))*] $default:tt) => {{
+        let mut sel = $crate::sync::mpmc::Select::new();
+        $(
+            let $chan = &$ch;
+            let $index = sel.$method($chan);
+        )*
+        $crate::sync::mpmc::select!(
+            @run sel $default [$(($method $chan $index $extra $res $body))*]
+        )
+    }},
+    (@parse $($tokens:tt)*) => {
+        $crate::compile_error!("invalid syntax in `select!`")
+    },
+    (@run $sel:ident [] $cases:tt) => {{
+        let oper = $sel.select();
+        $crate::sync::mpmc::select!(@complete oper $cases)
+    }},
+    (@run $sel:ident [(now) ($default:expr)] $cases:tt) => {
+        match $sel.try_select() {
+            $crate::result::Result::Ok(oper) => $crate::sync::mpmc::select!(@complete oper $cases),
+            $crate::result::Result::Err(_) => $default,
+        }
+    },
+    (@run $sel:ident [(timeout $timeout:expr) ($default:expr)] $cases:tt) => {
+        match $sel.select_timeout($timeout) {
+            $crate::result::Result::Ok(oper) => $crate::sync::mpmc::select!(@complete oper $cases),
+            $crate::result::Result::Err(_) => $default,
+        }
+    },
+    (
+        @complete $oper:ident
+        [$(($method:ident $chan:ident $index:ident ($($extra:tt)*) ($res:pat) ($body:expr)))*]
+    ) => {{
+        let i = $oper.index();
+        $(
+            if i == $index {
+                let $res = $oper.$method($chan $($extra)*);
+                $body
+            } else
+        )* {
+            $crate::unreachable!()
+        }
+    }},
+    ($($tokens:tt)*) => {
+        $crate::sync::mpmc::select!(@parse [] [] $($tokens)*)
+    },
+}
@@ -657,6 +657,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::sealed::AsSender<T> for Sender<T> {
+    fn as_sender(&self) -> &mpmc::Sender<T> {
+        &self.inner
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::SelectSender<T> for Sender<T> {}
+
 ////////////////////////////////////////////////////////////////////////////////
 // SyncSender
 ////////////////////////////////////////////////////////////////////////////////
@@ -789,6 +799,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::sealed::AsSender<T> for SyncSender<T> {
+    fn as_sender(&self) -> &mpmc::Sender<T> {
+        &self.inner
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::SelectSender<T> for SyncSender<T> {}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Receiver
 ////////////////////////////////////////////////////////////////////////////////
@@ -1148,6 +1168,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::sealed::AsReceiver<T> for Receiver<T> {
+    fn as_receiver(&self) -> &mpmc::Receiver<T> {
+        &self.inner
+    }
+}
+
+#[unstable(feature = "mpmc_select", issue = "none")]
+impl<T> mpmc::SelectReceiver<T> for Receiver<T> {}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> fmt::Debug for SendError<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
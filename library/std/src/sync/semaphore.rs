@@ -0,0 +1,298 @@
+use crate::fmt;
+use crate::sys::sync as sys;
+use crate::time::{Duration, Instant};
+
+/// A counting semaphore.
+///
+/// A semaphore holds a number of permits. Threads [acquire](Semaphore::acquire)
+/// permits, waiting until enough of them are available, and give them back
+/// when the returned [`SemaphorePermit`] is dropped. This is commonly used to
+/// limit how many threads access a resource at the same time.
+///
+/// # Fairness
+///
+/// Permits are handed out in the order in which they were requested. A thread
+/// that is waiting for permits is never overtaken by threads that request
+/// permits later, even if they request fewer permits and enough of them are
+/// available. In particular, [`try_acquire`](Semaphore::try_acquire) fails
+/// while other threads are waiting.
+///
+/// This means that a thread waiting for many permits cannot be starved by
+/// threads acquiring few permits at a time, but also that it holds up all
+/// threads behind it until enough permits are available.
+///
+/// # Examples
+///
+/// Limiting the number of concurrent downloads:
+///
+/// ```
+/// #![feature(sync_semaphore)]
+///
+/// use std::sync::Semaphore;
+/// use std::thread;
+///
+/// let downloads = Semaphore::new(4);
+/// thread::scope(|s| {
+///     for url in 0..20 {
+///         let downloads = &downloads;
+///         s.spawn(move || {
+///             // At most four threads get past this point at a time.
+///             let _permit = downloads.acquire();
+///             println!("downloading {url}");
+///         });
+///     }
+/// });
+/// assert_eq!(downloads.available_permits(), 4);
+/// ```
+#[unstable(feature = "sync_semaphore", issue = "none")]
+pub struct Semaphore {
+    inner: sys::Semaphore,
+}
+
+/// Permits acquired from a [`Semaphore`].
+///
+/// The permits are given back to the semaphore when this is dropped, unless
+/// they are [forgotten](SemaphorePermit::forget).
+#[must_use = "if unused the permits will immediately be given back"]
+#[unstable(feature = "sync_semaphore", issue = "none")]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    count: usize,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with `permits` available permits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is larger than `usize::MAX / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(3);
+    /// assert_eq!(semaphore.available_permits(), 3);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub const fn new(permits: usize) -> Semaphore {
+        Semaphore { inner: sys::Semaphore::new(permits) }
+    }
+
+    /// Acquires a permit, blocking the current thread until one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.acquire();
+    /// assert_eq!(semaphore.available_permits(), 0);
+    /// drop(permit);
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Acquires `count` permits at once, blocking the current thread until
+    /// they are available.
+    ///
+    /// Acquiring zero permits always succeeds immediately. If `count` is
+    /// larger than the number of permits that will ever be available, this
+    /// blocks forever.
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn acquire_many(&self, count: usize) -> SemaphorePermit<'_> {
+        match self.acquire_inner(count, None) {
+            Some(permit) => permit,
+            None => unreachable!("acquiring without a timeout cannot time out"),
+        }
+    }
+
+    /// Acquires a permit if one is available, without blocking.
+    ///
+    /// Returns `None` if no permit is available, or if other threads are
+    /// waiting for permits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.try_acquire().unwrap();
+    /// assert!(semaphore.try_acquire().is_none());
+    /// drop(permit);
+    /// assert!(semaphore.try_acquire().is_some());
+    /// ```
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Acquires `count` permits at once if they are available, without
+    /// blocking.
+    ///
+    /// Returns `None` if fewer than `count` permits are available, or if
+    /// other threads are waiting for permits.
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn try_acquire_many(&self, count: usize) -> Option<SemaphorePermit<'_>> {
+        if self.inner.try_acquire(count) {
+            Some(SemaphorePermit { semaphore: self, count })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires a permit, blocking the current thread until one is available
+    /// or `timeout` has elapsed.
+    ///
+    /// Returns `None` if the timeout elapsed before a permit became available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    /// use std::time::Duration;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let _permit = semaphore.acquire();
+    /// assert!(semaphore.acquire_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn acquire_timeout(&self, timeout: Duration) -> Option<SemaphorePermit<'_>> {
+        self.acquire_many_timeout(1, timeout)
+    }
+
+    /// Acquires `count` permits at once, blocking the current thread until
+    /// they are available or `timeout` has elapsed.
+    ///
+    /// Returns `None` if the timeout elapsed before the permits became
+    /// available. Acquiring zero permits always succeeds immediately.
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn acquire_many_timeout(
+        &self,
+        count: usize,
+        timeout: Duration,
+    ) -> Option<SemaphorePermit<'_>> {
+        // A timeout too large to represent is as good as none.
+        self.acquire_inner(count, Instant::now().checked_add(timeout))
+    }
+
+    fn acquire_inner(
+        &self,
+        count: usize,
+        deadline: Option<Instant>,
+    ) -> Option<SemaphorePermit<'_>> {
+        if count == 0 || self.inner.acquire(count, deadline) {
+            Some(SemaphorePermit { semaphore: self, count })
+        } else {
+            None
+        }
+    }
+
+    /// Makes `count` more permits available.
+    ///
+    /// This can be used to increase the number of permits of a semaphore,
+    /// or to give back permits that were [forgotten](SemaphorePermit::forget).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of available permits would be larger than
+    /// `usize::MAX / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// assert!(semaphore.try_acquire().is_none());
+    /// semaphore.add_permits(1);
+    /// assert!(semaphore.try_acquire().is_some());
+    /// ```
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn add_permits(&self, count: usize) {
+        self.inner.release(count);
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// Permits that are available may still be reserved for threads that are
+    /// waiting for more permits than are available.
+    #[must_use]
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn available_permits(&self) -> usize {
+        self.inner.available_permits()
+    }
+}
+
+#[unstable(feature = "sync_semaphore", issue = "none")]
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    #[must_use]
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Consumes the permits without giving them back to the semaphore.
+    ///
+    /// This permanently reduces the number of permits of the semaphore,
+    /// unless they are added back with [`Semaphore::add_permits`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sync_semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(2);
+    /// semaphore.acquire().forget();
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "sync_semaphore", issue = "none")]
+    pub fn forget(self) {
+        crate::mem::forget(self);
+    }
+}
+
+#[unstable(feature = "sync_semaphore", issue = "none")]
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.count > 0 {
+            self.semaphore.add_permits(self.count);
+        }
+    }
+}
+
+#[unstable(feature = "sync_semaphore", issue = "none")]
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit").field("count", &self.count).finish_non_exhaustive()
+    }
+}
@@ -68,6 +68,21 @@ pub fn read_dir(path: &Path) -> io::Result<ReadDir> {
     imp::readdir(path)
 }
 
+/// Opens the subdirectory `entry` of a directory that is being read, for
+/// walking a directory tree.
+///
+/// Where supported, the subdirectory is opened relative to its parent's file
+/// descriptor, so that it cannot be replaced by a symlink after it was read.
+pub fn read_dir_at(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+    #[cfg(any(target_family = "unix", target_os = "wasi"))]
+    return imp::read_dir_at(entry, follow_symlinks);
+    #[cfg(not(any(target_family = "unix", target_os = "wasi")))]
+    {
+        let _ = follow_symlinks;
+        read_dir(&entry.path())
+    }
+}
+
 pub fn remove_file(path: &Path) -> io::Result<()> {
     with_native_path(path, &imp::unlink)
 }
@@ -108,6 +123,51 @@ pub fn hard_link(original: &Path, link: &Path) -> io::Result<()> {
     })
 }
 
+/// Opens a new file in the directory `dir` that has no name until it is
+/// linked with [`link_tmpfile`].
+///
+/// Returns `None` where unnamed files are not supported, in which case a
+/// named temporary file has to be used instead.
+pub fn open_tmpfile(dir: &Path) -> io::Result<Option<File>> {
+    #[cfg(target_os = "linux")]
+    return imp::open_tmpfile(dir);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = dir;
+        Ok(None)
+    }
+}
+
+/// Gives a file opened with [`open_tmpfile`] the name `link`, which must not
+/// exist yet.
+pub fn link_tmpfile(file: &File, link: &Path) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    return imp::link_tmpfile(file, link);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (file, link);
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+}
+
+/// Flushes the entries of the directory `dir` to disk, so that files which
+/// were created in or renamed into it persist.
+///
+/// This does nothing on platforms where directories cannot be synced.
+pub fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let mut opts = OpenOptions::new();
+        opts.read(true);
+        File::open(dir, &opts)?.fsync()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
 pub fn metadata(path: &Path) -> io::Result<FileAttr> {
     with_native_path(path, &imp::stat)
 }
@@ -156,6 +216,37 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     with_native_path(from, &|from| with_native_path(to, &|to| imp::copy(from, to)))
 }
 
+/// Copies the file `from` to `to` by cloning it, so that the copy shares its
+/// data with the original until either is modified. This is also known as a
+/// reflink.
+///
+/// `to` must not exist yet. It is not left behind if cloning fails.
+///
+/// Returns an error if the platform or the file system does not support it.
+pub fn clone_file(from: &Path, to: &Path) -> io::Result<u64> {
+    #[cfg(any(target_os = "linux", target_vendor = "apple"))]
+    return imp::clone_file(from, to);
+    #[cfg(not(any(target_os = "linux", target_vendor = "apple")))]
+    {
+        let _ = (from, to);
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+}
+
+/// Creates a symlink at `link` that points to the same target as the
+/// symlink `original`.
+pub fn copy_symlink(original: &Path, link: &Path) -> io::Result<()> {
+    let target = read_link(original)?;
+    #[cfg(windows)]
+    {
+        // Windows distinguishes between symlinks to files and to directories.
+        let dir = symlink_metadata(original)?.file_type().is_symlink_dir();
+        imp::symlink_inner(&target, link, dir)
+    }
+    #[cfg(not(windows))]
+    symlink(&target, link)
+}
+
 pub fn exists(path: &Path) -> io::Result<bool> {
     // FIXME: use with_native_path on all platforms
     #[cfg(not(windows))]
@@ -2052,6 +2052,49 @@ pub fn link(original: &CStr, link: &CStr) -> io::Result<()> {
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+pub fn open_tmpfile(dir: &Path) -> io::Result<Option<File>> {
+    // The file can only be linked through its entry in `/proc`, so don't
+    // create one that could never be linked.
+    if stat(c"/proc/self/fd").is_err() {
+        return Ok(None);
+    }
+
+    let mut opts = OpenOptions::new();
+    opts.write(true);
+    opts.custom_flags(libc::O_TMPFILE);
+    match File::open(dir, &opts) {
+        Ok(file) => Ok(Some(file)),
+        Err(e) => match e.raw_os_error() {
+            // Kernels before 3.11 don't know `O_TMPFILE` and fail with
+            // `EISDIR` because it includes `O_DIRECTORY`, and not every file
+            // system supports it.
+            Some(libc::EISDIR | libc::EOPNOTSUPP | libc::EINVAL) => Ok(None),
+            _ => Err(e),
+        },
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn link_tmpfile(file: &File, link: &Path) -> io::Result<()> {
+    // `AT_EMPTY_PATH` would need `CAP_DAC_READ_SEARCH`, so go through `/proc`.
+    let original = format!("/proc/self/fd/{}", file.as_raw_fd());
+    run_path_with_cstr(Path::new(&original), &|original| {
+        run_path_with_cstr(link, &|link| {
+            cvt(unsafe {
+                libc::linkat(
+                    libc::AT_FDCWD,
+                    original.as_ptr(),
+                    libc::AT_FDCWD,
+                    link.as_ptr(),
+                    libc::AT_SYMLINK_FOLLOW,
+                )
+            })
+            .map(|_| ())
+        })
+    })
+}
+
 pub fn stat(p: &CStr) -> io::Result<FileAttr> {
     cfg_has_statx! {
         if let Some(ret) = unsafe { try_statx(
@@ -2350,6 +2393,45 @@ fn drop(&mut self) {
     Ok(bytes_copied as u64)
 }
 
+#[cfg(target_os = "linux")]
+pub fn clone_file(from: &Path, to: &Path) -> io::Result<u64> {
+    use crate::fs::OpenOptions;
+    use crate::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let (reader, reader_metadata) = open_from(from)?;
+    let perm = reader_metadata.permissions();
+    // Like `fclonefileat`, only create a new file, so that neither an existing
+    // file nor the target of a symlink is written to, and the file can be
+    // removed again if cloning fails.
+    let writer = OpenOptions::new().mode(perm.mode()).write(true).create_new(true).open(to)?;
+    let result = writer.set_permissions(perm).and_then(|()| {
+        cvt(unsafe { libc::ioctl(writer.as_raw_fd(), libc::FICLONE, reader.as_raw_fd()) })
+    });
+    if let Err(e) = result {
+        drop(writer);
+        let _ = run_path_with_cstr(to, &unlink);
+        return Err(e);
+    }
+    Ok(reader_metadata.len())
+}
+
+#[cfg(target_vendor = "apple")]
+pub fn clone_file(from: &Path, to: &Path) -> io::Result<u64> {
+    let (reader, reader_metadata) = open_from(from)?;
+    run_path_with_cstr(to, &|to| {
+        cvt(unsafe { libc::fclonefileat(reader.as_raw_fd(), libc::AT_FDCWD, to.as_ptr(), 0) })
+    })
+    .map_err(|e| match e.raw_os_error() {
+        // `ENOTSUP` is distinct from `EOPNOTSUPP` here, so it isn't decoded
+        // as `Unsupported`.
+        Some(libc::ENOTSUP) => {
+            io::const_error!(io::ErrorKind::Unsupported, "file system does not support cloning")
+        }
+        _ => e,
+    })?;
+    Ok(reader_metadata.len())
+}
+
 #[cfg(not(target_os = "wasi"))]
 pub fn chown(path: &Path, uid: u32, gid: u32) -> io::Result<()> {
     run_path_with_cstr(path, &|path| {
@@ -2396,7 +2478,7 @@ pub fn mkfifo(path: &Path, mode: u32) -> io::Result<()> {
     })
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub use remove_dir_impl::{read_dir_at, remove_dir_all};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita, Vxworks and Miri
 #[cfg(any(
@@ -2409,7 +2491,13 @@ pub fn mkfifo(path: &Path, mode: u32) -> io::Result<()> {
     miri
 ))]
 mod remove_dir_impl {
+    use super::{DirEntry, ReadDir, readdir};
+    use crate::io;
     pub use crate::sys::fs::common::remove_dir_all;
+
+    pub fn read_dir_at(entry: &DirEntry, _follow_symlinks: bool) -> io::Result<ReadDir> {
+        readdir(&entry.path())
+    }
 }
 
 // Modern implementation using openat(), unlinkat() and fdopendir()
@@ -2439,17 +2527,25 @@ mod remove_dir_impl {
     use crate::sys::{cvt, cvt_r};
 
     pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result<OwnedFd> {
+        openat_dironly(parent_fd, p, libc::O_NOFOLLOW)
+    }
+
+    fn openat_dironly(
+        parent_fd: Option<RawFd>,
+        p: &CStr,
+        flags: libc::c_int,
+    ) -> io::Result<OwnedFd> {
         let fd = cvt_r(|| unsafe {
             openat(
                 parent_fd.unwrap_or(libc::AT_FDCWD),
                 p.as_ptr(),
-                libc::O_CLOEXEC | libc::O_RDONLY | libc::O_NOFOLLOW | libc::O_DIRECTORY,
+                libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY | flags,
             )
         })?;
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    fn fdreaddir(dir_fd: OwnedFd, root: PathBuf) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
@@ -2457,13 +2553,30 @@ fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let dirp = DirStream(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
-        // a valid root is not needed because we do not call any functions involving the full path
-        // of the `DirEntry`s.
-        let dummy_root = PathBuf::new();
-        let inner = InnerReadDir { dirp, root: dummy_root };
+        let inner = InnerReadDir { dirp, root };
         Ok((ReadDir::new(inner), new_parent_fd))
     }
 
+    // dirfd isn't supported everywhere
+    #[cfg(any(target_os = "hurd", target_os = "rtems", target_os = "nuttx", target_os = "wasi"))]
+    pub fn read_dir_at(entry: &DirEntry, _follow_symlinks: bool) -> io::Result<ReadDir> {
+        super::readdir(&entry.path())
+    }
+
+    #[cfg(not(any(
+        target_os = "hurd",
+        target_os = "rtems",
+        target_os = "nuttx",
+        target_os = "wasi"
+    )))]
+    pub fn read_dir_at(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+        let parent_fd = cvt(unsafe { libc::dirfd(entry.dir.dirp.0) })?;
+        let flags = if follow_symlinks { 0 } else { libc::O_NOFOLLOW };
+        let fd = openat_dironly(Some(parent_fd), entry.name_cstr(), flags)?;
+        let (dir, _) = fdreaddir(fd, entry.path())?;
+        Ok(dir)
+    }
+
     #[cfg(any(
         target_os = "solaris",
         target_os = "illumos",
@@ -2519,7 +2632,9 @@ fn remove_dir_all_recursive(parent_fd: Option<RawFd>, path: &CStr) -> io::Result
         };
 
         // open the directory passing ownership of the fd
-        let (dir, fd) = fdreaddir(fd)?;
+        // a valid root is not needed because we do not call any functions involving the full path
+        // of the `DirEntry`s.
+        let (dir, fd) = fdreaddir(fd, PathBuf::new())?;
 
         // For WASI all directory entries for this directory are read first
         // before any removal is done. This works around the fact that the
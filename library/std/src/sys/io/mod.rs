@@ -28,6 +28,8 @@ mod is_terminal {
 }
 
 mod kernel_copy;
+#[cfg(target_family = "unix")]
+mod poller;
 
 #[cfg_attr(not(target_os = "linux"), allow(unused_imports))]
 #[cfg(all(
@@ -44,6 +46,8 @@ mod is_terminal {
 pub use error::{decode_error_kind, errno, error_string, is_interrupted};
 pub use is_terminal::is_terminal;
 pub use kernel_copy::{CopyState, kernel_copy};
+#[cfg(target_family = "unix")]
+pub use poller::Poller;
 
 // Bare metal platforms usually have very small amounts of RAM
 // (in the order of hundreds of KB)
@@ -0,0 +1,100 @@
+use libc::{c_int, epoll_event};
+
+use super::timeout_ms;
+use crate::io::{self, Event};
+use crate::os::fd::{AsRawFd, BorrowedFd, FromRawFd};
+use crate::sys::fd::FileDesc;
+use crate::sys::pal::cvt;
+use crate::time::Duration;
+use crate::{cmp, ptr};
+
+pub struct Poller {
+    epfd: FileDesc,
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        let epfd = cvt(unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) })?;
+        Ok(Poller { epfd: unsafe { FileDesc::from_raw_fd(epfd) } })
+    }
+
+    fn ctl(
+        &self,
+        op: c_int,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        let mut events = 0;
+        if readable {
+            events |= libc::EPOLLIN | libc::EPOLLRDHUP;
+        }
+        if writable {
+            events |= libc::EPOLLOUT;
+        }
+        let mut event = epoll_event { events: events as u32, u64: token as u64 };
+        cvt(unsafe { libc::epoll_ctl(self.epfd.as_raw_fd(), op, fd.as_raw_fd(), &mut event) })?;
+        Ok(())
+    }
+
+    pub fn register(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_ADD, fd, token, readable, writable)
+    }
+
+    pub fn reregister(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_MOD, fd, token, readable, writable)
+    }
+
+    pub fn deregister(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        let fd = fd.as_raw_fd();
+        cvt(unsafe {
+            libc::epoll_ctl(self.epfd.as_raw_fd(), libc::EPOLL_CTL_DEL, fd, ptr::null_mut())
+        })?;
+        Ok(())
+    }
+
+    pub fn poll(
+        &self,
+        events: &mut Vec<Event>,
+        capacity: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        let mut buf: Vec<epoll_event> = Vec::with_capacity(capacity);
+        let max = cmp::min(capacity, c_int::MAX as usize) as c_int;
+        let n = match cvt(unsafe {
+            libc::epoll_wait(self.epfd.as_raw_fd(), buf.as_mut_ptr(), max, timeout_ms(timeout))
+        }) {
+            Ok(n) => n as usize,
+            Err(e) if e.is_interrupted() => 0,
+            Err(e) => return Err(e),
+        };
+        // SAFETY: `epoll_wait` initialized the first `n` events.
+        unsafe { buf.set_len(n) };
+
+        events.extend(buf.iter().map(|event| {
+            let flags = event.events as c_int;
+            Event {
+                token: event.u64 as usize,
+                readable: flags
+                    & (libc::EPOLLIN | libc::EPOLLPRI | libc::EPOLLHUP | libc::EPOLLRDHUP)
+                    != 0,
+                writable: flags & libc::EPOLLOUT != 0,
+                error: flags & libc::EPOLLERR != 0,
+            }
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use libc::c_int;
+
+use crate::io::{self, Event};
+use crate::os::fd::{AsRawFd, BorrowedFd, FromRawFd, RawFd};
+use crate::sys::fd::FileDesc;
+use crate::sys::pal::cvt;
+use crate::time::Duration;
+use crate::{cmp, iter, mem, ptr};
+
+pub struct Poller {
+    kq: FileDesc,
+}
+
+fn kevent(fd: RawFd, token: usize) -> libc::kevent {
+    let mut event: libc::kevent = unsafe { mem::zeroed() };
+    event.ident = fd as _;
+    cfg_select! {
+        target_os = "netbsd" => {
+            event.udata = token as libc::intptr_t;
+        }
+        _ => {
+            event.udata = ptr::without_provenance_mut(token);
+        }
+    }
+    event
+}
+
+fn token(event: &libc::kevent) -> usize {
+    cfg_select! {
+        target_os = "netbsd" => {
+            event.udata as usize
+        }
+        _ => {
+            event.udata.addr()
+        }
+    }
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        cfg_select! {
+            target_os = "netbsd" => {
+                let kq = unsafe { FileDesc::from_raw_fd(cvt(libc::kqueue1(libc::O_CLOEXEC))?) };
+            }
+            _ => {
+                // There is no `kqueue1` here, but a kqueue is never inherited
+                // by a child created with `fork`, so there is no window in
+                // which another thread could leak it into an `exec`ed program.
+                let kq = unsafe { FileDesc::from_raw_fd(cvt(libc::kqueue())?) };
+                kq.set_cloexec()?;
+            }
+        }
+        Ok(Poller { kq })
+    }
+
+    /// Applies `changes`, returning the first error reported for any of them
+    /// other than deleting a filter that was never added. Returns how many
+    /// of those there were.
+    fn apply(&self, changes: &mut [libc::kevent]) -> io::Result<usize> {
+        // With `EV_RECEIPT`, every change is reported back in order as an
+        // event with `EV_ERROR` set and the error code, or zero, in `data`.
+        for change in changes.iter_mut() {
+            change.flags |= libc::EV_RECEIPT;
+        }
+        let mut receipts: [libc::kevent; 2] = unsafe { mem::zeroed() };
+        let receipts = &mut receipts[..changes.len()];
+        cvt(unsafe {
+            libc::kevent(
+                self.kq.as_raw_fd(),
+                changes.as_ptr(),
+                changes.len() as _,
+                receipts.as_mut_ptr(),
+                receipts.len() as _,
+                ptr::null(),
+            )
+        })?;
+        let mut missing = 0;
+        for (change, receipt) in iter::zip(changes, receipts) {
+            match receipt.data as i32 {
+                0 => {}
+                libc::ENOENT if change.flags & libc::EV_DELETE != 0 => missing += 1,
+                code => return Err(io::Error::from_raw_os_error(code)),
+            }
+        }
+        Ok(missing)
+    }
+
+    pub fn register(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        // Only the filters for the requested interest are added, as some are
+        // not supported everywhere: FreeBSD rejects `EVFILT_WRITE` for the
+        // read end of a pipe, for example.
+        let fd = fd.as_raw_fd();
+        let mut changes = [kevent(fd, token), kevent(fd, token)];
+        let mut len = 0;
+        for (filter, wanted) in [(libc::EVFILT_READ, readable), (libc::EVFILT_WRITE, writable)] {
+            if wanted {
+                changes[len].filter = filter;
+                changes[len].flags = libc::EV_ADD;
+                len += 1;
+            }
+        }
+        self.apply(&mut changes[..len]).map(drop)
+    }
+
+    pub fn reregister(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        // Adding a filter again updates its token. A filter that is no longer
+        // wanted is deleted, if it was added before.
+        let fd = fd.as_raw_fd();
+        let mut changes = [kevent(fd, token), kevent(fd, token)];
+        changes[0].filter = libc::EVFILT_READ;
+        changes[0].flags = if readable { libc::EV_ADD } else { libc::EV_DELETE };
+        changes[1].filter = libc::EVFILT_WRITE;
+        changes[1].flags = if writable { libc::EV_ADD } else { libc::EV_DELETE };
+        self.apply(&mut changes).map(drop)
+    }
+
+    pub fn deregister(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        let fd = fd.as_raw_fd();
+        let mut changes = [kevent(fd, 0), kevent(fd, 0)];
+        changes[0].filter = libc::EVFILT_READ;
+        changes[0].flags = libc::EV_DELETE;
+        changes[1].filter = libc::EVFILT_WRITE;
+        changes[1].flags = libc::EV_DELETE;
+        // Only one of the filters may have been added, but at least one must
+        // have been for `fd` to be registered.
+        if self.apply(&mut changes)? == changes.len() {
+            return Err(io::Error::from_raw_os_error(libc::ENOENT));
+        }
+        Ok(())
+    }
+
+    pub fn poll(
+        &self,
+        events: &mut Vec<Event>,
+        capacity: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        let timeout = timeout.map(|timeout| libc::timespec {
+            tv_sec: cmp::min(timeout.as_secs(), libc::time_t::MAX as u64) as libc::time_t,
+            tv_nsec: timeout.subsec_nanos() as _,
+        });
+        let mut buf: Vec<libc::kevent> = Vec::with_capacity(capacity);
+        let max = cmp::min(capacity, c_int::MAX as usize) as c_int;
+        let n = match cvt(unsafe {
+            libc::kevent(
+                self.kq.as_raw_fd(),
+                ptr::null(),
+                0,
+                buf.as_mut_ptr(),
+                max as _,
+                timeout.as_ref().map_or(ptr::null(), ptr::from_ref),
+            )
+        }) {
+            Ok(n) => n as usize,
+            Err(e) if e.is_interrupted() => 0,
+            Err(e) => return Err(e),
+        };
+        // SAFETY: `kevent` initialized the first `n` events.
+        unsafe { buf.set_len(n) };
+
+        events.extend(buf.iter().map(|event| Event {
+            token: token(event),
+            readable: event.filter == libc::EVFILT_READ,
+            writable: event.filter == libc::EVFILT_WRITE,
+            // `EV_ERROR` is only used for failed changes, which `apply`
+            // handles. A pending socket error sets `EV_EOF` with the error
+            // code in `fflags`.
+            error: event.flags & libc::EV_EOF != 0 && event.fflags != 0,
+        }));
+        Ok(())
+    }
+}
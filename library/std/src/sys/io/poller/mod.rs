@@ -0,0 +1,41 @@
+use crate::ffi::c_int;
+use crate::time::Duration;
+
+cfg_select! {
+    any(target_os = "linux", target_os = "android") => {
+        mod epoll;
+        pub use epoll::Poller;
+    }
+    any(
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ) => {
+        mod kqueue;
+        pub use kqueue::Poller;
+    }
+    _ => {
+        mod poll;
+        pub use poll::Poller;
+    }
+}
+
+/// Converts a timeout to milliseconds for `epoll_wait` and `poll`, where `-1`
+/// waits indefinitely.
+#[cfg_attr(
+    any(
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ),
+    allow(dead_code)
+)]
+fn timeout_ms(timeout: Option<Duration>) -> c_int {
+    match timeout {
+        None => -1,
+        // Round up, so that short timeouts don't turn into busy loops.
+        Some(timeout) => timeout.as_nanos().div_ceil(1_000_000).try_into().unwrap_or(c_int::MAX),
+    }
+}
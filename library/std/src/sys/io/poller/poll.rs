@@ -0,0 +1,126 @@
+use libc::{c_short, pollfd};
+
+use super::timeout_ms;
+use crate::io::{self, Event};
+use crate::os::fd::{AsRawFd, BorrowedFd, RawFd};
+use crate::sync::Mutex;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sys::pal::cvt;
+use crate::time::Duration;
+
+/// A poller built on `poll`, for platforms without a more scalable interface.
+///
+/// `poll` has no kernel-side state, so the registered sources are kept here and
+/// passed to every call.
+pub struct Poller {
+    sources: Mutex<Vec<Source>>,
+    /// Where the next call starts looking for ready sources, so that sources
+    /// late in the list still get reported when more than `capacity` are ready.
+    next: AtomicUsize,
+}
+
+#[derive(Clone, Copy)]
+struct Source {
+    fd: RawFd,
+    token: usize,
+    events: c_short,
+}
+
+fn poll_events(readable: bool, writable: bool) -> c_short {
+    let mut events = 0;
+    if readable {
+        events |= libc::POLLIN;
+    }
+    if writable {
+        events |= libc::POLLOUT;
+    }
+    events
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        Ok(Poller { sources: Mutex::new(Vec::new()), next: AtomicUsize::new(0) })
+    }
+
+    pub fn register(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        let fd = fd.as_raw_fd();
+        let mut sources = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+        if sources.iter().any(|source| source.fd == fd) {
+            return Err(io::const_error!(
+                io::ErrorKind::AlreadyExists,
+                "the source is already registered",
+            ));
+        }
+        sources.push(Source { fd, token, events: poll_events(readable, writable) });
+        Ok(())
+    }
+
+    pub fn reregister(
+        &self,
+        fd: BorrowedFd<'_>,
+        token: usize,
+        readable: bool,
+        writable: bool,
+    ) -> io::Result<()> {
+        let fd = fd.as_raw_fd();
+        let mut sources = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(source) = sources.iter_mut().find(|source| source.fd == fd) else {
+            return Err(io::const_error!(io::ErrorKind::NotFound, "the source is not registered"));
+        };
+        *source = Source { fd, token, events: poll_events(readable, writable) };
+        Ok(())
+    }
+
+    pub fn deregister(&self, fd: BorrowedFd<'_>) -> io::Result<()> {
+        let fd = fd.as_raw_fd();
+        let mut sources = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(index) = sources.iter().position(|source| source.fd == fd) else {
+            return Err(io::const_error!(io::ErrorKind::NotFound, "the source is not registered"));
+        };
+        sources.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn poll(
+        &self,
+        events: &mut Vec<Event>,
+        capacity: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        // Don't hold the lock while waiting, so that other threads can still
+        // change the registrations.
+        let sources = self.sources.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let mut fds: Vec<pollfd> = sources
+            .iter()
+            .map(|source| pollfd { fd: source.fd, events: source.events, revents: 0 })
+            .collect();
+        match cvt(unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, timeout_ms(timeout)) }) {
+            Ok(_) => {}
+            Err(e) if e.is_interrupted() => return Ok(()),
+            Err(e) => return Err(e),
+        }
+
+        if fds.is_empty() {
+            return Ok(());
+        }
+        let start = self.next.load(Ordering::Relaxed) % fds.len();
+        let ready = (start..fds.len())
+            .chain(0..start)
+            .filter(|&i| fds[i].revents != 0)
+            .take(capacity)
+            .inspect(|&i| self.next.store(i + 1, Ordering::Relaxed));
+        events.extend(ready.map(|i| Event {
+            token: sources[i].token,
+            readable: fds[i].revents & (libc::POLLIN | libc::POLLPRI | libc::POLLHUP) != 0,
+            writable: fds[i].revents & libc::POLLOUT != 0,
+            error: fds[i].revents & (libc::POLLERR | libc::POLLNVAL) != 0,
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Fallbacks for platforms without BSD sockets, built on the basic operations
+//! that every platform provides.
+//!
+//! Sockets cannot be configured before binding on these platforms. Without any
+//! options set, the `*_with` functions behave like the plain `bind` and `connect`.
+
+use super::{
+    SocketOptions, TcpListener, TcpStream, UdpSocket, recv_many_fallback, send_many_fallback,
+};
+use crate::io::{self, IoSlice, IoSliceMut};
+use crate::net::{RecvMeta, SocketAddr};
+
+fn check_default(opts: &SocketOptions) -> io::Result<()> {
+    let SocketOptions { reuse_address, reuse_port, only_v6, recv_buffer_size, send_buffer_size } =
+        *opts;
+    if reuse_address.is_some()
+        || reuse_port.is_some()
+        || only_v6.is_some()
+        || recv_buffer_size.is_some()
+        || send_buffer_size.is_some()
+    {
+        return Err(io::const_error!(
+            io::ErrorKind::Unsupported,
+            "socket options are not supported on this platform",
+        ));
+    }
+    Ok(())
+}
+
+impl TcpListener {
+    pub fn bind_with(
+        addr: &SocketAddr,
+        opts: &SocketOptions,
+        _backlog: Option<u32>,
+    ) -> io::Result<TcpListener> {
+        check_default(opts)?;
+        TcpListener::bind(addr)
+    }
+}
+
+impl TcpStream {
+    pub fn connect_with(
+        addr: &SocketAddr,
+        local: Option<&SocketAddr>,
+        opts: &SocketOptions,
+    ) -> io::Result<TcpStream> {
+        check_default(opts)?;
+        if local.is_some() {
+            return Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "binding before connecting is not supported on this platform",
+            ));
+        }
+        TcpStream::connect(addr)
+    }
+}
+
+impl UdpSocket {
+    pub fn bind_with(addr: &SocketAddr, opts: &SocketOptions) -> io::Result<UdpSocket> {
+        check_default(opts)?;
+        UdpSocket::bind(addr)
+    }
+
+    pub fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        recv_many_fallback(self, bufs, meta)
+    }
+
+    pub fn send_many(&self, bufs: &[IoSlice<'_>], addrs: &[SocketAddr]) -> io::Result<usize> {
+        send_many_fallback(self, bufs, addrs)
+    }
+}
@@ -35,12 +35,32 @@
     }
 }
 
-#[cfg_attr(
-    // Make sure that this is used on some platforms at least.
-    not(any(target_os = "linux", target_os = "windows")),
-    allow(dead_code)
-)]
-fn each_addr<A: crate::net::ToSocketAddrs, F, T>(addr: A, mut f: F) -> crate::io::Result<T>
+cfg_select! {
+    any(
+        all(target_family = "unix", not(target_os = "l4re")),
+        target_os = "windows",
+        target_os = "hermit",
+        all(target_os = "wasi", any(target_env = "p2", target_env = "p3")),
+        target_os = "solid_asp3",
+    ) => {}
+    _ => {
+        mod fallback;
+    }
+}
+
+/// Options applied to a socket between creating and binding it.
+///
+/// Options that are `None` are left at the platform default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SocketOptions {
+    pub reuse_address: Option<bool>,
+    pub reuse_port: Option<bool>,
+    pub only_v6: Option<bool>,
+    pub recv_buffer_size: Option<usize>,
+    pub send_buffer_size: Option<usize>,
+}
+
+pub fn each_addr<A: crate::net::ToSocketAddrs, F, T>(addr: A, mut f: F) -> crate::io::Result<T>
 where
     F: FnMut(&crate::net::SocketAddr) -> crate::io::Result<T>,
 {
@@ -60,6 +80,42 @@ fn each_addr<A: crate::net::ToSocketAddrs, F, T>(addr: A, mut f: F) -> crate::io
     }
 }
 
+/// Receives a single datagram, for platforms that cannot receive several at once.
+#[cfg_attr(any(target_os = "linux", target_os = "android"), allow(dead_code))]
+fn recv_many_fallback(
+    socket: &UdpSocket,
+    bufs: &mut [crate::io::IoSliceMut<'_>],
+    meta: &mut [crate::net::RecvMeta],
+) -> crate::io::Result<usize> {
+    match (bufs.first_mut(), meta.first_mut()) {
+        (Some(buf), Some(meta)) => {
+            let (len, addr) = socket.recv_from(buf)?;
+            *meta = crate::net::RecvMeta { len, addr, segment_size: len };
+            Ok(1)
+        }
+        _ => Ok(0),
+    }
+}
+
+/// Sends the datagrams one by one, for platforms that cannot send several at once.
+#[cfg_attr(any(target_os = "linux", target_os = "android"), allow(dead_code))]
+fn send_many_fallback(
+    socket: &UdpSocket,
+    bufs: &[crate::io::IoSlice<'_>],
+    addrs: &[crate::net::SocketAddr],
+) -> crate::io::Result<usize> {
+    let mut sent = 0;
+    for (buf, addr) in bufs.iter().zip(addrs) {
+        match socket.send_to(buf, addr) {
+            Ok(_) => sent += 1,
+            // Like `sendmmsg`, only report an error if nothing was sent.
+            Err(e) if sent == 0 => return Err(e),
+            Err(_) => break,
+        }
+    }
+    Ok(sent)
+}
+
 // Default implementation, may be overridden by platform-specific implementations.
 #[cfg(not(all(target_vendor = "fortanix", target_env = "sgx")))]
 pub(crate) fn lookup_host_string(
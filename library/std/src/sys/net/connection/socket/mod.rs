@@ -5,10 +5,12 @@
 use crate::io::{self, BorrowedCursor, ErrorKind, IoSlice, IoSliceMut};
 use crate::mem::MaybeUninit;
 use crate::net::{
-    Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs,
+    Ipv4Addr, Ipv6Addr, RecvMeta, Shutdown, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs,
 };
 use crate::sys::helpers::run_with_cstr;
-use crate::sys::net::connection::each_addr;
+use crate::sys::net::connection::{SocketOptions, each_addr};
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::sys::net::connection::{recv_many_fallback, send_many_fallback};
 use crate::sys::{AsInner, FromInner};
 use crate::time::Duration;
 use crate::{cmp, fmt, mem, ptr};
@@ -292,6 +294,53 @@ unsafe fn sockname<F>(f: F) -> io::Result<SocketAddr>
     unsafe { socket_addr_from_c(storage.as_ptr(), len as usize) }
 }
 
+/// Applies the options that were set to a socket that is about to be bound to `addr`.
+fn apply_options(sock: &Socket, addr: &SocketAddr, opts: &SocketOptions) -> io::Result<()> {
+    if let Some(reuse_address) = opts.reuse_address {
+        unsafe { setsockopt(sock, c::SOL_SOCKET, c::SO_REUSEADDR, reuse_address as c_int)? };
+    }
+    if let Some(reuse_port) = opts.reuse_port {
+        sock.set_reuse_port(reuse_port)?;
+    }
+    // `IPV6_V6ONLY` only exists for IPv6 sockets.
+    if let (Some(only_v6), SocketAddr::V6(_)) = (opts.only_v6, addr) {
+        unsafe { setsockopt(sock, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)? };
+    }
+    if let Some(size) = opts.recv_buffer_size {
+        sock.set_recv_buffer_size(size)?;
+    }
+    if let Some(size) = opts.send_buffer_size {
+        sock.set_send_buffer_size(size)?;
+    }
+    Ok(())
+}
+
+/// The most datagrams `recv_many` and `send_many` pass to the kernel in one
+/// call, which keeps their message headers on the stack.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MMSG_BATCH: usize = 32;
+
+/// Returns the segment size reported by generic receive offload, if the kernel
+/// coalesced several datagrams into the message.
+#[cfg(target_os = "linux")]
+fn gro_segment_size(msg: &c::msghdr) -> Option<usize> {
+    unsafe {
+        let mut cmsg = c::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == c::SOL_UDP && (*cmsg).cmsg_type == c::UDP_GRO {
+                return Some(ptr::read_unaligned(c::CMSG_DATA(cmsg) as *const c_int) as usize);
+            }
+            cmsg = c::CMSG_NXTHDR(msg, cmsg);
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "android")]
+fn gro_segment_size(_msg: &c::msghdr) -> Option<usize> {
+    None
+}
+
 #[cfg(target_os = "android")]
 fn to_ipv6mr_interface(value: u32) -> c_int {
     value as c_int
@@ -373,6 +422,23 @@ fn inner(addr: &SocketAddr) -> io::Result<TcpStream> {
         }
     }
 
+    pub fn connect_with(
+        addr: &SocketAddr,
+        local: Option<&SocketAddr>,
+        opts: &SocketOptions,
+    ) -> io::Result<TcpStream> {
+        init();
+
+        let sock = Socket::new(addr_family(addr), c::SOCK_STREAM)?;
+        apply_options(&sock, addr, opts)?;
+        if let Some(local) = local {
+            let (local, len) = socket_addr_to_c(local);
+            cvt(unsafe { c::bind(sock.as_raw(), local.as_ptr(), len as _) })?;
+        }
+        sock.connect(addr)?;
+        Ok(TcpStream { inner: sock })
+    }
+
     pub fn connect_timeout(addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
         init();
 
@@ -542,45 +608,56 @@ pub struct TcpListener {
 
 impl TcpListener {
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<TcpListener> {
-        init();
-        return each_addr(addr, inner);
+        each_addr(addr, |addr| TcpListener::bind_with(addr, &SocketOptions::default(), None))
+    }
 
-        fn inner(addr: &SocketAddr) -> io::Result<TcpListener> {
-            let sock = Socket::new(addr_family(addr), c::SOCK_STREAM)?;
+    pub fn bind_with(
+        addr: &SocketAddr,
+        opts: &SocketOptions,
+        backlog: Option<u32>,
+    ) -> io::Result<TcpListener> {
+        init();
 
-            // On platforms with Berkeley-derived sockets, this allows to quickly
-            // rebind a socket, without needing to wait for the OS to clean up the
-            // previous one.
-            //
-            // On Windows, this allows rebinding sockets which are actively in use,
-            // which allows “socket hijacking”, so we explicitly don't set it here.
-            // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
-            #[cfg(not(windows))]
-            unsafe {
-                setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)?
-            };
+        let sock = Socket::new(addr_family(addr), c::SOCK_STREAM)?;
 
-            // Bind our new socket
-            let (addr, len) = socket_addr_to_c(addr);
-            cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
-
-            let backlog = if cfg!(target_os = "horizon") {
-                // The 3DS doesn't support a big connection backlog. Sometimes
-                // it allows up to about 37, but other times it doesn't even
-                // accept 32. There may be a global limitation causing this.
-                20
-            } else if cfg!(target_os = "haiku") {
-                // Haiku does not support a queue length > 32
-                // https://github.com/haiku/haiku/blob/979a0bc487864675517fb2fab28f87dc8bf43041/headers/posix/sys/socket.h#L81
-                32
-            } else {
-                // The default for all other platforms
-                128
-            };
+        // On platforms with Berkeley-derived sockets, this allows to quickly
+        // rebind a socket, without needing to wait for the OS to clean up the
+        // previous one.
+        //
+        // On Windows, this allows rebinding sockets which are actively in use,
+        // which allows “socket hijacking”, so we explicitly don't set it here
+        // unless asked to.
+        // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
+        let reuse_address = opts.reuse_address.or(if cfg!(windows) { None } else { Some(true) });
+        apply_options(&sock, addr, &SocketOptions { reuse_address, ..*opts })?;
+
+        // Bind our new socket
+        let (addr, len) = socket_addr_to_c(addr);
+        cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
+
+        let backlog = match backlog {
+            Some(backlog) => cmp::min(backlog, c_int::MAX as u32) as c_int,
+            None => Self::default_backlog(),
+        };
 
-            // Start listening
-            cvt(unsafe { c::listen(sock.as_raw(), backlog) })?;
-            Ok(TcpListener { inner: sock })
+        // Start listening
+        cvt(unsafe { c::listen(sock.as_raw(), backlog) })?;
+        Ok(TcpListener { inner: sock })
+    }
+
+    fn default_backlog() -> c_int {
+        if cfg!(target_os = "horizon") {
+            // The 3DS doesn't support a big connection backlog. Sometimes
+            // it allows up to about 37, but other times it doesn't even
+            // accept 32. There may be a global limitation causing this.
+            20
+        } else if cfg!(target_os = "haiku") {
+            // Haiku does not support a queue length > 32
+            // https://github.com/haiku/haiku/blob/979a0bc487864675517fb2fab28f87dc8bf43041/headers/posix/sys/socket.h#L81
+            32
+        } else {
+            // The default for all other platforms
+            128
         }
     }
 
@@ -668,15 +745,17 @@ pub struct UdpSocket {
 
 impl UdpSocket {
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<UdpSocket> {
+        each_addr(addr, |addr| UdpSocket::bind_with(addr, &SocketOptions::default()))
+    }
+
+    pub fn bind_with(addr: &SocketAddr, opts: &SocketOptions) -> io::Result<UdpSocket> {
         init();
-        return each_addr(addr, inner);
 
-        fn inner(addr: &SocketAddr) -> io::Result<UdpSocket> {
-            let sock = Socket::new(addr_family(addr), c::SOCK_DGRAM)?;
-            let (addr, len) = socket_addr_to_c(addr);
-            cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
-            Ok(UdpSocket { inner: sock })
-        }
+        let sock = Socket::new(addr_family(addr), c::SOCK_DGRAM)?;
+        apply_options(&sock, addr, opts)?;
+        let (addr, len) = socket_addr_to_c(addr);
+        cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(UdpSocket { inner: sock })
     }
 
     #[inline]
@@ -720,6 +799,104 @@ pub fn send_to(&self, buf: &[u8], dst: &SocketAddr) -> io::Result<usize> {
         Ok(ret as usize)
     }
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        let count = cmp::min(cmp::min(bufs.len(), meta.len()), MMSG_BATCH);
+        if count == 0 {
+            return Ok(0);
+        }
+
+        // `recvmmsg` will fill in the storage with the addresses, so we don't
+        // need to zero it here. The control buffers receive the segment size
+        // if GRO is enabled.
+        let mut storage = [const { MaybeUninit::<c::sockaddr_storage>::uninit() }; MMSG_BATCH];
+        let mut control = [[0usize; 4]; MMSG_BATCH];
+        let mut msgs: [c::mmsghdr; MMSG_BATCH] = unsafe { mem::zeroed() };
+        for (((msg, buf), storage), control) in
+            msgs.iter_mut().zip(&mut bufs[..count]).zip(&mut storage).zip(&mut control)
+        {
+            let msg = &mut msg.msg_hdr;
+            msg.msg_name = storage.as_mut_ptr().cast();
+            msg.msg_namelen = size_of::<c::sockaddr_storage>() as c::socklen_t;
+            // `IoSliceMut` is ABI-compatible with `iovec`.
+            msg.msg_iov = (buf as *mut IoSliceMut<'_>).cast();
+            msg.msg_iovlen = 1;
+            msg.msg_control = control.as_mut_ptr().cast();
+            msg.msg_controllen = size_of_val(control) as _;
+        }
+
+        let n = cvt(unsafe {
+            c::recvmmsg(
+                self.inner.as_raw(),
+                msgs.as_mut_ptr(),
+                count as _,
+                c::MSG_WAITFORONE as _,
+                ptr::null_mut(),
+            )
+        })? as usize;
+
+        for (i, ((msg, storage), meta)) in msgs.iter().zip(&storage).zip(meta).take(n).enumerate() {
+            let len = msg.msg_len as usize;
+            let addr = match unsafe {
+                socket_addr_from_c(storage.as_ptr(), msg.msg_hdr.msg_namelen as usize)
+            } {
+                Ok(addr) => addr,
+                // The datagrams have already been taken off the socket, so
+                // hand out the ones before this rather than losing them too.
+                Err(e) if i == 0 => return Err(e),
+                Err(_) => return Ok(i),
+            };
+            let segment_size = gro_segment_size(&msg.msg_hdr).unwrap_or(len);
+            *meta = RecvMeta { len, addr, segment_size };
+        }
+        Ok(n)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        recv_many_fallback(self, bufs, meta)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn send_many(&self, bufs: &[IoSlice<'_>], addrs: &[SocketAddr]) -> io::Result<usize> {
+        let count = cmp::min(cmp::min(bufs.len(), addrs.len()), MMSG_BATCH);
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let mut storage = [const { MaybeUninit::<SocketAddrCRepr>::uninit() }; MMSG_BATCH];
+        let mut msgs: [c::mmsghdr; MMSG_BATCH] = unsafe { mem::zeroed() };
+        for (((msg, buf), addr), storage) in
+            msgs.iter_mut().zip(&bufs[..count]).zip(addrs).zip(&mut storage)
+        {
+            let (addr, addrlen) = socket_addr_to_c(addr);
+            let msg = &mut msg.msg_hdr;
+            msg.msg_name = storage.write(addr).as_ptr() as *mut c_void;
+            msg.msg_namelen = addrlen;
+            // `IoSlice` is ABI-compatible with `iovec`.
+            msg.msg_iov = (buf as *const IoSlice<'_>).cast_mut().cast();
+            msg.msg_iovlen = 1;
+        }
+
+        let n = cvt(unsafe {
+            c::sendmmsg(self.inner.as_raw(), msgs.as_mut_ptr(), count as _, MSG_NOSIGNAL as _)
+        })?;
+        Ok(n as usize)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn send_many(&self, bufs: &[IoSlice<'_>], addrs: &[SocketAddr]) -> io::Result<usize> {
+        send_many_fallback(self, bufs, addrs)
+    }
+
     pub fn duplicate(&self) -> io::Result<UdpSocket> {
         self.inner.duplicate().map(|s| UdpSocket { inner: s })
     }
@@ -363,6 +363,18 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         .map(drop)
     }
 
+    pub fn set_recv_buffer_size(&self, _size: usize) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::Unsupported, "SO_RCVBUF is not supported"))
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::Unsupported, "SO_SNDBUF is not supported"))
+    }
+
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = unsafe { getsockopt(self, netc::SOL_SOCKET, netc::SO_ERROR)? };
         if raw == 0 { Ok(None) } else { Ok(Some(io::Error::from_raw_os_error(raw as i32))) }
@@ -601,6 +601,42 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         unsafe { setsockopt(self, libc::SOL_SOCKET, libc::SO_NONBLOCK, option) }
     }
 
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        unsafe { setsockopt(self, libc::SOL_SOCKET, libc::SO_RCVBUF, size) }
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        unsafe { setsockopt(self, libc::SOL_SOCKET, libc::SO_SNDBUF, size) }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_vendor = "apple",
+    ))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        unsafe { setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int) }
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_vendor = "apple",
+    )))]
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
     #[cfg(any(target_os = "solaris", target_os = "illumos"))]
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         // FIONBIO is inadequate for sockets on illumos/Solaris, so use the
@@ -608,6 +644,28 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         self.0.set_nonblocking(nonblocking)
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn set_udp_gro(&self, gro: bool) -> io::Result<()> {
+        unsafe { setsockopt(self, libc::SOL_UDP, libc::UDP_GRO, gro as c_int) }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn udp_gro(&self) -> io::Result<bool> {
+        let raw: c_int = unsafe { getsockopt(self, libc::SOL_UDP, libc::UDP_GRO)? };
+        Ok(raw != 0)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_udp_segment(&self, segment_size: u16) -> io::Result<()> {
+        unsafe { setsockopt(self, libc::SOL_UDP, libc::UDP_SEGMENT, segment_size as c_int) }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn udp_segment(&self) -> io::Result<u16> {
+        let raw: c_int = unsafe { getsockopt(self, libc::SOL_UDP, libc::UDP_SEGMENT)? };
+        Ok(raw as u16)
+    }
+
     #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
     pub fn set_mark(&self, mark: u32) -> io::Result<()> {
         #[cfg(target_os = "linux")]
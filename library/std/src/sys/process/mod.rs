@@ -29,14 +29,56 @@
 pub use env::CommandEnvs;
 #[unstable(feature = "command_resolved_envs", issue = "149070")]
 pub use env::CommandResolvedEnvs;
-#[cfg(target_os = "linux")]
-pub use imp::PidFd;
 #[cfg(target_family = "unix")]
 pub use imp::getppid;
 pub use imp::{
     ChildPipe, Command, CommandArgs, EnvKey, ExitCode, ExitStatus, ExitStatusError, Process, Stdio,
     getpid, read_output,
 };
+#[cfg(target_os = "linux")]
+pub use imp::{PidFd, poll_exited};
+
+/// Waits for a child process to exit for at most `timeout` by repeatedly
+/// calling `try_wait`, for platforms that cannot wait on a child with a timeout.
+///
+/// The delay between attempts grows exponentially, so that children exiting
+/// quickly are noticed promptly without spinning on long-running ones. Unlike
+/// waiting for `SIGCHLD`, this does not interfere with signal handlers or
+/// signal masks set up by the program.
+#[cfg(any(target_family = "unix", target_os = "motor"))]
+#[cfg_attr(
+    any(
+        target_os = "fuchsia",
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+    ),
+    allow(dead_code)
+)]
+pub(crate) fn wait_timeout_by_polling<T>(
+    timeout: crate::time::Duration,
+    mut try_wait: impl FnMut() -> crate::io::Result<Option<T>>,
+) -> crate::io::Result<Option<T>> {
+    use crate::time::{Duration, Instant};
+
+    const MAX_DELAY: Duration = Duration::from_millis(50);
+
+    let start = Instant::now();
+    let mut delay = Duration::from_micros(100);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return Ok(None);
+        }
+        crate::thread::sleep(delay.min(remaining));
+        delay = (delay * 2).min(MAX_DELAY);
+    }
+}
 
 #[cfg(any(
     all(
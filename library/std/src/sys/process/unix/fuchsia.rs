@@ -4,6 +4,7 @@
 use crate::num::NonZero;
 use crate::process::StdioPipes;
 use crate::sys::pal::fuchsia::*;
+use crate::time::Duration;
 use crate::{fmt, io, mem, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -194,13 +195,26 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_deadline(0)
+    }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let timeout = zx_time_t::try_from(timeout.as_nanos()).unwrap_or(ZX_TIME_INFINITE);
+        self.wait_deadline(zx_clock_get_monotonic().saturating_add(timeout))
+    }
+
+    fn wait_deadline(&mut self, deadline: zx_time_t) -> io::Result<Option<ExitStatus>> {
         let mut proc_info: zx_info_process_t = Default::default();
         let mut actual: size_t = 0;
         let mut avail: size_t = 0;
 
         unsafe {
-            let status =
-                zx_object_wait_one(self.handle.raw(), ZX_TASK_TERMINATED, 0, ptr::null_mut());
+            let status = zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                deadline,
+                ptr::null_mut(),
+            );
             match status {
                 0 => {} // Success
                 x if x == ZX_ERR_TIMED_OUT => {
@@ -26,7 +26,7 @@
 
 pub use imp::{ExitStatus, ExitStatusError, Process};
 #[cfg(target_os = "linux")]
-pub use pidfd::PidFd;
+pub use pidfd::{PidFd, poll_exited};
 
 pub use self::common::{
     ChildPipe, Command, CommandArgs, ExitCode, Stdio, getpid, getppid, read_output,
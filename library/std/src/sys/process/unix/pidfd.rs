@@ -3,6 +3,7 @@
 use crate::os::fd::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use crate::sys::fd::FileDesc;
 use crate::sys::{AsInner, FromInner, IntoInner, cvt};
+use crate::time::{Duration, Instant};
 
 #[cfg(test)]
 mod tests;
@@ -17,7 +18,14 @@ pub fn kill(&self) -> io::Result<()> {
 
     #[cfg(any(test, target_env = "gnu", target_env = "musl"))]
     pub fn current_process() -> io::Result<PidFd> {
-        let pid = crate::process::id();
+        PidFd::open(crate::process::id())
+    }
+
+    /// Opens a pidfd for the process with the given pid.
+    ///
+    /// This is only race-free for children that have not been reaped yet.
+    #[cfg(any(test, target_env = "gnu", target_env = "musl"))]
+    pub fn open(pid: u32) -> io::Result<PidFd> {
         let pidfd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) })?;
         Ok(unsafe { PidFd::from_raw_fd(pidfd as RawFd) })
     }
@@ -123,6 +131,51 @@ pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
     }
 }
 
+/// Blocks until one of the processes referred to by `pidfds` has exited,
+/// returning the index of its pidfd, or `None` if `timeout` elapsed first.
+///
+/// The exited process is not reaped.
+pub fn poll_exited(pidfds: &[&PidFd], timeout: Option<Duration>) -> io::Result<Option<usize>> {
+    let mut fds: Vec<libc::pollfd> = pidfds
+        .iter()
+        .map(|pidfd| libc::pollfd { fd: pidfd.0.as_raw_fd(), events: libc::POLLIN, revents: 0 })
+        .collect();
+    let deadline = timeout.map(|timeout| (Instant::now(), timeout));
+
+    loop {
+        let timeout_ms = match deadline {
+            None => -1,
+            Some((start, timeout)) => {
+                let Some(remaining) = timeout.checked_sub(start.elapsed()) else {
+                    return Ok(None);
+                };
+                // Round up so that we never wake up before the deadline and
+                // spin on a zero timeout.
+                let ms = remaining.as_nanos().div_ceil(1_000_000);
+                ms.min(libc::c_int::MAX as u128) as libc::c_int
+            }
+        };
+
+        match unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout_ms) } {
+            -1 => {
+                let err = io::Error::last_os_error();
+                if !err.is_interrupted() {
+                    return Err(err);
+                }
+            }
+            0 if timeout_ms == 0 => return Ok(None),
+            0 => {}
+            _ => {
+                // A pidfd becomes readable once its process has exited. POLLHUP
+                // is reported instead if the process has already been reaped.
+                if let Some(index) = fds.iter().position(|fd| fd.revents != 0) {
+                    return Ok(Some(index));
+                }
+            }
+        }
+    }
+}
+
 impl AsInner<FileDesc> for PidFd {
     fn as_inner(&self) -> &FileDesc {
         &self.0
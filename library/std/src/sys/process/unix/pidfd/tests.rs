@@ -1,10 +1,11 @@
 use super::PidFd as InternalPidFd;
 use crate::assert_matches;
 use crate::os::fd::AsRawFd;
-use crate::os::linux::process::{ChildExt, CommandExt as _};
+use crate::os::linux::process::{ChildExt, ChildSet, CommandExt as _};
 use crate::os::unix::process::{CommandExt as _, ExitStatusExt};
 use crate::process::Command;
 use crate::sys::AsInner;
+use crate::time::Duration;
 
 #[test]
 fn test_command_pidfd() {
@@ -107,6 +108,44 @@ fn test_pidfd() {
     assert_matches!(res, Err(e) if e.raw_os_error() == Some(libc::ESRCH));
 }
 
+#[test]
+fn test_child_set() {
+    if !probe_pidfd_support() {
+        return;
+    }
+
+    let mut set = ChildSet::new();
+    assert_matches!(set.wait_any_timeout(Duration::ZERO), Ok(None));
+    assert_eq!(set.wait_any().unwrap_err().kind(), crate::io::ErrorKind::InvalidInput);
+
+    let child = Command::new("true").spawn().unwrap();
+    let mut child = set.insert(child).expect_err("child without a pidfd should be rejected");
+    child.wait().unwrap();
+
+    let sleeper = Command::new("sleep").arg("1000").create_pidfd(true).spawn().unwrap();
+    let sleeper_id = sleeper.id();
+    set.insert(sleeper).unwrap();
+    let exits = Command::new("sh").args(["-c", "exit 3"]).create_pidfd(true).spawn().unwrap();
+    let exits_id = exits.id();
+    set.insert(exits).unwrap();
+    assert_eq!(set.len(), 2);
+
+    let (child, status) = set.wait_any().unwrap();
+    assert_eq!(child.id(), exits_id);
+    assert_eq!(status.code(), Some(3));
+    assert_eq!(set.len(), 1);
+
+    assert_matches!(set.wait_any_timeout(Duration::from_millis(10)), Ok(None));
+
+    for child in set.iter_mut() {
+        child.kill().unwrap();
+    }
+    let (child, status) = set.wait_any_timeout(Duration::from_secs(1000)).unwrap().unwrap();
+    assert_eq!(child.id(), sleeper_id);
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+    assert!(set.is_empty());
+}
+
 fn probe_pidfd_support() -> bool {
     InternalPidFd::current_process().is_ok()
 }
@@ -17,13 +17,13 @@
 use crate::sys::cvt;
 #[cfg(target_os = "linux")]
 use crate::sys::process::PidFd;
+use crate::time::Duration;
 use crate::{fmt, mem, sys};
 
 cfg_select! {
     target_os = "nto" => {
         use crate::thread;
         use libc::{c_char, posix_spawn_file_actions_t, posix_spawnattr_t};
-        use crate::time::Duration;
         use crate::sync::LazyLock;
         // Get smallest amount of time we can sleep.
         // Return a common value if it cannot be determined.
@@ -1053,6 +1053,105 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(pid_fd) = self.pidfd.as_ref() {
+            if sys::process::poll_exited(&[pid_fd], Some(timeout))?.is_none() {
+                return Ok(None);
+            }
+            return self.try_wait();
+        }
+        // The child has not been reaped yet, so its pid cannot have been
+        // recycled and a pidfd opened for it now refers to the same process.
+        #[cfg(all(target_os = "linux", any(target_env = "gnu", target_env = "musl")))]
+        if let Ok(pid_fd) = PidFd::open(self.pid as u32) {
+            if sys::process::poll_exited(&[&pid_fd], Some(timeout))?.is_none() {
+                return Ok(None);
+            }
+            return self.try_wait();
+        }
+        cfg_select! {
+            any(
+                target_vendor = "apple",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "dragonfly",
+            ) => {
+                if !wait_exit_kqueue(self.pid, timeout)? {
+                    return Ok(None);
+                }
+                // The child has exited, so this does not block for long.
+                self.wait().map(Some)
+            }
+            _ => {
+                sys::process::wait_timeout_by_polling(timeout, || self.try_wait())
+            }
+        }
+    }
+}
+
+/// Waits for the child `pid` to exit for at most `timeout` with a kqueue,
+/// returning whether it has exited.
+#[cfg(any(
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly",
+))]
+fn wait_exit_kqueue(pid: pid_t, timeout: Duration) -> io::Result<bool> {
+    use crate::os::fd::{AsRawFd, FromRawFd};
+    use crate::ptr;
+    use crate::sys::fd::FileDesc;
+    use crate::time::Instant;
+
+    // A kqueue is not inherited by children created with `fork`, so it does
+    // not need to be close-on-exec.
+    let kq = unsafe { FileDesc::from_raw_fd(cvt(libc::kqueue())?) };
+    let mut event: libc::kevent = unsafe { mem::zeroed() };
+    event.ident = pid as _;
+    event.filter = libc::EVFILT_PROC;
+    event.flags = libc::EV_ADD;
+    event.fflags = libc::NOTE_EXIT;
+    // Without room for events, a failed change is reported through `errno`.
+    match cvt(unsafe { libc::kevent(kq.as_raw_fd(), &event, 1, ptr::null_mut(), 0, ptr::null()) }) {
+        Ok(_) => {}
+        // The child has already exited.
+        Err(e) if e.raw_os_error() == Some(libc::ESRCH) => return Ok(true),
+        Err(e) => return Err(e),
+    }
+
+    // Without a deadline, the timeout is too long to ever elapse.
+    let deadline = Instant::now().checked_add(timeout);
+    loop {
+        let timeout = deadline.map(|deadline| {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            libc::timespec {
+                tv_sec: crate::cmp::min(remaining.as_secs(), libc::time_t::MAX as u64)
+                    as libc::time_t,
+                tv_nsec: remaining.subsec_nanos() as _,
+            }
+        });
+        match cvt(unsafe {
+            libc::kevent(
+                kq.as_raw_fd(),
+                ptr::null(),
+                0,
+                &mut event,
+                1,
+                timeout.as_ref().map_or(ptr::null(), ptr::from_ref),
+            )
+        }) {
+            Ok(n) => return Ok(n > 0),
+            Err(e) if e.is_interrupted() => {}
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -6,6 +6,7 @@
 use crate::num::NonZero;
 use crate::process::StdioPipes;
 use crate::sys::{cvt, thread};
+use crate::time::Duration;
 use crate::{fmt, sys};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -193,6 +194,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        sys::process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
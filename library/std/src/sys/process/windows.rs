@@ -22,8 +22,9 @@
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::pal::api::{self, WinError, utf16};
-use crate::sys::pal::{ensure_no_nuls, fill_utf16_buf};
+use crate::sys::pal::{dur2timeout, ensure_no_nuls, fill_utf16_buf};
 use crate::sys::{IntoInner, cvt, path, stdio};
+use crate::time::Duration;
 use crate::{cmp, env, fmt, ptr};
 
 mod child_pipe;
@@ -726,8 +727,12 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_timeout(Duration::ZERO)
+    }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
         unsafe {
-            match c::WaitForSingleObject(self.handle.as_raw_handle(), 0) {
+            match c::WaitForSingleObject(self.handle.as_raw_handle(), dur2timeout(timeout)) {
                 c::WAIT_OBJECT_0 => {}
                 c::WAIT_TIMEOUT => {
                     return Ok(None);
@@ -0,0 +1,43 @@
+use crate::sync::atomic::Ordering::{Acquire, Release};
+use crate::sys::futex::{self, futex_wait, futex_wake};
+use crate::time::Instant;
+
+type Futex = futex::SmallFutex;
+type State = futex::SmallPrimitive;
+
+const WAITING: State = 0;
+const NOTIFIED: State = 1;
+
+/// Wakes up a thread waiting for permits.
+pub struct Signal {
+    futex: Futex,
+}
+
+impl Signal {
+    pub fn new() -> Signal {
+        Signal { futex: Futex::new(WAITING) }
+    }
+
+    pub fn is_notified(&self) -> bool {
+        self.futex.load(Acquire) == NOTIFIED
+    }
+
+    /// Blocks until the signal is notified or `deadline` has passed.
+    pub fn wait(&self, deadline: Option<Instant>) {
+        while !self.is_notified() {
+            let timeout = match deadline {
+                None => None,
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(timeout) if !timeout.is_zero() => Some(timeout),
+                    _ => return,
+                },
+            };
+            futex_wait(&self.futex, WAITING, timeout);
+        }
+    }
+
+    pub fn notify(&self) {
+        self.futex.store(NOTIFIED, Release);
+        futex_wake(&self.futex);
+    }
+}
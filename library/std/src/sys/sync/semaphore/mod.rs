@@ -0,0 +1,248 @@
+// A fair counting semaphore.
+//
+// The available permits are counted in `state`, so that they can be taken and
+// given back with a single atomic operation while no thread is waiting.
+// Threads that have to wait are put into a queue of nodes on their own
+// stacks, which is protected by `lock`. While the queue is not empty, the
+// `QUEUED` bit of `state` is set, and the permits are only changed with the
+// lock held, which hands them out in queue order.
+//
+// A queued thread is woken through the `Signal` in its node, which is a futex
+// where there is one, and thread parking elsewhere. The thread handing out the
+// permits removes the node from the queue and notifies the signal with the
+// lock held. The woken thread takes the lock once more before returning, which
+// makes sure that nobody uses the node anymore when it goes out of scope.
+
+cfg_select! {
+    any(
+        all(target_os = "windows", not(target_vendor = "win7")),
+        target_os = "linux",
+        target_os = "android",
+        all(target_family = "wasm", target_feature = "atomics"),
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "fuchsia",
+        target_os = "motor",
+        target_os = "hermit",
+    ) => {
+        mod futex;
+        use futex::Signal;
+    }
+    _ => {
+        mod park;
+        use park::Signal;
+    }
+}
+
+use crate::cell::{Cell, UnsafeCell};
+use crate::ptr;
+use crate::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use crate::sync::atomic::{Atomic, AtomicUsize};
+use crate::sys::sync::Mutex;
+use crate::time::Instant;
+
+/// Set in `state` while threads are queued.
+const QUEUED: usize = 1;
+/// The permits are counted in the bits above `QUEUED`.
+const ONE_PERMIT: usize = 2;
+
+/// The largest number of permits a semaphore can hold.
+const MAX_PERMITS: usize = usize::MAX / ONE_PERMIT;
+
+pub struct Semaphore {
+    state: Atomic<usize>,
+    lock: Mutex,
+    queue: UnsafeCell<Queue>,
+}
+
+// SAFETY: the queue is only accessed with the lock held, and it is empty
+// whenever the semaphore is not borrowed.
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+/// A thread waiting for permits.
+struct Waiter {
+    count: usize,
+    prev: Cell<*const Waiter>,
+    next: Cell<*const Waiter>,
+    signal: Signal,
+}
+
+/// The waiting threads, in the order in which they get their permits.
+struct Queue {
+    head: *const Waiter,
+    tail: *const Waiter,
+}
+
+impl Queue {
+    /// # Safety
+    /// `waiter` must stay in place until it is removed from the queue.
+    unsafe fn push_back(&mut self, waiter: &Waiter) {
+        waiter.prev.set(self.tail);
+        waiter.next.set(ptr::null());
+        match unsafe { self.tail.as_ref() } {
+            Some(tail) => tail.next.set(waiter),
+            None => self.head = waiter,
+        }
+        self.tail = waiter;
+    }
+
+    /// # Safety
+    /// `waiter` must be in this queue.
+    unsafe fn remove(&mut self, waiter: &Waiter) {
+        let (prev, next) = (waiter.prev.get(), waiter.next.get());
+        match unsafe { prev.as_ref() } {
+            Some(prev) => prev.next.set(next),
+            None => self.head = next,
+        }
+        match unsafe { next.as_ref() } {
+            Some(next) => next.prev.set(prev),
+            None => self.tail = prev,
+        }
+    }
+}
+
+/// Unlocks the lock when dropped, so that it is also released on panic.
+struct Guard<'a>(&'a Mutex);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        unsafe { self.0.unlock() };
+    }
+}
+
+/// Adds `count` permits to `state`.
+fn add_permits(state: usize, count: usize) -> usize {
+    count
+        .checked_mul(ONE_PERMIT)
+        .and_then(|added| state.checked_add(added))
+        .expect("overflow in semaphore permits")
+}
+
+impl Semaphore {
+    #[inline]
+    pub const fn new(permits: usize) -> Semaphore {
+        assert!(permits <= MAX_PERMITS, "too many semaphore permits");
+        Semaphore {
+            state: AtomicUsize::new(permits * ONE_PERMIT),
+            lock: Mutex::new(),
+            queue: UnsafeCell::new(Queue { head: ptr::null(), tail: ptr::null() }),
+        }
+    }
+
+    fn lock(&self) -> Guard<'_> {
+        self.lock.lock();
+        Guard(&self.lock)
+    }
+
+    #[inline]
+    pub fn available_permits(&self) -> usize {
+        self.state.load(Relaxed) / ONE_PERMIT
+    }
+
+    /// Takes `count` permits if they are available and no thread is queued.
+    #[inline]
+    pub fn try_acquire(&self, count: usize) -> bool {
+        self.state
+            .try_update(Acquire, Relaxed, |state| {
+                (state & QUEUED == 0 && state / ONE_PERMIT >= count)
+                    .then(|| state - count * ONE_PERMIT)
+            })
+            .is_ok()
+    }
+
+    /// Takes `count` permits, waiting in the queue until they are available
+    /// or `deadline` has passed. Returns whether the permits were taken.
+    #[inline]
+    pub fn acquire(&self, count: usize, deadline: Option<Instant>) -> bool {
+        self.try_acquire(count) || self.acquire_contended(count, deadline)
+    }
+
+    #[cold]
+    fn acquire_contended(&self, count: usize, deadline: Option<Instant>) -> bool {
+        let waiter = Waiter {
+            count,
+            prev: Cell::new(ptr::null()),
+            next: Cell::new(ptr::null()),
+            signal: Signal::new(),
+        };
+
+        {
+            let _guard = self.lock();
+            // The permits may have been given back in the meantime. If not,
+            // set `QUEUED` so that nobody takes them without queueing.
+            let available = |state| state & QUEUED == 0 && state / ONE_PERMIT >= count;
+            let state = self.state.update(Acquire, Relaxed, |state| {
+                if available(state) { state - count * ONE_PERMIT } else { state | QUEUED }
+            });
+            if available(state) {
+                return true;
+            }
+            // SAFETY: `waiter` is not moved until it has left the queue, which
+            // we wait for by taking the lock below.
+            unsafe { (*self.queue.get()).push_back(&waiter) };
+        }
+
+        waiter.signal.wait(deadline);
+
+        let _guard = self.lock();
+        if waiter.signal.is_notified() {
+            // `grant` has removed us from the queue and taken our permits.
+            return true;
+        }
+        // We timed out, so leave the queue.
+        let queue = unsafe { &mut *self.queue.get() };
+        unsafe { queue.remove(&waiter) };
+        if queue.head.is_null() {
+            self.state.fetch_and(!QUEUED, Release);
+        } else {
+            // The threads behind us may be able to get their permits now.
+            unsafe { self.grant() };
+        }
+        false
+    }
+
+    /// Gives back `count` permits, handing them to queued threads first.
+    #[inline]
+    pub fn release(&self, count: usize) {
+        let result = self.state.try_update(Release, Relaxed, |state| {
+            (state & QUEUED == 0).then(|| add_permits(state, count))
+        });
+        if result.is_err() {
+            self.release_contended(count);
+        }
+    }
+
+    #[cold]
+    fn release_contended(&self, count: usize) {
+        let _guard = self.lock();
+        // The queue may have become empty before we took the lock, so other
+        // threads may be changing `state` concurrently.
+        self.state.update(Release, Relaxed, |state| add_permits(state, count));
+        if unsafe { !(*self.queue.get()).head.is_null() } {
+            unsafe { self.grant() };
+        }
+    }
+
+    /// Hands out permits to the queued threads in order, for as long as there
+    /// are enough of them, and clears `QUEUED` once the queue is empty.
+    ///
+    /// # Safety
+    /// The lock must be held and the queue must not be empty.
+    unsafe fn grant(&self) {
+        let queue = unsafe { &mut *self.queue.get() };
+        // While `QUEUED` is set, `state` is only changed with the lock held.
+        let mut state = self.state.load(Relaxed);
+        while let Some(waiter) = unsafe { queue.head.as_ref() } {
+            let Some(permits) = (state / ONE_PERMIT).checked_sub(waiter.count) else { break };
+            state = permits * ONE_PERMIT | QUEUED;
+            unsafe { queue.remove(waiter) };
+            waiter.signal.notify();
+        }
+        if queue.head.is_null() {
+            state &= !QUEUED;
+        }
+        self.state.store(state, Release);
+    }
+}
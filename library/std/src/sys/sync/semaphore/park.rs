@@ -0,0 +1,42 @@
+use crate::sync::atomic::Ordering::{Acquire, Release};
+use crate::sync::atomic::{Atomic, AtomicBool};
+use crate::thread::{self, Thread};
+use crate::time::Instant;
+
+/// Wakes up a thread waiting for permits.
+pub struct Signal {
+    thread: Thread,
+    notified: Atomic<bool>,
+}
+
+impl Signal {
+    pub fn new() -> Signal {
+        Signal { thread: thread::current(), notified: AtomicBool::new(false) }
+    }
+
+    pub fn is_notified(&self) -> bool {
+        self.notified.load(Acquire)
+    }
+
+    /// Blocks until the signal is notified or `deadline` has passed.
+    pub fn wait(&self, deadline: Option<Instant>) {
+        // Parking may return spuriously, so check the flag every time.
+        while !self.is_notified() {
+            match deadline {
+                None => thread::park(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }
+    }
+
+    pub fn notify(&self) {
+        self.notified.store(true, Release);
+        self.thread.unpark();
+    }
+}
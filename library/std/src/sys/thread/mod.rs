@@ -51,6 +51,8 @@
     any(target_family = "unix", target_os = "wasi") => {
         mod unix;
         pub use unix::{Thread, available_parallelism, current_os_id, sleep, yield_now, DEFAULT_MIN_STACK_SIZE};
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        pub use unix::current_affinity;
         #[cfg(not(any(
             target_env = "newlib",
             target_os = "l4re",
@@ -152,3 +154,55 @@ pub fn sleep_until(deadline: crate::time::Instant) {
         sleep(delay);
     }
 }
+
+/// Attributes of a new thread that are set when it is created, in addition to
+/// its stack size.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnAttrs {
+    /// The CPUs the thread may run on.
+    pub affinity: Option<Vec<usize>>,
+    #[cfg(unix)]
+    pub sched_policy: Option<crate::os::unix::thread::SchedPolicy>,
+    #[cfg(unix)]
+    pub sched_priority: Option<i32>,
+}
+
+impl SpawnAttrs {
+    /// Returns whether no attributes were set, so that the platform defaults
+    /// are used.
+    pub fn is_default(&self) -> bool {
+        #[cfg(unix)]
+        if self.sched_policy.is_some() || self.sched_priority.is_some() {
+            return false;
+        }
+        self.affinity.is_none()
+    }
+}
+
+/// Spawns a new thread with the given stack size and attributes.
+///
+/// # Safety
+///
+/// See `thread::Builder::spawn_unchecked` for safety requirements.
+pub unsafe fn spawn(
+    stack: usize,
+    attrs: &SpawnAttrs,
+    init: Box<crate::thread::ThreadInit>,
+) -> crate::io::Result<Thread> {
+    cfg_select! {
+        any(target_family = "unix", target_os = "wasi") => {
+            unsafe { Thread::new(stack, attrs, init) }
+        }
+        _ => {
+            if !attrs.is_default() {
+                return Err(crate::io::Error::UNSUPPORTED_PLATFORM);
+            }
+            unsafe { Thread::new(stack, init) }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn current_affinity() -> crate::io::Result<Vec<usize>> {
+    Err(crate::io::Error::UNSUPPORTED_PLATFORM)
+}
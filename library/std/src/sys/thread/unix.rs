@@ -1,3 +1,4 @@
+use super::SpawnAttrs;
 #[cfg(not(any(
     target_env = "newlib",
     target_os = "l4re",
@@ -43,7 +44,11 @@ unsafe impl Sync for Thread {}
 impl Thread {
     // unsafe: see thread::Builder::spawn_unchecked for safety requirements
     #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
-    pub unsafe fn new(stack: usize, init: Box<ThreadInit>) -> io::Result<Thread> {
+    pub unsafe fn new(
+        stack: usize,
+        attrs: &SpawnAttrs,
+        init: Box<ThreadInit>,
+    ) -> io::Result<Thread> {
         let data = init;
         let mut attr: mem::MaybeUninit<libc::pthread_attr_t> = mem::MaybeUninit::uninit();
         assert_eq!(libc::pthread_attr_init(attr.as_mut_ptr()), 0);
@@ -51,6 +56,14 @@ pub unsafe fn new(stack: usize, init: Box<ThreadInit>) -> io::Result<Thread> {
             assert_eq!(libc::pthread_attr_destroy(attr.as_mut_ptr()), 0)
         });
 
+        // The affinity mask, if the new thread has to apply it itself.
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let affinity = if attrs.is_default() { None } else { set_attrs(attr.as_mut_ptr(), attrs)? };
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        if !attrs.is_default() {
+            return Err(io::Error::UNSUPPORTED_PLATFORM);
+        }
+
         #[cfg(any(target_os = "espidf", target_os = "nuttx"))]
         if stack > 0 {
             // Only set the stack if a non-zero value is passed
@@ -93,6 +106,11 @@ pub unsafe fn new(stack: usize, init: Box<ThreadInit>) -> io::Result<Thread> {
             };
         }
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(mask) = affinity {
+            return spawn_with_affinity(attr.as_ptr(), data, mask);
+        }
+
         let data = Box::into_raw(data);
         let mut native: libc::pthread_t = mem::zeroed();
         let ret = libc::pthread_create(&mut native, attr.as_ptr(), thread_start, data as *mut _);
@@ -144,6 +162,227 @@ fn drop(&mut self) {
     }
 }
 
+/// Applies the CPU affinity and scheduling attributes of a new thread to its
+/// pthread attributes.
+///
+/// Returns the affinity mask if it cannot be set this way, in which case the
+/// new thread has to apply it itself.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg_attr(target_os = "android", allow(unused_variables))]
+unsafe fn set_attrs(
+    attr: *mut libc::pthread_attr_t,
+    attrs: &SpawnAttrs,
+) -> io::Result<Option<CpuMask>> {
+    let affinity = attrs.affinity.as_deref().map(CpuMask::new).transpose()?;
+    // Only glibc can set the affinity of a thread before it starts.
+    #[cfg(target_env = "gnu")]
+    let affinity = match affinity {
+        Some(mask) => {
+            sys::cvt_nz(unsafe {
+                libc::pthread_attr_setaffinity_np(attr, mask.size(), mask.as_ptr())
+            })?;
+            None
+        }
+        None => None,
+    };
+
+    if attrs.sched_policy.is_some() || attrs.sched_priority.is_some() {
+        #[cfg(target_os = "android")]
+        return Err(io::Error::UNSUPPORTED_PLATFORM);
+        #[cfg(target_os = "linux")]
+        {
+            use crate::os::unix::thread::SchedPolicy;
+            use crate::sys::cvt_nz;
+
+            let policy = match attrs.sched_policy.unwrap_or(SchedPolicy::Other) {
+                SchedPolicy::Other => libc::SCHED_OTHER,
+                SchedPolicy::Fifo => libc::SCHED_FIFO,
+                SchedPolicy::RoundRobin => libc::SCHED_RR,
+            };
+            // `sched_param` has more fields than the priority on some platforms.
+            let mut param: libc::sched_param = unsafe { mem::zeroed() };
+            param.sched_priority = match attrs.sched_priority {
+                Some(priority) => priority,
+                None => unsafe { libc::sched_get_priority_min(policy) },
+            };
+            unsafe {
+                // Without this, the scheduling attributes are ignored and the new
+                // thread inherits those of the spawning thread.
+                cvt_nz(libc::pthread_attr_setinheritsched(attr, libc::PTHREAD_EXPLICIT_SCHED))?;
+                cvt_nz(libc::pthread_attr_setschedpolicy(attr, policy))?;
+                cvt_nz(libc::pthread_attr_setschedparam(attr, &param))?;
+            }
+        }
+    }
+    Ok(affinity)
+}
+
+/// A CPU affinity mask for `sched_setaffinity` and `sched_getaffinity`.
+///
+/// Unlike `cpu_set_t`, this grows as needed, so that it also works on
+/// machines with more than `CPU_SETSIZE` CPUs.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+struct CpuMask(Vec<libc::c_ulong>);
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl CpuMask {
+    const WORD_BITS: usize = libc::c_ulong::BITS as usize;
+    /// Far more CPUs than Linux supports, so that a bogus index is rejected
+    /// instead of allocating a huge mask.
+    const MAX_CPUS: usize = 1 << 16;
+
+    fn new(cpus: &[usize]) -> io::Result<CpuMask> {
+        let Some(&max) = cpus.iter().max() else {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "no CPUs to run on"));
+        };
+        if max >= Self::MAX_CPUS {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "CPU index out of range"));
+        }
+        let mut words = vec![0; max / Self::WORD_BITS + 1];
+        for &cpu in cpus {
+            words[cpu / Self::WORD_BITS] |= 1 << (cpu % Self::WORD_BITS);
+        }
+        Ok(CpuMask(words))
+    }
+
+    /// Returns the affinity mask of the current thread.
+    fn current() -> io::Result<CpuMask> {
+        let mut mask =
+            CpuMask(vec![0; size_of::<libc::cpu_set_t>().div_ceil(size_of::<libc::c_ulong>())]);
+        loop {
+            match sys::cvt(unsafe { libc::sched_getaffinity(0, mask.size(), mask.as_mut_ptr()) }) {
+                Ok(_) => return Ok(mask),
+                // The kernel's mask doesn't fit, try again with a larger one.
+                Err(e)
+                    if e.raw_os_error() == Some(libc::EINVAL)
+                        && mask.0.len() * Self::WORD_BITS < Self::MAX_CPUS =>
+                {
+                    mask.0.resize(mask.0.len() * 2, 0);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Restricts the current thread to the CPUs in this mask.
+    fn apply(&self) -> io::Result<()> {
+        sys::cvt(unsafe { libc::sched_setaffinity(0, self.size(), self.as_ptr()) })?;
+        Ok(())
+    }
+
+    fn cpus(&self) -> Vec<usize> {
+        (0..self.0.len() * Self::WORD_BITS)
+            .filter(|&cpu| self.0[cpu / Self::WORD_BITS] & (1 << (cpu % Self::WORD_BITS)) != 0)
+            .collect()
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(&*self.0)
+    }
+
+    // The kernel and libc only access the first `size()` bytes of the set.
+    fn as_ptr(&self) -> *const libc::cpu_set_t {
+        self.0.as_ptr().cast()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut libc::cpu_set_t {
+        self.0.as_mut_ptr().cast()
+    }
+}
+
+/// Spawns a thread that restricts itself to the CPUs in `mask` before it runs
+/// any user code, for platforms where this can't be done with the pthread
+/// attributes.
+///
+/// This waits for the new thread to report whether setting the affinity
+/// worked. If it didn't, the new thread exits without running `init`'s entry
+/// point, and the error is returned.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn spawn_with_affinity(
+    attr: *const libc::pthread_attr_t,
+    init: Box<ThreadInit>,
+    mask: CpuMask,
+) -> io::Result<Thread> {
+    use crate::any::Any;
+    use crate::panic::{self, AssertUnwindSafe};
+    use crate::sync::atomic::Ordering::{Acquire, Release};
+    use crate::sync::atomic::{Atomic, AtomicBool};
+    use crate::thread;
+
+    /// Shared with the new thread, which only accesses it until `done` is set.
+    struct Start {
+        init: Option<Box<ThreadInit>>,
+        mask: CpuMask,
+        result: Option<io::Result<()>>,
+        // A panic from dropping the closure, if it was not run.
+        panic: Option<Box<dyn Any + Send>>,
+        spawner: thread::Thread,
+        done: Atomic<bool>,
+    }
+
+    let mut start = Start {
+        init: Some(init),
+        mask,
+        result: None,
+        panic: None,
+        spawner: thread::current(),
+        done: AtomicBool::new(false),
+    };
+    let start = &raw mut start;
+    let mut native: libc::pthread_t = unsafe { mem::zeroed() };
+    let ret = unsafe { libc::pthread_create(&mut native, attr, thread_start, start.cast()) };
+    if ret != 0 {
+        // The thread failed to start, so `init` is dropped with `start`.
+        return Err(io::Error::from_raw_os_error(ret));
+    }
+    let native = Thread { id: native };
+
+    // Parking may return spuriously, so check `done` every time.
+    while unsafe { !(*start).done.load(Acquire) } {
+        thread::park();
+    }
+    if let Some(payload) = unsafe { (*start).panic.take() } {
+        panic::resume_unwind(payload);
+    }
+    return unsafe { (*start).result.take().unwrap() }.map(|()| native);
+
+    extern "C" fn thread_start(data: *mut libc::c_void) -> *mut libc::c_void {
+        unsafe {
+            let start = data as *mut Start;
+            // Set up the current thread first, as in `Thread::new`. Moving
+            // `init` out of `start` doesn't touch the allocator.
+            let rust_start = (*start).init.take().unwrap().init();
+            let result = (*start).mask.apply();
+            // If the closure is not run, drop it before `spawn` returns, as it
+            // may borrow data that does not outlive the call.
+            let rust_start = match result {
+                Ok(()) => Some(rust_start),
+                Err(_) => {
+                    let dropped = panic::catch_unwind(AssertUnwindSafe(|| drop(rust_start)));
+                    (*start).panic = dropped.err();
+                    None
+                }
+            };
+            (*start).result = Some(result);
+            // `start` may be gone as soon as `done` is set.
+            let spawner = (*start).spawner.clone();
+            (*start).done.store(true, Release);
+            spawner.unpark();
+
+            if let Some(rust_start) = rust_start {
+                let _handler = sys::stack_overflow::Handler::new();
+                rust_start();
+            }
+        }
+        ptr::null_mut()
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn current_affinity() -> io::Result<Vec<usize>> {
+    Ok(CpuMask::current()?.cpus())
+}
+
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     cfg_select! {
         any(
@@ -1,6 +1,7 @@
 use super::join_handle::JoinHandle;
 use super::lifecycle::spawn_unchecked;
 use crate::io;
+use crate::sys::thread::SpawnAttrs;
 
 /// Thread factory, which can be used in order to configure the properties of
 /// a new thread.
@@ -53,6 +54,8 @@ pub struct Builder {
     pub(super) stack_size: Option<usize>,
     /// Skip running and inheriting the thread spawn hooks
     pub(super) no_hooks: bool,
+    /// Platform-specific attributes such as the CPU affinity
+    pub(crate) attrs: SpawnAttrs,
 }
 
 impl Builder {
@@ -76,7 +79,7 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None, no_hooks: false }
+        Builder { name: None, stack_size: None, no_hooks: false, attrs: SpawnAttrs::default() }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -144,6 +147,50 @@ pub fn no_hooks(mut self) -> Builder {
         self
     }
 
+    /// Restricts the thread-to-be to run only on the given CPUs.
+    ///
+    /// CPUs are identified by their index, starting at zero, as used by the
+    /// operating system. The CPUs the current thread may run on can be
+    /// queried with [`current_affinity`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is currently only supported on Linux and Android. With glibc, it
+    /// uses `pthread_attr_setaffinity_np`. Elsewhere, the new thread calls
+    /// `sched_setaffinity` before it runs the closure, and [`spawn`] waits for
+    /// it to do so. On other platforms, [`spawn`] returns an error of kind
+    /// [`io::ErrorKind::Unsupported`] if this is set.
+    ///
+    /// [`spawn`] returns an error of kind [`io::ErrorKind::InvalidInput`] if
+    /// `cpus` is empty or contains an index too large for the platform, and
+    /// an error if none of the CPUs are available to the process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_affinity)]
+    ///
+    /// use std::thread;
+    ///
+    /// // Pin a latency-critical worker to the second CPU.
+    /// let handler = thread::Builder::new()
+    ///     .affinity(&[1])
+    ///     .spawn(|| {
+    ///         assert_eq!(thread::current_affinity().unwrap(), [1]);
+    ///     })
+    ///     .unwrap();
+    ///
+    /// handler.join().unwrap();
+    /// ```
+    ///
+    /// [`current_affinity`]: super::current_affinity
+    /// [`spawn`]: Builder::spawn
+    #[unstable(feature = "thread_affinity", issue = "none")]
+    pub fn affinity(mut self, cpus: &[usize]) -> Builder {
+        self.attrs.affinity = Some(cpus.to_vec());
+        self
+    }
+
     /// Spawns a new thread by taking ownership of the `Builder`, and returns an
     /// [`io::Result`] to its [`JoinHandle`].
     ///
@@ -257,7 +304,7 @@ pub unsafe fn spawn_unchecked<F, T>(self, f: F) -> io::Result<JoinHandle<T>>
         F: Send,
         T: Send,
     {
-        let Builder { name, stack_size, no_hooks } = self;
-        Ok(JoinHandle(unsafe { spawn_unchecked(name, stack_size, no_hooks, None, f) }?))
+        let Builder { name, stack_size, no_hooks, attrs } = self;
+        Ok(JoinHandle(unsafe { spawn_unchecked(name, stack_size, no_hooks, attrs, None, f) }?))
     }
 }
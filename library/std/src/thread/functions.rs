@@ -694,3 +694,30 @@ pub fn park_timeout(dur: Duration) {
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     imp::available_parallelism()
 }
+
+/// Returns the indices of the CPUs the current thread may run on.
+///
+/// The CPUs are returned in increasing order. A thread can be restricted to
+/// particular CPUs when it is spawned with [`Builder::affinity`].
+///
+/// # Platform-specific behavior
+///
+/// This currently corresponds to `sched_getaffinity` on Linux and Android.
+/// On other platforms, an error of kind [`io::ErrorKind::Unsupported`] is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(thread_affinity)]
+///
+/// use std::thread;
+///
+/// if let Ok(cpus) = thread::current_affinity() {
+///     assert!(!cpus.is_empty());
+/// }
+/// ```
+#[unstable(feature = "thread_affinity", issue = "none")]
+pub fn current_affinity() -> io::Result<Vec<usize>> {
+    imp::current_affinity()
+}
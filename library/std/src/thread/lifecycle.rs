@@ -18,6 +18,7 @@ pub(super) unsafe fn spawn_unchecked<'scope, F, T>(
     name: Option<String>,
     stack_size: Option<usize>,
     no_hooks: bool,
+    attrs: imp::SpawnAttrs,
     scope_data: Option<Arc<ScopeData>>,
     f: F,
 ) -> io::Result<JoinInner<'scope, T>>
@@ -113,7 +114,7 @@ pub(super) unsafe fn spawn_unchecked<'scope, F, T>(
         // Similarly, the `sys` implementation must guarantee that no references to the closure
         // exist after the thread has terminated, which is signaled by `Thread::join`
         // returning.
-        native: unsafe { imp::Thread::new(stack_size, init)? },
+        native: unsafe { imp::spawn(stack_size, &attrs, init)? },
         thread,
         packet: my_packet,
     })
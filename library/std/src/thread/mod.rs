@@ -185,6 +185,8 @@
 pub(crate) use current::{current_or_unnamed, current_os_id, drop_current, with_current_name};
 #[stable(feature = "available_parallelism", since = "1.59.0")]
 pub use functions::available_parallelism;
+#[unstable(feature = "thread_affinity", issue = "none")]
+pub use functions::current_affinity;
 #[stable(feature = "park_timeout", since = "1.4.0")]
 pub use functions::park_timeout;
 #[stable(feature = "thread_sleep", since = "1.4.0")]
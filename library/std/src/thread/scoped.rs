@@ -262,9 +262,9 @@ pub fn spawn_scoped<'scope, 'env, F, T>(
         F: FnOnce() -> T + Send + 'scope,
         T: Send + 'scope,
     {
-        let Builder { name, stack_size, no_hooks } = self;
+        let Builder { name, stack_size, no_hooks, attrs } = self;
         Ok(ScopedJoinHandle(unsafe {
-            spawn_unchecked(name, stack_size, no_hooks, Some(scope.data.clone()), f)
+            spawn_unchecked(name, stack_size, no_hooks, attrs, Some(scope.data.clone()), f)
         }?))
     }
 }
@@ -425,3 +425,71 @@ fn test_minimal_thread_stack() {
     assert_eq!(before, 0);
     assert_eq!(COUNT.load(Ordering::Relaxed), 1);
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_affinity() {
+    let cpus = thread::current_affinity().unwrap();
+    let last = *cpus.last().unwrap();
+
+    let affinity = Builder::new()
+        .affinity(&[last])
+        .spawn(|| thread::current_affinity().unwrap())
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(affinity, [last]);
+
+    let err = Builder::new().affinity(&[]).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::InvalidInput);
+    let err = Builder::new().affinity(&[usize::MAX]).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_affinity_failure_drops_closure() {
+    struct SetOnDrop<'a>(&'a AtomicBool);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // No machine has this CPU, so setting the affinity fails, possibly only
+    // once the new thread has started. The closure borrows `dropped`, so it
+    // must be gone by the time `spawn_scoped` returns.
+    let dropped = AtomicBool::new(false);
+    thread::scope(|s| {
+        let guard = SetOnDrop(&dropped);
+        let result = Builder::new().affinity(&[65535]).spawn_scoped(s, move || drop(guard));
+        assert!(result.is_err());
+        assert!(dropped.load(Ordering::Relaxed));
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sched_policy() {
+    use crate::os::unix::thread::{BuilderExt, SchedPolicy};
+
+    let policy = || {
+        let mut param: libc::sched_param = unsafe { crate::mem::zeroed() };
+        let mut policy = 0;
+        assert_eq!(
+            unsafe { libc::pthread_getschedparam(libc::pthread_self(), &mut policy, &mut param) },
+            0
+        );
+        policy
+    };
+
+    // Real-time policies need privileges that the tests may not have.
+    match Builder::new().sched_policy(SchedPolicy::RoundRobin).spawn(policy) {
+        Ok(handle) => assert_eq!(handle.join().unwrap(), libc::SCHED_RR),
+        Err(err) => assert_eq!(err.kind(), crate::io::ErrorKind::PermissionDenied),
+    }
+
+    let err = Builder::new().priority(1000).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::InvalidInput);
+}
@@ -39,6 +39,7 @@
 use crate::error::Error;
 use crate::fmt;
 use crate::ops::{Add, AddAssign, Sub, SubAssign};
+use crate::str::FromStr;
 use crate::sys::{FromInner, IntoInner, time};
 
 /// A measurement of a monotonically nondecreasing clock.
@@ -732,6 +733,68 @@ pub fn saturating_sub(&self, duration: Duration) -> SystemTime {
     pub fn saturating_duration_since(&self, earlier: SystemTime) -> Duration {
         self.duration_since(earlier).unwrap_or(Duration::ZERO)
     }
+
+    /// Breaks this time down into a UTC calendar date and time of day.
+    ///
+    /// Like the system clock, this ignores leap seconds: every day is
+    /// exactly 86400 seconds long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_utc_datetime)]
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let time = SystemTime::UNIX_EPOCH + Duration::new(1_000_000_000, 5_000_000);
+    /// let utc = time.to_utc();
+    /// assert_eq!((utc.year(), utc.month(), utc.day()), (2001, 9, 9));
+    /// assert_eq!((utc.hour(), utc.minute(), utc.second()), (1, 46, 40));
+    /// assert_eq!(utc.to_string(), "2001-09-09T01:46:40.005Z");
+    /// ```
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub fn to_utc(&self) -> UtcDateTime {
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => (i128::from(d.as_secs()), d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => (-i128::from(d.as_secs()), 0),
+                    nanos => (-i128::from(d.as_secs()) - 1, NANOS_PER_SEC - nanos),
+                }
+            }
+        };
+        UtcDateTime::from_unix(secs, nanos)
+    }
+
+    /// Creates a `SystemTime` from a UTC calendar date and time of day.
+    ///
+    /// Returns `None` if the time cannot be represented as a `SystemTime` on
+    /// this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_utc_datetime)]
+    /// use std::time::{SystemTime, UtcDateTime};
+    ///
+    /// let utc: UtcDateTime = "1970-01-02T00:00:00Z".parse().unwrap();
+    /// let time = SystemTime::from_utc(utc).unwrap();
+    /// assert_eq!(time.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(), 86400);
+    /// assert_eq!(time.to_utc(), utc);
+    /// ```
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub fn from_utc(utc: UtcDateTime) -> Option<SystemTime> {
+        let secs = utc.unix_secs();
+        let nanos = Duration::from_nanos(u64::from(utc.nanosecond));
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(u64::try_from(secs).ok()?))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(u64::try_from(-secs).ok()?))
+        };
+        time?.checked_add(nanos)
+    }
 }
 
 #[stable(feature = "time2", since = "1.8.0")]
@@ -848,6 +911,350 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+const SECS_PER_DAY: i128 = 86400;
+
+/// A date and time of day in UTC, broken down into calendar fields.
+///
+/// Dates use the proleptic Gregorian calendar, extended to years before
+/// 1 AD with astronomical year numbering: the year before year 1 is year 0.
+/// Leap seconds are not supported, as they are not by [`SystemTime`].
+///
+/// A `UtcDateTime` is usually created from a [`SystemTime`] with
+/// [`SystemTime::to_utc`] and turned back into one with
+/// [`SystemTime::from_utc`].
+///
+/// # Formatting and parsing
+///
+/// The [`Display`](fmt::Display) implementation writes the time in the
+/// [RFC 3339] format, which is also valid ISO 8601, such as
+/// `2024-02-29T13:05:09.25Z`. Fractional seconds are written with as many
+/// digits as needed, or with the number of digits given as the precision,
+/// such as `{:.3}` for milliseconds. Years outside of `0..=9999`, which RFC
+/// 3339 cannot represent, are written with a sign as in ISO 8601, such as
+/// `+10000-01-01T00:00:00Z`.
+///
+/// The [`FromStr`] implementation parses RFC 3339 timestamps. Times with an
+/// offset other than `Z`, such as `2024-02-29T14:05:09+01:00`, are converted
+/// to UTC. Leap seconds (a seconds value of `60`) are rejected.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+///
+/// # Examples
+///
+/// ```
+/// #![feature(time_utc_datetime)]
+/// use std::time::UtcDateTime;
+///
+/// let utc = UtcDateTime::new(2024, 2, 29).unwrap().with_time(13, 5, 9, 250_000_000).unwrap();
+/// assert_eq!(utc.to_string(), "2024-02-29T13:05:09.25Z");
+/// assert_eq!(format!("{utc:.3}"), "2024-02-29T13:05:09.250Z");
+/// assert_eq!(format!("{utc:.0}"), "2024-02-29T13:05:09Z");
+///
+/// assert_eq!("2024-02-29T14:05:09.25+01:00".parse(), Ok(utc));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+pub struct UtcDateTime {
+    // The field order makes the derived `Ord` chronological.
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+impl UtcDateTime {
+    /// Creates the start of the given day, at midnight UTC.
+    ///
+    /// `month` and `day` are one-based. Returns `None` if the date does not
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_utc_datetime)]
+    /// use std::time::UtcDateTime;
+    ///
+    /// assert!(UtcDateTime::new(2024, 2, 29).is_some());
+    /// assert!(UtcDateTime::new(2023, 2, 29).is_none());
+    /// assert!(UtcDateTime::new(2024, 13, 1).is_none());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn new(year: i64, month: u8, day: u8) -> Option<UtcDateTime> {
+        if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(UtcDateTime { year, month, day, hour: 0, minute: 0, second: 0, nanosecond: 0 })
+    }
+
+    /// Returns the same day at the given time of day.
+    ///
+    /// Returns `None` if `hour`, `minute`, `second` or `nanosecond` is out of
+    /// range. Leap seconds cannot be represented.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn with_time(
+        self,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Option<UtcDateTime> {
+        if hour > 23 || minute > 59 || second > 59 || nanosecond >= NANOS_PER_SEC {
+            return None;
+        }
+        Some(UtcDateTime { hour, minute, second, nanosecond, ..self })
+    }
+
+    /// Returns the year.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the month, from 1 (January) to 12 (December).
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting at 1.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns the fractional part of the second in nanoseconds, from 0 to
+    /// 999,999,999.
+    #[must_use]
+    #[unstable(feature = "time_utc_datetime", issue = "none")]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Creates the time `secs` seconds and `nanos` nanoseconds after the Unix
+    /// epoch.
+    fn from_unix(secs: i128, nanos: u32) -> UtcDateTime {
+        let days = secs.div_euclid(SECS_PER_DAY);
+        let secs = secs.rem_euclid(SECS_PER_DAY) as u32;
+
+        // Howard Hinnant's `civil_from_days`, see
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i128::from(month <= 2)) as i64;
+
+        UtcDateTime {
+            year,
+            month,
+            day,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+            nanosecond: nanos,
+        }
+    }
+
+    /// Returns the number of whole seconds since the Unix epoch.
+    fn unix_secs(&self) -> i128 {
+        // Howard Hinnant's `days_from_civil`, see
+        // https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = i128::from(self.month);
+        let year = i128::from(self.year) - i128::from(month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let doy = (153 * ((month + 9) % 12) + 2) / 5 + i128::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+
+        let secs = i128::from(self.hour) * 3600 + i128::from(self.minute) * 60;
+        days * SECS_PER_DAY + secs + i128::from(self.second)
+    }
+}
+
+const fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+impl fmt::Display for UtcDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if (0..=9999).contains(&self.year) {
+            write!(f, "{:04}", self.year)?;
+        } else {
+            write!(f, "{:+05}", self.year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        let digits = match f.precision() {
+            Some(precision) => precision.min(9) as u32,
+            None if self.nanosecond == 0 => 0,
+            // Leave out trailing zeros.
+            None => {
+                let mut digits = 9;
+                while self.nanosecond % 10u32.pow(10 - digits) == 0 {
+                    digits -= 1;
+                }
+                digits
+            }
+        };
+        if digits > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - digits);
+            write!(f, ".{fraction:0width$}", width = digits as usize)?;
+        }
+        f.write_str("Z")
+    }
+}
+
+/// An error returned when parsing a [`UtcDateTime`] fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+pub struct ParseUtcDateTimeError;
+
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+impl fmt::Display for ParseUtcDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid RFC 3339 date and time")
+    }
+}
+
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+impl Error for ParseUtcDateTimeError {}
+
+#[unstable(feature = "time_utc_datetime", issue = "none")]
+impl FromStr for UtcDateTime {
+    type Err = ParseUtcDateTimeError;
+
+    fn from_str(s: &str) -> Result<UtcDateTime, ParseUtcDateTimeError> {
+        parse_rfc3339(s.as_bytes()).ok_or(ParseUtcDateTimeError)
+    }
+}
+
+fn parse_rfc3339(mut s: &[u8]) -> Option<UtcDateTime> {
+    // Takes exactly `n` ASCII digits from the front of `s`.
+    fn digits(s: &mut &[u8], n: usize) -> Option<u32> {
+        let digits = s.get(..n)?;
+        let mut value = 0;
+        for &b in digits {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+            value = value * 10 + u32::from(b - b'0');
+        }
+        *s = &s[n..];
+        Some(value)
+    }
+
+    // Takes one byte from the front of `s` if it matches `expected`.
+    fn take(s: &mut &[u8], expected: impl Fn(u8) -> bool) -> Option<u8> {
+        let (&b, rest) = s.split_first()?;
+        if !expected(b) {
+            return None;
+        }
+        *s = rest;
+        Some(b)
+    }
+
+    let year = digits(&mut s, 4)?;
+    take(&mut s, |b| b == b'-')?;
+    let month = digits(&mut s, 2)?;
+    take(&mut s, |b| b == b'-')?;
+    let day = digits(&mut s, 2)?;
+    // RFC 3339 allows a space instead of the `T`.
+    take(&mut s, |b| matches!(b, b'T' | b't' | b' '))?;
+    let hour = digits(&mut s, 2)?;
+    take(&mut s, |b| b == b':')?;
+    let minute = digits(&mut s, 2)?;
+    take(&mut s, |b| b == b':')?;
+    let second = digits(&mut s, 2)?;
+
+    let mut nanosecond = 0;
+    if take(&mut s, |b| b == b'.').is_some() {
+        let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        // Digits beyond nanosecond precision are ignored.
+        let precise = len.min(9);
+        nanosecond = digits(&mut s, precise)? * 10u32.pow(9 - precise as u32);
+        s = &s[len - precise..];
+    }
+
+    let offset = match take(&mut s, |_| true)? {
+        b'Z' | b'z' => 0,
+        sign @ (b'+' | b'-') => {
+            let hours = digits(&mut s, 2)?;
+            take(&mut s, |b| b == b':')?;
+            let minutes = digits(&mut s, 2)?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = i128::from(hours * 3600 + minutes * 60);
+            if sign == b'-' { -offset } else { offset }
+        }
+        _ => return None,
+    };
+    if !s.is_empty() {
+        return None;
+    }
+
+    let local = UtcDateTime::new(i64::from(year), month as u8, day as u8)?.with_time(
+        hour as u8,
+        minute as u8,
+        second as u8,
+        nanosecond,
+    )?;
+    if offset == 0 {
+        Some(local)
+    } else {
+        Some(UtcDateTime::from_unix(local.unix_secs() - offset, nanosecond))
+    }
+}
+
 impl FromInner<time::SystemTime> for SystemTime {
     fn from_inner(time: time::SystemTime) -> SystemTime {
         SystemTime(time)
@@ -2,11 +2,13 @@
 #![feature(const_trait_impl)]
 #![feature(mapped_lock_guards)]
 #![feature(mpmc_channel)]
+#![feature(mpmc_select)]
 #![feature(oneshot_channel)]
 #![feature(once_cell_try)]
 #![feature(lock_value_accessors)]
 #![feature(reentrant_lock)]
 #![feature(std_internals)]
+#![feature(sync_semaphore)]
 #![feature(sync_nonpoison)]
 #![feature(nonpoison_condvar)]
 #![feature(nonpoison_mutex)]
@@ -34,6 +36,8 @@
 mod reentrant_lock;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod rwlock;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod semaphore;
 
 #[path = "../common/mod.rs"]
 mod common;
@@ -773,3 +773,151 @@ fn issue_39364() {
     let _tx = t.join().unwrap(); // delay dropping until end of test
     let _ = rx.recv_timeout(Duration::from_millis(500));
 }
+
+#[test]
+fn select_ready() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+    assert!(sel.try_select().is_err());
+
+    tx1.send(1).unwrap();
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+    drop(tx2);
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx, rx) = channel::<i32>();
+    let (stx, _srx) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    sel.send(&stx);
+
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(100)).err(), Some(SelectTimeoutError));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert_eq!(sel.select_deadline(deadline).err(), Some(SelectTimeoutError));
+
+    // With no operations, selection only waits for the timeout.
+    assert!(Select::new().select_timeout(Duration::from_millis(10)).is_err());
+}
+
+#[test]
+fn select_disconnected_and_remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    drop(tx2);
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Err(RecvError));
+    sel.remove(oper2);
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx1.send(1).unwrap();
+    });
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_send() {
+    let (tx, rx) = sync_channel::<i32>(1);
+    tx.send(0).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.send(&tx);
+    assert!(sel.try_select().is_err());
+
+    assert_eq!(rx.recv(), Ok(0));
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    oper.send(&tx, 1).unwrap();
+    assert_eq!(rx.recv(), Ok(1));
+
+    drop(rx);
+    let oper = sel.select();
+    assert_eq!(oper.send(&tx, 2), Err(SendError(2)));
+}
+
+#[test]
+fn select_rendezvous() {
+    // Both sides of zero-capacity channels selecting at the same time must pair up.
+    const N: usize = 1000;
+    let (tx1, rx1) = sync_channel::<usize>(0);
+    let (tx2, rx2) = sync_channel::<usize>(0);
+
+    let t = thread::spawn(move || {
+        for i in 0..N {
+            select! {
+                send(tx1, i) -> res => res.unwrap(),
+                send(tx2, i) -> res => res.unwrap(),
+            }
+        }
+    });
+
+    let mut sum = 0;
+    for _ in 0..N {
+        select! {
+            recv(rx1) -> msg => sum += msg.unwrap(),
+            recv(rx2) -> msg => sum += msg.unwrap(),
+        }
+    }
+    t.join().unwrap();
+    assert_eq!(sum, N * (N - 1) / 2);
+}
+
+#[test]
+fn select_macro() {
+    let (tx, rx) = channel::<i32>();
+    let (stx, srx) = sync_channel::<i32>(0);
+
+    let res = select! {
+        recv(rx) -> msg => msg.ok(),
+        default => None,
+    };
+    assert_eq!(res, None);
+
+    let sent = select! {
+        send(stx, 1) -> res => res.is_ok(),
+        default(Duration::from_millis(10)) => false,
+    };
+    assert!(!sent);
+
+    for i in 0..3 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+    let mut received = Vec::new();
+    loop {
+        select! {
+            recv(rx) -> msg => match msg {
+                Ok(msg) => received.push(msg),
+                Err(_) => break,
+            },
+            recv(srx) -> _ => unreachable!(),
+        }
+    }
+    assert_eq!(received, [0, 1, 2]);
+}
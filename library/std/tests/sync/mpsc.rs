@@ -736,3 +736,32 @@ fn issue_39364() {
     let _tx = t.join().unwrap(); // delay dropping until end of test
     let _ = rx.recv_timeout(Duration::from_millis(500));
 }
+
+#[test]
+fn select() {
+    use std::sync::mpmc::{Select, select};
+
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        tx2.send(2).unwrap();
+        tx1.send(1).unwrap();
+        // Keep `rx2` from becoming ready because of a disconnect.
+        tx2
+    });
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    let msg = select! {
+        recv(rx1) -> msg => msg.unwrap(),
+        recv(rx2) -> _ => unreachable!(),
+    };
+    assert_eq!(msg, 1);
+    drop(t.join().unwrap());
+}
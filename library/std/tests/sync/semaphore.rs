@@ -0,0 +1,157 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Semaphore};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn smoke() {
+    let semaphore = Semaphore::new(2);
+    let a = semaphore.acquire();
+    let b = semaphore.try_acquire().unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+    assert!(semaphore.try_acquire().is_none());
+    drop(a);
+    assert_eq!(semaphore.available_permits(), 1);
+    drop(b);
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[test]
+fn acquire_many() {
+    let semaphore = Semaphore::new(5);
+    let permit = semaphore.acquire_many(3);
+    assert_eq!(permit.count(), 3);
+    assert!(semaphore.try_acquire_many(3).is_none());
+    let rest = semaphore.try_acquire_many(2).unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+
+    // Acquiring no permits always succeeds.
+    assert_eq!(semaphore.acquire_many(0).count(), 0);
+
+    drop(permit);
+    drop(rest);
+    assert_eq!(semaphore.available_permits(), 5);
+}
+
+#[test]
+fn forget_and_add_permits() {
+    let semaphore = Semaphore::new(1);
+    semaphore.acquire().forget();
+    assert_eq!(semaphore.available_permits(), 0);
+    semaphore.add_permits(3);
+    assert_eq!(semaphore.available_permits(), 3);
+}
+
+#[test]
+fn acquire_timeout() {
+    let semaphore = Semaphore::new(1);
+    let permit = semaphore.acquire_timeout(Duration::from_millis(10)).unwrap();
+    assert!(semaphore.acquire_timeout(Duration::from_millis(10)).is_none());
+    assert!(semaphore.acquire_many_timeout(2, Duration::ZERO).is_none());
+    drop(permit);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "wasi"), ignore)] // no threads
+fn limits_concurrency() {
+    const PERMITS: usize = 3;
+
+    let semaphore = Semaphore::new(PERMITS);
+    let active = AtomicUsize::new(0);
+    let max_active = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..10 {
+            s.spawn(|| {
+                for _ in 0..10 {
+                    let _permit = semaphore.acquire();
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_active.fetch_max(now, Ordering::SeqCst);
+                    thread::yield_now();
+                    active.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert!(max_active.load(Ordering::SeqCst) <= PERMITS);
+    assert_eq!(semaphore.available_permits(), PERMITS);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "wasi"), ignore)] // no threads
+fn fairness() {
+    let semaphore = Arc::new(Semaphore::new(1));
+
+    // A thread waiting for two permits...
+    let waiter = thread::spawn({
+        let semaphore = semaphore.clone();
+        move || semaphore.acquire_many(2).forget()
+    });
+
+    // ...keeps other threads from taking the available permit once it waits.
+    while let Some(permit) = semaphore.try_acquire() {
+        drop(permit);
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert!(semaphore.acquire_timeout(Duration::from_millis(10)).is_none());
+    assert_eq!(semaphore.available_permits(), 1);
+
+    semaphore.add_permits(1);
+    waiter.join().unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "wasi"), ignore)] // no threads
+fn timeout_unblocks_waiters_behind() {
+    let semaphore = Arc::new(Semaphore::new(1));
+
+    // A waiter for two permits at the front of the queue times out...
+    let big = thread::spawn({
+        let semaphore = semaphore.clone();
+        move || semaphore.acquire_many_timeout(2, Duration::from_millis(100)).is_some()
+    });
+    while let Some(permit) = semaphore.try_acquire() {
+        drop(permit);
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    // ...after which the waiter for the available permit behind it gets it.
+    let permit = semaphore.acquire();
+    assert!(!big.join().unwrap());
+    drop(permit);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "wasi"), ignore)] // no threads
+fn mixed_counts_and_timeouts() {
+    const PERMITS: usize = 4;
+
+    let semaphore = Semaphore::new(PERMITS);
+    let active = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for t in 0..8 {
+            let semaphore = &semaphore;
+            let active = &active;
+            s.spawn(move || {
+                for i in 0..200 {
+                    let count = (t + i) % 3 + 1;
+                    let permit = if i % 4 == 0 {
+                        match semaphore.acquire_many_timeout(count, Duration::from_micros(50)) {
+                            Some(permit) => permit,
+                            None => continue,
+                        }
+                    } else {
+                        semaphore.acquire_many(count)
+                    };
+                    assert!(active.fetch_add(count, Ordering::SeqCst) + count <= PERMITS);
+                    active.fetch_sub(count, Ordering::SeqCst);
+                    drop(permit);
+                }
+            });
+        }
+    });
+    assert_eq!(semaphore.available_permits(), PERMITS);
+    assert!(semaphore.try_acquire_many(PERMITS).is_some());
+}
@@ -2,9 +2,10 @@
 #![feature(duration_constructors)]
 #![feature(time_systemtime_limits)]
 #![feature(time_saturating_systemtime)]
+#![feature(time_utc_datetime)]
 
 use std::fmt::Debug;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH, UtcDateTime};
 
 macro_rules! assert_almost_eq {
     ($a:expr, $b:expr) => {{
@@ -310,3 +311,98 @@ fn system_time_saturating() {
         Duration::ZERO
     );
 }
+
+#[test]
+fn system_time_to_utc() {
+    let utc = (UNIX_EPOCH + Duration::new(951_782_400, 7)).to_utc();
+    assert_eq!((utc.year(), utc.month(), utc.day()), (2000, 2, 29));
+    assert_eq!((utc.hour(), utc.minute(), utc.second(), utc.nanosecond()), (0, 0, 0, 7));
+
+    let utc = UNIX_EPOCH.to_utc();
+    assert_eq!(utc, UtcDateTime::new(1970, 1, 1).unwrap());
+
+    // Times before the epoch round towards the past.
+    if let Some(before) = UNIX_EPOCH.checked_sub(Duration::new(0, 1)) {
+        let utc = before.to_utc();
+        assert_eq!(utc.to_string(), "1969-12-31T23:59:59.999999999Z");
+        assert_eq!(SystemTime::from_utc(utc), Some(before));
+    }
+}
+
+#[test]
+fn system_time_utc_round_trip() {
+    let now = SystemTime::now();
+    assert_eq!(SystemTime::from_utc(now.to_utc()), Some(now));
+
+    let mut secs = 0;
+    while secs < 10_000_000_000 {
+        let time = UNIX_EPOCH + Duration::new(secs, 123_456_789);
+        assert_eq!(SystemTime::from_utc(time.to_utc()), Some(time));
+        secs += 12_345_678;
+    }
+
+    let far = UtcDateTime::new(i64::MAX, 12, 31).unwrap();
+    assert_eq!(SystemTime::from_utc(far), None);
+}
+
+#[test]
+fn utc_date_time_new() {
+    assert!(UtcDateTime::new(2000, 2, 29).is_some());
+    assert!(UtcDateTime::new(1900, 2, 29).is_none());
+    assert!(UtcDateTime::new(2023, 4, 31).is_none());
+    assert!(UtcDateTime::new(2023, 0, 1).is_none());
+    assert!(UtcDateTime::new(2023, 1, 0).is_none());
+
+    let date = UtcDateTime::new(2023, 1, 1).unwrap();
+    assert!(date.with_time(23, 59, 59, 999_999_999).is_some());
+    assert!(date.with_time(24, 0, 0, 0).is_none());
+    assert!(date.with_time(0, 60, 0, 0).is_none());
+    assert!(date.with_time(0, 0, 60, 0).is_none());
+    assert!(date.with_time(0, 0, 0, 1_000_000_000).is_none());
+    assert!(date < date.with_time(0, 0, 0, 1).unwrap());
+}
+
+#[test]
+fn utc_date_time_display() {
+    let date = UtcDateTime::new(2024, 7, 4).unwrap();
+    let utc = date.with_time(9, 5, 3, 120_000_000).unwrap();
+    assert_eq!(utc.to_string(), "2024-07-04T09:05:03.12Z");
+    assert_eq!(format!("{utc:.0}"), "2024-07-04T09:05:03Z");
+    assert_eq!(format!("{utc:.6}"), "2024-07-04T09:05:03.120000Z");
+    assert_eq!(format!("{utc:.12}"), "2024-07-04T09:05:03.120000000Z");
+    assert_eq!(date.to_string(), "2024-07-04T00:00:00Z");
+
+    assert_eq!(UtcDateTime::new(0, 1, 1).unwrap().to_string(), "0000-01-01T00:00:00Z");
+    assert_eq!(UtcDateTime::new(-1, 1, 1).unwrap().to_string(), "-0001-01-01T00:00:00Z");
+    assert_eq!(UtcDateTime::new(12345, 1, 1).unwrap().to_string(), "+12345-01-01T00:00:00Z");
+}
+
+#[test]
+fn utc_date_time_parse() {
+    let utc = UtcDateTime::new(1985, 4, 12).unwrap().with_time(23, 20, 50, 520_000_000).unwrap();
+    assert_eq!("1985-04-12T23:20:50.52Z".parse(), Ok(utc));
+    assert_eq!("1985-04-12t23:20:50.520z".parse(), Ok(utc));
+    assert_eq!("1985-04-12 23:20:50.5200000009Z".parse(), Ok(utc));
+    // Offsets are converted to UTC, possibly changing the date.
+    assert_eq!("1985-04-13T01:20:50.52+02:00".parse(), Ok(utc));
+    assert_eq!("1985-04-12T15:50:50.52-07:30".parse(), Ok(utc));
+    assert_eq!("1985-04-12T23:20:50.52-00:00".parse(), Ok(utc));
+
+    for invalid in [
+        "",
+        "1985-04-12",
+        "1985-04-12T23:20:50",
+        "1985-04-12T23:20:50.Z",
+        "1985-04-12T23:20:60Z",
+        "1985-04-12T24:00:00Z",
+        "1985-02-29T00:00:00Z",
+        "1985-4-12T23:20:50Z",
+        "1985-04-12T23:20:50+2:00",
+        "1985-04-12T23:20:50+24:00",
+        "1985-04-12T23:20:50Z ",
+        "+1985-04-12T23:20:50Z",
+        "1985-04-12T23:20:50.+1Z",
+    ] {
+        assert!(invalid.parse::<UtcDateTime>().is_err(), "{invalid:?}");
+    }
+}
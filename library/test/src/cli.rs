@@ -3,16 +3,23 @@
 use std::env;
 use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
-use super::time::TestTimeOptions;
+use super::time::{TestTimeOptions, parse_duration};
 
 #[derive(Debug)]
 pub struct TestOpts {
     pub list: bool,
     pub filters: Vec<String>,
     pub filter_exact: bool,
+    /// Run only the tests defined in one of these source files.
+    pub filter_files: Vec<String>,
+    /// Run only the tests at one of these source locations, see `--filter-line`.
+    pub filter_lines: Vec<(String, usize)>,
     pub force_run_in_process: bool,
+    /// Run each test in a separate process, so that a crash only fails that test.
+    pub isolate: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
     pub run_tests: bool,
@@ -23,9 +30,23 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub shuffle: bool,
     pub shuffle_seed: Option<u64>,
+    /// Run only the tests assigned to this shard (counting from 0).
+    pub shard_index: Option<usize>,
+    /// Number of shards to split the tests into.
+    pub shard_count: Option<usize>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Hard limit on the execution time of each test, after which the test
+    /// is reported as failed and, when it runs in a subprocess, killed.
+    pub test_timeout: Option<Duration>,
+    /// Number of times a failed test is run again before it is reported as
+    /// failed. Tests that pass on a later attempt are reported as flaky.
+    pub retry: usize,
+    /// Save the results of the benchmarks as the baseline with this name.
+    pub save_baseline: Option<String>,
+    /// Compare the results of the benchmarks with the baseline with this name.
+    pub baseline: Option<String>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
@@ -53,6 +74,12 @@ fn optgroups() -> getopts::Options {
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
         .optflag("", "force-run-in-process", "Forces tests to run in-process when panic=abort")
+        .optflag(
+            "",
+            "isolate",
+            "Run each test in its own process, so that a test that crashes or
+            exits the process fails on its own instead of ending the run",
+        )
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
@@ -87,6 +114,20 @@ fn optgroups() -> getopts::Options {
              Alias to --format=terse",
         )
         .optflag("", "exact", "Exactly match filters rather than by substring")
+        .optmulti(
+            "",
+            "filter-file",
+            "Run only the tests defined in FILE (this flag can be used
+            multiple times)",
+            "FILE",
+        )
+        .optmulti(
+            "",
+            "filter-line",
+            "Run only the test in FILE whose name or body contains LINE
+            (this flag can be used multiple times)",
+            "FILE:LINE",
+        )
         .optopt(
             "",
             "color",
@@ -103,8 +144,9 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP version 14 document",
+            "pretty|terse|json|junit|tap",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -143,12 +185,61 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Fail tests that run for longer than DURATION. Tests that run in a
+            separate process (e.g. with panic=abort) are killed; in-process
+            tests are abandoned and keep running in the background.
+
+            DURATION is a number followed by an optional unit (`ms`, `s` or
+            `m`), e.g. `500ms` or `30s`; the default unit is seconds.",
+            "DURATION",
+        )
+        .optopt(
+            "",
+            "retry",
+            "Run failed tests again up to N times; tests that pass on a
+            retry are reported as flaky instead of failed. Only tests
+            defined with #[test] can be retried, not doctests",
+            "N",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the results of the benchmarks as the baseline NAME, in the
+            directory given by `RUST_TEST_BASELINE_DIR` (by default
+            `target/libtest-baselines`)",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the results of the benchmarks with the baseline NAME saved
+            by an earlier run with --save-baseline",
+            "NAME",
+        )
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
             "shuffle-seed",
             "Run tests in random order; seed the random number generator with SEED",
             "SEED",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into N disjoint shards; use together with
+            --shard-index to run a single shard, or with --list to show the
+            shard each test belongs to",
+            "N",
+        )
+        .optopt(
+            "",
+            "shard-index",
+            "Run only the tests in shard INDEX (counting from 0) of the
+            --shard-count shards",
+            "INDEX",
         );
     opts
 }
@@ -253,6 +344,21 @@ macro_rules! unstable_optopt {
     }};
 }
 
+// Gets the option values and checks if unstable features are enabled.
+macro_rules! unstable_optmulti {
+    ($matches:ident, $allow_unstable:ident, $option_name:literal) => {{
+        let opt = $matches.opt_strs($option_name);
+        if !$allow_unstable && !opt.is_empty() {
+            return Err(format!(
+                "The \"{}\" option is only accepted on the nightly compiler with -Z unstable-options",
+                $option_name
+            ));
+        }
+
+        opt
+    }};
+}
+
 // Implementation of `parse_opts` that doesn't care about help message
 // and returns a `Result`.
 fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
@@ -260,11 +366,21 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     // Unstable flags
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    if isolate && force_run_in_process {
+        return Err("--isolate and --force-run-in-process are mutually exclusive".to_string());
+    }
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let fail_fast = unstable_optflag!(matches, allow_unstable, "fail-fast");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retry = get_retry(&matches, allow_unstable)?;
+    let (save_baseline, baseline) = get_baselines(&matches, allow_unstable)?;
+    let filter_files = get_filter_files(&matches, allow_unstable)?;
+    let filter_lines = get_filter_lines(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let (shard_index, shard_count) = get_shard(&matches, allow_unstable)?;
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -293,7 +409,10 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         list,
         filters,
         filter_exact: exact,
+        filter_files,
+        filter_lines,
         force_run_in_process,
+        isolate,
         exclude_should_panic,
         run_ignored,
         run_tests,
@@ -304,9 +423,15 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         format,
         shuffle,
         shuffle_seed,
+        shard_index,
+        shard_count,
         test_threads,
         skip,
         time_options,
+        test_timeout,
+        retry,
+        save_baseline,
+        baseline,
         options,
         fail_fast,
     };
@@ -343,6 +468,92 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(timeout_str) => match parse_duration(&timeout_str) {
+            Some(timeout) if !timeout.is_zero() => Some(timeout),
+            _ => {
+                return Err(format!(
+                    "argument for --test-timeout must be a positive duration \
+                     such as `500ms`, `30s` or `2m` (was {timeout_str})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retry(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retry = match unstable_optopt!(matches, allow_unstable, "retry") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!(
+                    "argument for --retry must be a number \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retry)
+}
+
+fn get_filter_files(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Vec<String>> {
+    let filter_files = unstable_optmulti!(matches, allow_unstable, "filter-file");
+
+    Ok(filter_files)
+}
+
+fn get_filter_lines(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Vec<(String, usize)>> {
+    let filter_lines = unstable_optmulti!(matches, allow_unstable, "filter-line");
+
+    filter_lines
+        .into_iter()
+        .map(|location| match location.rsplit_once(':') {
+            Some((file, line)) if !file.is_empty() => match line.parse::<usize>() {
+                Ok(line) if line > 0 => Ok((file.to_owned(), line)),
+                _ => Err(format!(
+                    "argument for --filter-line must be FILE:LINE with a line number > 0 \
+                     (was {location})"
+                )),
+            },
+            _ => Err(format!("argument for --filter-line must be FILE:LINE (was {location})")),
+        })
+        .collect()
+}
+
+fn get_baselines(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<String>, Option<String>)> {
+    let save_baseline = unstable_optopt!(matches, allow_unstable, "save-baseline");
+    let baseline = unstable_optopt!(matches, allow_unstable, "baseline");
+
+    // Baselines are files in a shared directory, so only plain names are allowed.
+    let is_valid =
+        |name: &str| !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']);
+    if let Some(name) = save_baseline.as_deref().filter(|name| !is_valid(name)) {
+        return Err(format!(
+            "argument for --save-baseline must be a name, not a path (was {name})"
+        ));
+    }
+    if let Some(name) = baseline.as_deref().filter(|name| !is_valid(name)) {
+        return Err(format!("argument for --baseline must be a name, not a path (was {name})"));
+    }
+
+    Ok((save_baseline, baseline))
+}
+
 fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
     let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
     if !shuffle && allow_unstable {
@@ -382,6 +593,50 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn get_shard(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<usize>, Option<usize>)> {
+    let shard_count = match unstable_optopt!(matches, allow_unstable, "shard-count") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(0) => return Err("argument for --shard-count must not be 0".to_string()),
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shard-count must be a number > 0 \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    let shard_index = match unstable_optopt!(matches, allow_unstable, "shard-index") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shard-index must be a number \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    match (shard_index, shard_count) {
+        (Some(index), Some(count)) if index >= count => Err(format!(
+            "argument for --shard-index must be less than --shard-count \
+             (was {index}, with {count} shards)"
+        )),
+        (Some(_), None) => Err("--shard-index requires --shard-count".into()),
+        (None, Some(_)) if !matches.opt_present("list") => {
+            Err("--shard-count requires --shard-index, unless used with --list".into())
+        }
+        shard => Ok(shard),
+    }
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -421,9 +676,15 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit or tap (was \
                  {v})"
             ));
         }
@@ -5,14 +5,16 @@
 use std::io::prelude::Write;
 use std::time::Instant;
 
-use super::bench::fmt_bench_samples;
+use super::bench::{BenchSamples, fmt_bench_samples};
 use super::cli::TestOpts;
 use super::event::{CompletedTest, TestEvent};
 use super::formatters::{
-    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter,
+    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter, TerseFormatter,
 };
+use super::helpers::baseline::{Baseline, BenchChange};
 use super::helpers::concurrency::get_concurrency;
 use super::helpers::metrics::MetricMap;
+use super::helpers::shard::get_test_shards;
 use super::options::{Options, OutputFormat};
 use super::test_result::TestResult;
 use super::time::{TestExecTime, TestSuiteExecTime};
@@ -79,6 +81,7 @@ pub(crate) struct ConsoleTestState {
     pub total: usize,
     pub passed: usize,
     pub failed: usize,
+    pub flaky: usize,
     pub ignored: usize,
     pub filtered_out: usize,
     pub measured: usize,
@@ -88,6 +91,11 @@ pub(crate) struct ConsoleTestState {
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flaky_tests: Vec<(TestDesc, Vec<u8>)>,
+    /// Benchmark results to compare with, see `--baseline`
+    pub baseline: Option<Baseline>,
+    /// Benchmark results of this run
+    pub bench_results: Baseline,
     pub options: Options,
 }
 
@@ -97,12 +105,18 @@ pub(crate) fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let baseline = match opts.baseline {
+            Some(ref name) => Some(Baseline::load(name)?),
+            None => None,
+        };
+        let bench_results = Baseline::new(opts.save_baseline.as_deref().unwrap_or_default());
 
         Ok(ConsoleTestState {
             log_out,
             total: 0,
             passed: 0,
             failed: 0,
+            flaky: 0,
             ignored: 0,
             filtered_out: 0,
             measured: 0,
@@ -112,6 +126,9 @@ pub(crate) fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             not_failures: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            flaky_tests: Vec::new(),
+            baseline,
+            bench_results,
             options: opts.options,
         })
     }
@@ -154,6 +171,8 @@ pub(crate) fn write_log_result(
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimeout(limit) => format!("failed (timed out after {limit:?})"),
+                    TestResult::TrFlaky(failures) => format!("flaky (failed {failures} times)"),
                 },
                 name,
             )
@@ -164,8 +183,13 @@ pub(crate) fn write_log_result(
         self.write_log(|| "\n")
     }
 
+    /// Compares the result of a benchmark with the baseline, if any.
+    pub(crate) fn bench_change(&self, desc: &TestDesc, bs: &BenchSamples) -> Option<BenchChange> {
+        self.baseline.as_ref()?.compare(desc.name.as_slice(), &bs.ns_iter_summ)
+    }
+
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.failed + self.flaky + self.ignored + self.measured
     }
 }
 
@@ -177,7 +201,7 @@ pub(crate) fn list_tests_console(opts: &TestOpts, tests: TestList) -> io::Result
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
@@ -185,8 +209,17 @@ pub(crate) fn list_tests_console(opts: &TestOpts, tests: TestList) -> io::Result
     };
     let mut st = ConsoleTestDiscoveryState::new(opts)?;
 
+    let tests = filter_tests(opts, tests);
+    let shards = match (opts.shard_index, opts.shard_count) {
+        (Some(shard_index), _) => vec![Some(shard_index); tests.len()],
+        (None, Some(shard_count)) => {
+            get_test_shards(&tests, shard_count, opts.shuffle_seed).into_iter().map(Some).collect()
+        }
+        (None, None) => vec![None; tests.len()],
+    };
+
     out.write_discovery_start()?;
-    for test in filter_tests(opts, tests) {
+    for (test, shard) in tests.into_iter().zip(shards) {
         use crate::TestFn::*;
 
         let TestDescAndFn { desc, testfn } = test;
@@ -204,7 +237,7 @@ pub(crate) fn list_tests_console(opts: &TestOpts, tests: TestList) -> io::Result
 
         st.ignored += if desc.ignore { 1 } else { 0 };
 
-        out.write_test_discovered(&desc, fntype)?;
+        out.write_test_discovered(&desc, fntype, shard)?;
         st.write_log(|| format!("{fntype} {}\n", desc.name))?;
     }
 
@@ -230,6 +263,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.bench_results.insert(test.name.as_slice(), bs.ns_iter_summ);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -246,6 +280,18 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimeout(limit) => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            stdout.extend_from_slice(
+                format!("note: test did not finish within {limit:?}").as_bytes(),
+            );
+            st.failures.push((test, stdout));
+        }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flaky_tests.push((test, stdout));
+        }
     }
 }
 
@@ -302,6 +348,7 @@ pub(crate) fn get_formatter(opts: &TestOpts, max_name_len: usize) -> Box<dyn Out
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
     }
 }
 
@@ -330,7 +377,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: TestList) -> io::Result<bool> {
 
     assert!(opts.fail_fast || st.current_test_count() == st.total);
 
-    out.write_run_finish(&st)
+    let success = out.write_run_finish(&st)?;
+
+    // Don't replace a saved baseline with the results of a run without benchmarks.
+    if opts.save_baseline.is_some() && !st.bench_results.is_empty() {
+        st.bench_results.save()?;
+    }
+
+    Ok(success)
 }
 
 // Calculates padding for given test description.
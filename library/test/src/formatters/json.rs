@@ -3,10 +3,12 @@
 use std::io::prelude::Write;
 
 use super::OutputFormatter;
+use crate::bench::BENCH_SAMPLES;
 use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::options::ShouldPanic;
 use crate::test_result::TestResult;
 use crate::time;
-use crate::types::TestDesc;
+use crate::types::{TestDesc, TestType};
 
 pub(crate) struct JsonFormatter<T> {
     out: OutputLocation<T>,
@@ -61,7 +63,12 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         self.writeln_message(concat!(r#"{ "type": "suite", "event": "discovery" }"#, "\n"))
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        shard: Option<usize>,
+    ) -> io::Result<()> {
         let TestDesc {
             name,
             ignore,
@@ -71,16 +78,38 @@ fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Res
             start_col,
             end_line,
             end_col,
-            ..
+            body_end_line,
+            should_panic,
+            compile_fail,
+            no_run,
+            test_type: kind,
+            timeout,
         } = desc;
 
         let name = EscapedString(name.as_slice());
-        let ignore_message = ignore_message.unwrap_or("");
+        let ignore_message = EscapedString(ignore_message.unwrap_or(""));
         let source_path = EscapedString(source_file);
+        let (should_panic, should_panic_message) = match should_panic {
+            ShouldPanic::No => (false, String::from("null")),
+            ShouldPanic::Yes => (true, String::from("null")),
+            ShouldPanic::YesWithMessage(msg) => (true, format!(r#""{}""#, EscapedString(msg))),
+        };
+        let kind = match kind {
+            TestType::UnitTest => "unit",
+            TestType::IntegrationTest => "integration",
+            TestType::DocTest => "doctest",
+            TestType::Unknown => "unknown",
+        };
+        let timeout = match timeout {
+            Some(timeout) => timeout.as_secs_f64().to_string(),
+            None => String::from("null"),
+        };
+        let shard_json =
+            if let Some(shard) = shard { format!(r#", "shard": {shard}"#) } else { String::new() };
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col} }}{newline}"#
+            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col}, "body_end_line": {body_end_line}, "should_panic": {should_panic}, "should_panic_message": {should_panic_message}, "compile_fail": {compile_fail}, "no_run": {no_run}, "test_type": "{kind}", "timeout": {timeout}{shard_json} }}{newline}"#
         ))
     }
 
@@ -146,6 +175,24 @@ fn write_result(
                 Some(r#""reason": "time limit exceeded""#),
             ),
 
+            TestResult::TrTimeout(limit) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "failed",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""reason": "timeout", "timeout": {}"#, limit.as_secs_f64())),
+            ),
+
+            TestResult::TrFlaky(failures) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "flaky",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""failures": {failures}"#)),
+            ),
+
             TestResult::TrFailedMsg(ref m) => self.write_event(
                 "test",
                 desc.name.as_slice(),
@@ -169,19 +216,36 @@ fn write_result(
             TestResult::TrBench(ref bs) => {
                 let median = bs.ns_iter_summ.median;
                 let deviation = bs.ns_iter_summ.max - bs.ns_iter_summ.min;
+                let mean = bs.ns_iter_summ.mean;
+                let std_dev = bs.ns_iter_summ.std_dev;
+                let samples = BENCH_SAMPLES;
 
                 let mbps = if bs.mb_s == 0 {
                     String::new()
                 } else {
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
+                let baseline = match (&state.baseline, state.bench_change(desc, bs)) {
+                    (Some(baseline), Some(change)) => format!(
+                        r#", "baseline": "{}", "baseline_median": {}, "change_pct": {}, "p_value": {}, "verdict": "{}""#,
+                        EscapedString(&baseline.name),
+                        change.baseline_median,
+                        change.change_pct,
+                        change.p_value,
+                        change.verdict(),
+                    ),
+                    _ => String::new(),
+                };
                 let name = EscapedString(desc.name.as_slice());
 
                 self.writeln_message(&format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{name}\", \
                      \"median\": {median}, \
-                     \"deviation\": {deviation}{mbps} }}\n",
+                     \"deviation\": {deviation}, \
+                     \"mean\": {mean}, \
+                     \"std_dev\": {std_dev}, \
+                     \"samples\": {samples}{mbps}{baseline} }}\n",
                 ))
             }
         }
@@ -202,6 +266,8 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         let ignored = state.ignored;
         let measured = state.measured;
         let filtered_out = state.filtered_out;
+        let flaky_json =
+            if state.flaky > 0 { format!(r#", "flaky": {}"#, state.flaky) } else { String::new() };
         let exec_time_json = if let Some(ref exec_time) = state.exec_time {
             format!(r#", "exec_time": {}"#, exec_time.0.as_secs_f64())
         } else {
@@ -210,7 +276,7 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{flaky_json}{exec_time_json} }}{newline}"#
         ))?;
 
         Ok(state.failed == 0)
@@ -230,7 +296,7 @@ fn write_merged_doctests_times(
 
 /// A formatting utility used to print strings with characters in need of escaping.
 /// Base code taken form `libserialize::json::escape_str`
-struct EscapedString<S: AsRef<str>>(S);
+pub(crate) struct EscapedString<S: AsRef<str>>(pub(crate) S);
 
 impl<S: AsRef<str>> std::fmt::Display for EscapedString<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> ::std::fmt::Result {
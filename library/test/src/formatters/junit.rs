@@ -42,7 +42,12 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
     }
 
-    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        _desc: &TestDesc,
+        _test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
         Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
     }
 
@@ -146,6 +151,48 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                     self.write_message("</testcase>")?;
                 }
 
+                TestResult::TrTimeout(limit) => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    self.write_message(&format!(
+                        "<failure message=\"timed out after {limit:?}\" type=\"timeout\"/>"
+                    ))?;
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
+                TestResult::TrFlaky(failures) => {
+                    // Reported the way Maven Surefire reports reruns of flaky tests.
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    let attempts = if failures == 1 { "attempt" } else { "attempts" };
+                    self.write_message(&format!(
+                        "<flakyFailure message=\"passed after {failures} failed {attempts}\" \
+                         type=\"flaky\">"
+                    ))?;
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</flakyFailure>")?;
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrBench(ref b) => {
                     self.write_message(&format!(
                         "<testcase classname=\"benchmark::{}\" \
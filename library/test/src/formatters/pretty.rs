@@ -1,9 +1,11 @@
 use std::io;
 use std::io::prelude::Write;
+use std::time::Duration;
 
 use super::OutputFormatter;
 use crate::bench::fmt_bench_samples;
 use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::helpers::baseline::{BenchChange, Verdict};
 use crate::test_result::TestResult;
 use crate::types::TestDesc;
 use crate::{term, time};
@@ -55,10 +57,34 @@ pub(crate) fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub(crate) fn write_timed_out(&mut self, limit: Duration) -> io::Result<()> {
+        self.write_short_result(&format!("FAILED (timed out after {limit:?})"), term::color::RED)
+    }
+
+    pub(crate) fn write_flaky(&mut self, failures: usize) -> io::Result<()> {
+        let attempts = if failures == 1 { "attempt" } else { "attempts" };
+        self.write_short_result(
+            &format!("ok (flaky, {failures} failed {attempts})"),
+            term::color::YELLOW,
+        )
+    }
+
     pub(crate) fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
 
+    pub(crate) fn write_bench_change(&mut self, change: &BenchChange) -> io::Result<()> {
+        self.write_plain(format!(
+            "; change: {:+.2}% (p = {:.2}) ",
+            change.change_pct, change.p_value
+        ))?;
+        match change.verdict() {
+            Verdict::Regressed => self.write_pretty("regressed", term::color::RED),
+            Verdict::Improved => self.write_pretty("improved", term::color::GREEN),
+            Verdict::NoChange => self.write_plain("no change"),
+        }
+    }
+
     pub(crate) fn write_short_result(
         &mut self,
         result: &str,
@@ -166,6 +192,10 @@ pub(crate) fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Re
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub(crate) fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_results(&state.flaky_tests, "flaky tests")
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -183,8 +213,17 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         Ok(())
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
-        self.write_plain(format!("{}: {test_type}\n", desc.name))
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        shard: Option<usize>,
+    ) -> io::Result<()> {
+        if let Some(shard) = shard {
+            self.write_plain(format!("{}: {test_type} (shard {shard})\n", desc.name))
+        } else {
+            self.write_plain(format!("{}: {test_type}\n", desc.name))
+        }
     }
 
     fn write_discovery_finish(&mut self, state: &ConsoleTestDiscoveryState) -> io::Result<()> {
@@ -234,7 +273,7 @@ fn write_result(
         result: &TestResult,
         exec_time: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         if self.is_multithreaded {
             self.write_test_name(desc)?;
@@ -247,8 +286,13 @@ fn write_result(
             TestResult::TrBench(ref bs) => {
                 self.write_bench()?;
                 self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(change) = state.bench_change(desc, bs) {
+                    self.write_bench_change(&change)?;
+                }
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrTimeout(limit) => self.write_timed_out(limit)?,
+            TestResult::TrFlaky(failures) => self.write_flaky(failures)?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -267,6 +311,9 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
         let success = state.failed == 0;
         if !success {
             if !state.failures.is_empty() {
@@ -294,6 +341,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_plain(s)?;
 
+        if state.flaky > 0 {
+            self.write_plain(format!("; {} flaky", state.flaky))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::io::prelude::Write;
+
+use super::OutputFormatter;
+use super::json::EscapedString;
+use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::test_result::TestResult;
+use crate::time;
+use crate::types::{TestDesc, TestType};
+
+/// Writes results in the [Test Anything Protocol](https://testanything.org/tap-version-14-specification.html),
+/// version 14.
+///
+/// Doctests are grouped into one subtest per source file. As results come in
+/// out of order, the subtests are written once the whole run has finished.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    /// Number of the last top-level test point
+    test_number: usize,
+    /// Finished doctests, by the file they were extracted from
+    doctests: BTreeMap<String, Vec<TapResult>>,
+}
+
+struct TapResult {
+    desc: TestDesc,
+    result: TestResult,
+    exec_time: Option<time::TestExecTime>,
+    stdout: Vec<u8>,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub(crate) fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0, doctests: BTreeMap::new() }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_line(&mut self, indent: &str, s: &str) -> io::Result<()> {
+        self.out.write_all(format!("{indent}{s}\n").as_bytes())
+    }
+
+    /// Writes a single test point, followed by a YAML diagnostic block if
+    /// there is anything to report. Returns whether the test point was `ok`.
+    fn write_test_point(
+        &mut self,
+        indent: &str,
+        number: usize,
+        description: &str,
+        test: &TapResult,
+        display_output: bool,
+    ) -> io::Result<bool> {
+        let TapResult { desc, result, exec_time, stdout } = test;
+        let description = escape_description(description);
+
+        if let TestResult::TrIgnored = result {
+            // Ignored tests are `ok`, with the reason given in the directive.
+            let reason = desc.ignore_message.map(escape_description).unwrap_or_default();
+            let directive = if reason.is_empty() { String::new() } else { format!(" {reason}") };
+            self.write_line(indent, &format!("ok {number} - {description} # SKIP{directive}"))?;
+            return Ok(true);
+        }
+
+        let mut diagnostics = Vec::new();
+        let ok = match result {
+            TestResult::TrOk | TestResult::TrIgnored => true,
+            TestResult::TrBench(bs) => {
+                diagnostics.push(format!("median: {}", bs.ns_iter_summ.median));
+                diagnostics
+                    .push(format!("deviation: {}", bs.ns_iter_summ.max - bs.ns_iter_summ.min));
+                if bs.mb_s != 0 {
+                    diagnostics.push(format!("mib_per_second: {}", bs.mb_s));
+                }
+                true
+            }
+            TestResult::TrFlaky(failures) => {
+                diagnostics.push(format!("failed_attempts: {failures}"));
+                true
+            }
+            TestResult::TrFailed => {
+                if let Some(message) = panic_message(stdout) {
+                    diagnostics.push(format!(r#"message: "{}""#, EscapedString(message)));
+                }
+                false
+            }
+            TestResult::TrFailedMsg(message) => {
+                diagnostics.push(format!(r#"message: "{}""#, EscapedString(message)));
+                false
+            }
+            TestResult::TrTimedFail => {
+                diagnostics.push(r#"message: "time limit exceeded""#.to_owned());
+                false
+            }
+            TestResult::TrTimeout(limit) => {
+                diagnostics.push(format!(r#"message: "timed out after {limit:?}""#));
+                false
+            }
+        };
+
+        if !ok {
+            diagnostics.push("severity: fail".to_owned());
+            if !desc.source_file.is_empty() {
+                diagnostics.push(format!(
+                    r#"at: {{ file: "{}", line: {} }}"#,
+                    EscapedString(desc.source_file),
+                    desc.start_line
+                ));
+            }
+        }
+        if let Some(exec_time) = exec_time {
+            diagnostics.push(format!("duration_ms: {}", exec_time.0.as_secs_f64() * 1000.0));
+        }
+        // The output of a flaky test is that of its last failed attempt.
+        let flaky = matches!(result, TestResult::TrFlaky(_));
+        if (!ok || flaky || display_output) && !stdout.is_empty() {
+            diagnostics
+                .push(format!(r#"stdout: "{}""#, EscapedString(String::from_utf8_lossy(stdout))));
+        }
+
+        let status = if ok { "ok" } else { "not ok" };
+        self.write_line(indent, &format!("{status} {number} - {description}"))?;
+        if !diagnostics.is_empty() {
+            let yaml_indent = format!("{indent}  ");
+            self.write_line(&yaml_indent, "---")?;
+            for line in &diagnostics {
+                self.write_line(&yaml_indent, line)?;
+            }
+            self.write_line(&yaml_indent, "...")?;
+        }
+
+        Ok(ok)
+    }
+
+    /// Writes the doctests of one source file as a subtest, followed by the
+    /// top-level test point that summarizes it.
+    fn write_doctest_subtest(
+        &mut self,
+        file: &str,
+        mut tests: Vec<TapResult>,
+        display_output: bool,
+    ) -> io::Result<()> {
+        tests.sort_by(|a, b| a.desc.name.as_slice().cmp(b.desc.name.as_slice()));
+
+        self.write_line("", &format!("# Subtest: {file}"))?;
+        let mut all_ok = true;
+        for (i, test) in tests.iter().enumerate() {
+            // Doctest names look like `src/lib.rs - module::item (line 10)`.
+            let name = test.desc.name.as_slice();
+            let name = name.split_once(" - ").map_or(name, |(_, item)| item);
+            all_ok &= self.write_test_point("    ", i + 1, name, test, display_output)?;
+        }
+        self.write_line("    ", &format!("1..{}", tests.len()))?;
+
+        self.test_number += 1;
+        let status = if all_ok { "ok" } else { "not ok" };
+        self.write_line(
+            "",
+            &format!("{status} {} - {}", self.test_number, escape_description(file)),
+        )
+    }
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_test_discovered(
+        &mut self,
+        _desc: &TestDesc,
+        _test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::NotFound, "not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        // The plan is written at the end, since doctests are grouped into
+        // subtests and the number of top-level test points isn't known yet.
+        self.write_line("", "TAP version 14")?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.write_line("", &format!("# shuffle seed: {shuffle_seed}"))?;
+        }
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_line(
+            "",
+            &format!(
+                "# test {} has been running for over {} seconds",
+                desc.name,
+                time::TEST_WARN_TIMEOUT_S
+            ),
+        )
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        let test = TapResult {
+            desc: desc.clone(),
+            result: result.clone(),
+            exec_time: exec_time.cloned(),
+            stdout: stdout.to_vec(),
+        };
+
+        if desc.test_type == TestType::DocTest {
+            let name = desc.name.as_slice();
+            let file = match name.split_once(" - ") {
+                Some((file, _)) => file.trim(),
+                None => desc.source_file,
+            };
+            self.doctests.entry(file.to_owned()).or_default().push(test);
+            return Ok(());
+        }
+
+        self.test_number += 1;
+        self.write_test_point(
+            "",
+            self.test_number,
+            desc.name.as_slice(),
+            &test,
+            state.options.display_output,
+        )?;
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        for (file, tests) in std::mem::take(&mut self.doctests) {
+            self.write_doctest_subtest(&file, tests, state.options.display_output)?;
+        }
+
+        self.write_line("", &format!("1..{}", self.test_number))?;
+        self.write_line(
+            "",
+            &format!(
+                "# {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+            ),
+        )?;
+        if state.flaky > 0 {
+            self.write_line("", &format!("# {} flaky", state.flaky))?;
+        }
+
+        Ok(state.failed == 0)
+    }
+
+    fn write_merged_doctests_times(
+        &mut self,
+        total_time: f64,
+        compilation_time: f64,
+    ) -> io::Result<()> {
+        self.write_line(
+            "",
+            &format!(
+                "# all doctests ran in {total_time:.2}s; merged doctests compilation took {compilation_time:.2}s",
+            ),
+        )
+    }
+}
+
+/// Escapes the characters that have a special meaning in a test point
+/// description.
+fn escape_description(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('#', "\\#")
+}
+
+/// Extracts the message of the last panic from the captured output of a
+/// failed test, i.e. the lines after `thread '...' panicked at ...:`.
+fn panic_message(stdout: &[u8]) -> Option<String> {
+    let stdout = String::from_utf8_lossy(stdout);
+    let start = stdout.rfind("' panicked at ")?;
+    let message = stdout[start..].split_once('\n')?.1;
+    let message: Vec<&str> =
+        message.lines().take_while(|line| !line.starts_with("note: ")).collect();
+    Some(message.join("\n").trim_end().to_owned())
+}
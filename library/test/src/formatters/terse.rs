@@ -4,6 +4,7 @@
 use super::OutputFormatter;
 use crate::bench::fmt_bench_samples;
 use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::helpers::baseline::{BenchChange, Verdict};
 use crate::test_result::TestResult;
 use crate::types::{NamePadding, TestDesc};
 use crate::{term, time};
@@ -66,10 +67,26 @@ pub(crate) fn write_ignored(&mut self) -> io::Result<()> {
         self.write_short_result("i", term::color::YELLOW)
     }
 
+    pub(crate) fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("f", term::color::YELLOW)
+    }
+
     pub(crate) fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
 
+    pub(crate) fn write_bench_change(&mut self, change: &BenchChange) -> io::Result<()> {
+        self.write_plain(format!(
+            "; change: {:+.2}% (p = {:.2}) ",
+            change.change_pct, change.p_value
+        ))?;
+        match change.verdict() {
+            Verdict::Regressed => self.write_pretty("regressed", term::color::RED),
+            Verdict::Improved => self.write_pretty("improved", term::color::GREEN),
+            Verdict::NoChange => self.write_plain("no change"),
+        }
+    }
+
     pub(crate) fn write_short_result(
         &mut self,
         result: &str,
@@ -172,6 +189,16 @@ pub(crate) fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<
         Ok(())
     }
 
+    pub(crate) fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests:\n")?;
+        let mut flaky: Vec<_> = state.flaky_tests.iter().map(|(f, _)| f.name.to_string()).collect();
+        flaky.sort();
+        for name in &flaky {
+            self.write_plain(&format!("    {name}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -189,8 +216,17 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         Ok(())
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
-        self.write_plain(format!("{}: {test_type}\n", desc.name))
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        shard: Option<usize>,
+    ) -> io::Result<()> {
+        if let Some(shard) = shard {
+            self.write_plain(format!("{}: {test_type} (shard {shard})\n", desc.name))
+        } else {
+            self.write_plain(format!("{}: {test_type}\n", desc.name))
+        }
     }
 
     fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
@@ -226,20 +262,26 @@ fn write_result(
         result: &TestResult,
         _: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         match *result {
             TestResult::TrOk => self.write_ok(),
-            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
-                self.write_failed(desc.name.as_slice())
-            }
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimeout(_) => self.write_failed(desc.name.as_slice()),
             TestResult::TrIgnored => self.write_ignored(),
+            TestResult::TrFlaky(_) => self.write_flaky(),
             TestResult::TrBench(ref bs) => {
                 if self.is_multithreaded {
                     self.write_test_name(desc)?;
                 }
                 self.write_bench()?;
-                self.write_plain(format!(": {}\n", fmt_bench_samples(bs)))
+                self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(change) = state.bench_change(desc, bs) {
+                    self.write_bench_change(&change)?;
+                }
+                self.write_plain("\n")
             }
         }
     }
@@ -256,6 +298,9 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
         let success = state.failed == 0;
         if !success {
             self.write_failures(state)?;
@@ -277,6 +322,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_plain(s)?;
 
+        if state.flaky > 0 {
+            self.write_plain(format!("; {} flaky", state.flaky))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
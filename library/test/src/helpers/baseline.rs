@@ -0,0 +1,353 @@
+//! Saving benchmark results as a named baseline, and comparing later
+//! results against it.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::{env, fmt, fs, io};
+
+use crate::bench::BENCH_SAMPLES;
+use crate::formatters::EscapedString;
+use crate::stats::{Summary, welch_t_test};
+
+/// Changes with a p-value below this are considered significant.
+pub(crate) const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Environment variable with the directory baselines are stored in.
+const BASELINE_DIR_VAR: &str = "RUST_TEST_BASELINE_DIR";
+const DEFAULT_BASELINE_DIR: &str = "target/libtest-baselines";
+
+/// Version of the file format, written so that it can be changed later.
+const FORMAT_VERSION: u32 = 1;
+
+/// Benchmark summaries by benchmark name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Baseline {
+    pub name: String,
+    benchmarks: BTreeMap<String, BaselineEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BaselineEntry {
+    summary: Summary,
+    /// Number of samples the summary was computed from
+    samples: usize,
+}
+
+/// How a benchmark result compares to the one in a baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BenchChange {
+    pub baseline_median: f64,
+    /// Change of the median in percent, positive if the benchmark got slower
+    pub change_pct: f64,
+    /// Probability of a difference in means this large occurring by chance
+    pub p_value: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+impl BenchChange {
+    pub(crate) fn verdict(&self) -> Verdict {
+        if self.p_value >= SIGNIFICANCE_LEVEL {
+            Verdict::NoChange
+        } else if self.change_pct > 0.0 {
+            Verdict::Regressed
+        } else {
+            Verdict::Improved
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Improved => "improved",
+            Verdict::Regressed => "regressed",
+            Verdict::NoChange => "no change",
+        })
+    }
+}
+
+impl Baseline {
+    pub(crate) fn new(name: &str) -> Baseline {
+        Baseline { name: name.to_owned(), benchmarks: BTreeMap::new() }
+    }
+
+    /// Records the result of a benchmark run by `bench::iter`.
+    pub(crate) fn insert(&mut self, name: &str, summary: Summary) {
+        self.benchmarks.insert(name.to_owned(), BaselineEntry { summary, samples: BENCH_SAMPLES });
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.benchmarks.is_empty()
+    }
+
+    /// Compares the result of a benchmark run by `bench::iter` with the
+    /// result in this baseline, if it has one.
+    pub(crate) fn compare(&self, name: &str, summary: &Summary) -> Option<BenchChange> {
+        let old = self.benchmarks.get(name)?;
+        if !(old.summary.median > 0.0) {
+            return None;
+        }
+        let change_pct = (summary.median - old.summary.median) / old.summary.median * 100.0;
+        let p_value = welch_t_test(summary, BENCH_SAMPLES, &old.summary, old.samples);
+        Some(BenchChange { baseline_median: old.summary.median, change_pct, p_value })
+    }
+
+    /// Returns the file the baseline called `name` is stored in.
+    pub(crate) fn path(name: &str) -> PathBuf {
+        let dir = env::var_os(BASELINE_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINE_DIR));
+        dir.join(format!("{name}.json"))
+    }
+
+    pub(crate) fn load(name: &str) -> io::Result<Baseline> {
+        let path = Baseline::path(name);
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(e.kind(), format!("failed to read baseline `{}`: {e}", path.display()))
+        })?;
+        Baseline::from_json(name, &contents).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("baseline `{}` is not a valid baseline file", path.display()),
+            )
+        })
+    }
+
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let path = Baseline::path(&self.name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        fn number(n: f64) -> String {
+            // JSON has no representation of NaN or infinity.
+            if n.is_finite() { n.to_string() } else { "null".to_owned() }
+        }
+
+        let mut json = format!("{{\n  \"version\": {FORMAT_VERSION},\n  \"benchmarks\": {{");
+        for (i, (name, entry)) in self.benchmarks.iter().enumerate() {
+            let BaselineEntry { summary: s, samples } = entry;
+            let separator = if i == 0 { "" } else { "," };
+            let (q1, q2, q3) = s.quartiles;
+            write!(
+                json,
+                "{separator}\n    \"{}\": {{ \"samples\": {samples}, \"sum\": {}, \"min\": {}, \
+                 \"max\": {}, \"mean\": {}, \"median\": {}, \"var\": {}, \"std_dev\": {}, \
+                 \"std_dev_pct\": {}, \"median_abs_dev\": {}, \"median_abs_dev_pct\": {}, \
+                 \"quartiles\": [{}, {}, {}], \"iqr\": {} }}",
+                EscapedString(name),
+                number(s.sum),
+                number(s.min),
+                number(s.max),
+                number(s.mean),
+                number(s.median),
+                number(s.var),
+                number(s.std_dev),
+                number(s.std_dev_pct),
+                number(s.median_abs_dev),
+                number(s.median_abs_dev_pct),
+                number(q1),
+                number(q2),
+                number(q3),
+                number(s.iqr),
+            )
+            .unwrap();
+        }
+        json.push_str("\n  }\n}\n");
+        json
+    }
+
+    pub(crate) fn from_json(name: &str, json: &str) -> Option<Baseline> {
+        let mut parser = Parser { input: json.as_bytes(), pos: 0 };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return None;
+        }
+
+        if value.get("version")?.as_f64()? != FORMAT_VERSION as f64 {
+            return None;
+        }
+        let mut baseline = Baseline::new(name);
+        let Value::Object(benchmarks) = value.get("benchmarks")? else { return None };
+        for (bench_name, entry) in benchmarks {
+            let field = |key| entry.get(key)?.as_f64();
+            let Value::Array(quartiles) = entry.get("quartiles")? else { return None };
+            let [q1, q2, q3] = quartiles.as_slice() else { return None };
+            let summary = Summary {
+                sum: field("sum")?,
+                min: field("min")?,
+                max: field("max")?,
+                mean: field("mean")?,
+                median: field("median")?,
+                var: field("var")?,
+                std_dev: field("std_dev")?,
+                std_dev_pct: field("std_dev_pct")?,
+                median_abs_dev: field("median_abs_dev")?,
+                median_abs_dev_pct: field("median_abs_dev_pct")?,
+                quartiles: (q1.as_f64()?, q2.as_f64()?, q3.as_f64()?),
+                iqr: field("iqr")?,
+            };
+            let samples = field("samples")?;
+            if !(samples >= 0.0) {
+                return None;
+            }
+            baseline
+                .benchmarks
+                .insert(bench_name.clone(), BaselineEntry { summary, samples: samples as usize });
+        }
+        Some(baseline)
+    }
+}
+
+/// The subset of JSON values that baseline files are made of.
+enum Value {
+    Null,
+    Number(f64),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Number(n) => Some(n),
+            Value::Null => Some(f64::NAN),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.input.get(self.pos) == Some(&byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn parse_value(&mut self) -> Option<Value> {
+        self.skip_whitespace();
+        match *self.input.get(self.pos)? {
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if self.eat(b'}') {
+                    return Some(Value::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.parse_string()?;
+                    if !self.eat(b':') {
+                        return None;
+                    }
+                    fields.push((key, self.parse_value()?));
+                    if self.eat(b'}') {
+                        return Some(Value::Object(fields));
+                    }
+                    if !self.eat(b',') {
+                        return None;
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                if self.eat(b']') {
+                    return Some(Value::Array(values));
+                }
+                loop {
+                    values.push(self.parse_value()?);
+                    if self.eat(b']') {
+                        return Some(Value::Array(values));
+                    }
+                    if !self.eat(b',') {
+                        return None;
+                    }
+                }
+            }
+            b'n' if self.input[self.pos..].starts_with(b"null") => {
+                self.pos += 4;
+                Some(Value::Null)
+            }
+            _ => {
+                let start = self.pos;
+                while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') =
+                    self.input.get(self.pos)
+                {
+                    self.pos += 1;
+                }
+                let number = str::from_utf8(&self.input[start..self.pos]).ok()?;
+                number.parse().ok().map(Value::Number)
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Option<String> {
+        if self.input.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = *self.input.get(self.pos)?;
+            self.pos += 1;
+            match byte {
+                b'"' => return String::from_utf8(bytes).ok(),
+                b'\\' => {
+                    let escaped = *self.input.get(self.pos)?;
+                    self.pos += 1;
+                    let c = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let hex = self.input.get(self.pos..self.pos + 4)?;
+                            self.pos += 4;
+                            let code = u32::from_str_radix(str::from_utf8(hex).ok()?, 16).ok()?;
+                            char::from_u32(code)?
+                        }
+                        _ => return None,
+                    };
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Helper module which splits the tests between several test processes
+//! (`--shard-index` and `--shard-count`).
+
+use super::shuffle::calculate_hash;
+use crate::types::TestDescAndFn;
+
+/// Returns the shard owning each of `tests`, in the same order.
+///
+/// Tests are ordered by a hash of their name, salted with the shuffle seed if
+/// one was given, and then dealt out to the shards in turn. This keeps the
+/// shards balanced to within one test, and gives the same assignment in every
+/// process that sees the same list of tests.
+pub(crate) fn get_test_shards(
+    tests: &[TestDescAndFn],
+    shard_count: usize,
+    shuffle_seed: Option<u64>,
+) -> Vec<usize> {
+    let mut order: Vec<(u64, &str, usize)> = tests
+        .iter()
+        .enumerate()
+        .map(|(i, test)| {
+            // Hash the plain name, so that padding doesn't change the result.
+            let name = test.desc.name.as_slice();
+            (calculate_hash(&(shuffle_seed, name)), name, i)
+        })
+        .collect();
+    order.sort_unstable();
+
+    let mut shards = vec![0; tests.len()];
+    for (position, &(_, _, i)) in order.iter().enumerate() {
+        shards[i] = position % shard_count;
+    }
+    shards
+}
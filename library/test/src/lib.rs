@@ -19,6 +19,7 @@
 #![doc(rust_logo)]
 #![feature(rustdoc_internals)]
 #![feature(file_buffered)]
+#![feature(float_gamma)]
 #![feature(internal_output_capture)]
 #![feature(io_const_error)]
 #![feature(staged_api)]
@@ -55,7 +56,7 @@ pub mod test {
 }
 
 use std::collections::VecDeque;
-use std::io::prelude::Write;
+use std::io::prelude::{Read, Write};
 use std::mem::ManuallyDrop;
 use std::panic::{self, AssertUnwindSafe, PanicHookInfo, catch_unwind};
 use std::process::{self, Command, Termination};
@@ -84,6 +85,7 @@ pub mod test {
 
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
+use helpers::shard::get_test_shards;
 use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::RunStrategy;
 use test_result::*;
@@ -117,6 +119,16 @@ fn test_main_inner<F: FnOnce()>(
     options: Option<Options>,
     exit_callback: F,
 ) {
+    // If we're being run as a test process spawned by `--isolate`, run the
+    // test here. This will then exit the process.
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        unsafe {
+            env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        }
+        let panic_abort = options.is_some_and(|options| options.panic_abort);
+        run_test_in_secondary_process(&name, tests.tests, panic_abort);
+    }
+
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -128,6 +140,13 @@ fn test_main_inner<F: FnOnce()>(
     if let Some(options) = options {
         opts.options = options;
     }
+    let is_dynamic = |test: &TestDescAndFn| {
+        matches!(test.testfn, DynTestFn(..) | DynBenchFn(..) | DynBenchAsTestFn(..))
+    };
+    if opts.isolate && tests.tests.iter().any(is_dynamic) {
+        eprintln!("error: --isolate can only run tests defined with `#[test]`");
+        process::exit(ERROR_EXIT_CODE);
+    }
     if opts.list {
         if let Err(e) = console::list_tests_console(&opts, tests) {
             eprintln!("error: io error when listing tests: {e:?}");
@@ -211,32 +230,8 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
             env::remove_var(SECONDARY_TEST_INVOKER_VAR);
         }
 
-        // Convert benchmarks to tests if we're not benchmarking.
-        let mut tests = tests.iter().map(make_owned_test).collect::<Vec<_>>();
-        if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
-            unsafe {
-                env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
-            }
-        } else {
-            tests = convert_benchmarks_to_tests(tests);
-        };
-
-        let test = tests
-            .into_iter()
-            .find(|test| test.desc.name.as_slice() == name)
-            .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
-        let TestDescAndFn { desc, testfn } = test;
-        match testfn.into_runnable() {
-            Runnable::Test(runnable_test) => {
-                if runnable_test.is_dynamic() {
-                    panic!("only static tests are supported");
-                }
-                run_test_in_spawned_subprocess(desc, runnable_test);
-            }
-            Runnable::Bench(_) => {
-                panic!("benchmarks should not be executed into child processes")
-            }
-        }
+        let tests = tests.iter().map(make_owned_test).collect::<Vec<_>>();
+        run_test_in_secondary_process(&name, tests, true);
     }
 
     let args = env::args().collect::<Vec<_>>();
@@ -246,6 +241,44 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
     test_main_inner(&args, tests, Some(Options::new().panic_abort(true)), || {})
 }
 
+/// Runs the test called `name` in this process, which was spawned for it by
+/// `spawn_test_subprocess`, and exits with the result.
+fn run_test_in_secondary_process(
+    name: &str,
+    mut tests: Vec<TestDescAndFn>,
+    panic_abort: bool,
+) -> ! {
+    // Convert benchmarks to tests if we're not benchmarking.
+    if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
+        unsafe {
+            env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
+        }
+    } else {
+        tests = convert_benchmarks_to_tests(tests);
+    };
+
+    let test = tests
+        .into_iter()
+        .find(|test| test.desc.name.as_slice() == name)
+        .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
+    let TestDescAndFn { desc, testfn } = test;
+    match testfn.into_runnable() {
+        Runnable::Test(runnable_test) => {
+            if runnable_test.is_dynamic() {
+                panic!("only static tests are supported");
+            }
+            if panic_abort {
+                run_test_in_spawned_subprocess(desc, runnable_test)
+            } else {
+                run_test_in_isolated_subprocess(desc, runnable_test)
+            }
+        }
+        Runnable::Bench(_) => {
+            panic!("benchmarks should not be executed into child processes")
+        }
+    }
+}
+
 /// Clones static values for putting into a dynamic vector, which test_main()
 /// needs to hand out ownership of tests to parallel test runners.
 ///
@@ -348,6 +381,24 @@ struct TimeoutEntry {
         timeout: Instant,
     }
 
+    // An in-process test that is abandoned if it is still running at `deadline`.
+    struct HardTimeoutEntry {
+        id: TestId,
+        desc: TestDesc,
+        limit: Duration,
+        deadline: Instant,
+    }
+
+    // A test that may be run again if it fails, see `--retry`.
+    struct RetryEntry {
+        testfn: TestFn,
+        failures: usize,
+        // Output of the last failed attempt
+        stdout: Vec<u8>,
+    }
+
+    type RetryMap = HashMap<TestId, RetryEntry, BuildHasherDefault<DefaultHasher>>;
+
     let tests_len = tests.tests.len();
 
     let mut filtered = FilteredTests { tests: Vec::new(), benches: Vec::new(), next_id: 0 };
@@ -392,14 +443,95 @@ struct TimeoutEntry {
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if opts.isolate || (opts.options.panic_abort && !opts.force_run_in_process) {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
     };
 
+    // Tests running in a subprocess are killed by `spawn_test_subprocess` once
+    // they exceed their hard timeout, so only in-process tests are tracked here.
+    let in_process_hard_timeout = |desc: &TestDesc| match run_strategy {
+        RunStrategy::InProcess => time::get_hard_timeout(desc, opts.test_timeout),
+        RunStrategy::SpawnPrimary => None,
+    };
+    // Whether any test thread was left running after exceeding its hard timeout.
+    let mut abandoned_tests = false;
+
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut hard_timeouts: Vec<HardTimeoutEntry> = Vec::new();
+    let mut retries: RetryMap = HashMap::default();
+
+    // Keeps a copy of the test function, so that the test can be run again
+    // if it fails. Returns whether this is a retry of a failed test.
+    fn track_retries(
+        opts: &TestOpts,
+        retries: &mut RetryMap,
+        id: TestId,
+        test: &TestDescAndFn,
+    ) -> bool {
+        if let Some(entry) = retries.get(&id) {
+            return entry.failures > 0;
+        }
+        if opts.retry > 0 {
+            if let Some(testfn) = test.testfn.try_clone() {
+                retries.insert(id, RetryEntry { testfn, failures: 0, stdout: Vec::new() });
+            }
+        }
+        false
+    }
+
+    // Returns the test to run again if it failed and has attempts left, and
+    // reports a test that passed after failing as flaky.
+    fn check_retry(
+        opts: &TestOpts,
+        run_strategy: RunStrategy,
+        retries: &mut RetryMap,
+        completed_test: &mut CompletedTest,
+    ) -> Option<TestDescAndFn> {
+        let Some(entry) = retries.get_mut(&completed_test.id) else {
+            // Only tests whose function can be copied are tracked, see `track_retries`.
+            // Say so when a failed test can't be retried, rather than silently
+            // reporting it as failed.
+            if opts.retry > 0
+                && matches!(
+                    completed_test.result,
+                    TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimeout(_)
+                )
+            {
+                let stdout = &mut completed_test.stdout;
+                if stdout.last().is_some_and(|&b| b != b'\n') {
+                    stdout.push(b'\n');
+                }
+                stdout.extend_from_slice(
+                    b"note: not retried, only tests defined with `#[test]` can be run again\n",
+                );
+            }
+            return None;
+        };
+        match completed_test.result {
+            TrOk if entry.failures > 0 => {
+                completed_test.result = TrFlaky(entry.failures);
+                completed_test.stdout = std::mem::take(&mut entry.stdout);
+                None
+            }
+            // The thread of an abandoned test is still running, don't start another one.
+            TrTimeout(_) if matches!(run_strategy, RunStrategy::InProcess) => None,
+            TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimeout(_)
+                if entry.failures < opts.retry =>
+            {
+                entry.failures += 1;
+                entry.stdout = std::mem::take(&mut completed_test.stdout);
+                if let TrFailedMsg(msg) = &completed_test.result {
+                    entry.stdout.extend_from_slice(format!("note: {msg}\n").as_bytes());
+                }
+                let testfn = entry.testfn.try_clone()?;
+                Some(TestDescAndFn { desc: completed_test.desc.clone(), testfn })
+            }
+            _ => None,
+        }
+    }
 
     fn get_timed_out_tests(
         running_tests: &TestMap,
@@ -419,26 +551,91 @@ fn get_timed_out_tests(
         timed_out
     }
 
-    fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
-        timeout_queue.front().map(|&TimeoutEntry { timeout: next_timeout, .. }| {
-            let now = Instant::now();
-            if next_timeout >= now { next_timeout - now } else { Duration::new(0, 0) }
-        })
+    // Removes the tests whose hard timeout has expired from `running_tests`,
+    // leaving their threads running detached, and reports them as timed out.
+    fn get_hard_timed_out_tests(
+        running_tests: &mut TestMap,
+        hard_timeouts: &mut Vec<HardTimeoutEntry>,
+    ) -> Vec<CompletedTest> {
+        let now = Instant::now();
+        let mut timed_out = Vec::new();
+        hard_timeouts.retain(|entry| {
+            if now < entry.deadline {
+                return true;
+            }
+            running_tests.remove(&entry.id);
+            timed_out.push(CompletedTest::new(
+                entry.id,
+                entry.desc.clone(),
+                TrTimeout(entry.limit),
+                None,
+                Vec::new(),
+            ));
+            false
+        });
+        timed_out
+    }
+
+    fn calc_timeout(
+        timeout_queue: &VecDeque<TimeoutEntry>,
+        hard_timeouts: &[HardTimeoutEntry],
+    ) -> Option<Duration> {
+        let next_warn_timeout = timeout_queue.front().map(|entry| entry.timeout);
+        let next_hard_timeout = hard_timeouts.iter().map(|entry| entry.deadline).min();
+        let next_timeout = match (next_warn_timeout, next_hard_timeout) {
+            (Some(warn), Some(hard)) => Some(warn.min(hard)),
+            (warn, hard) => warn.or(hard),
+        };
+        next_timeout.map(|next_timeout| next_timeout.saturating_duration_since(Instant::now()))
     }
 
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            // A retry continues the line of the failed attempt.
+            if !track_retries(opts, &mut retries, id, &test) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
+            let hard_timeout = in_process_hard_timeout(&test.desc);
+            let desc = test.desc.clone();
             let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+            let deadline = hard_timeout.map(|limit| Instant::now() + limit);
             // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+            let mut completed_test = loop {
+                let res = match deadline {
+                    Some(deadline) => {
+                        rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    }
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match res {
+                    Ok(mut completed_test) if completed_test.id == id => {
+                        RunningTest { join_handle }.join(&mut completed_test);
+                        break completed_test;
+                    }
+                    // A late result from a test that was abandoned after timing out.
+                    Ok(_) => {}
+                    Err(RecvTimeoutError::Timeout) => {
+                        // There is no way to stop the test thread, so leave it running.
+                        abandoned_tests = true;
+                        let limit = hard_timeout.unwrap();
+                        break CompletedTest::new(id, desc, TrTimeout(limit), None, Vec::new());
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        unreachable!("`run_tests` holds a sender for the whole run")
+                    }
+                }
+            };
+
+            if let Some(test) = check_retry(opts, run_strategy, &mut retries, &mut completed_test) {
+                remaining.push_front((id, test));
+                continue;
+            }
 
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                TrIgnored | TrOk | TrBench(_) | TrFlaky(_) => false,
+                TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimeout(_) => opts.fail_fast,
             };
 
             let event = TestEvent::TeResult(completed_test);
@@ -455,18 +652,36 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                let is_retry = track_retries(opts, &mut retries, id, &test);
+                if !is_retry {
+                    let event = TestEvent::TeWait(desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
+                let hard_timeout = in_process_hard_timeout(&desc);
                 let join_handle =
                     run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
                 running_tests.insert(id, RunningTest { join_handle });
+                if let Some(limit) = hard_timeout {
+                    let deadline = Instant::now() + limit;
+                    hard_timeouts.push(HardTimeoutEntry {
+                        id,
+                        desc: desc.clone(),
+                        limit,
+                        deadline,
+                    });
+                }
+                if is_retry {
+                    // The failed attempt may still have an entry, which would
+                    // report this attempt as running for too long.
+                    timeout_queue.retain(|entry| entry.id != id);
+                }
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
             let mut res;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
+                if let Some(timeout) = calc_timeout(&timeout_queue, &hard_timeouts) {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
                         let event = TestEvent::TeTimeout(test);
@@ -475,6 +690,11 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
                     match res {
                         Err(RecvTimeoutError::Timeout) => {
+                            let now = Instant::now();
+                            if hard_timeouts.iter().any(|entry| entry.deadline <= now) {
+                                // Some test has to be abandoned, stop the loop.
+                                break;
+                            }
                             // Result is not yet ready, continue waiting.
                         }
                         _ => {
@@ -488,23 +708,48 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                 }
             }
 
-            let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
-            running_test.join(&mut completed_test);
-
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+            let completed_tests = match res {
+                Ok(mut completed_test) => {
+                    // Results may still arrive from tests that were abandoned
+                    // after timing out; those have already been reported.
+                    let Some(running_test) = running_tests.remove(&completed_test.id) else {
+                        continue;
+                    };
+                    running_test.join(&mut completed_test);
+                    hard_timeouts.retain(|entry| entry.id != completed_test.id);
+                    vec![completed_test]
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    abandoned_tests = true;
+                    get_hard_timed_out_tests(&mut running_tests, &mut hard_timeouts)
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    unreachable!("`run_tests` holds a sender for the whole run")
+                }
             };
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
-            pending -= 1;
+            for mut completed_test in completed_tests {
+                pending -= 1;
+                if let Some(test) =
+                    check_retry(opts, run_strategy, &mut retries, &mut completed_test)
+                {
+                    remaining.push_front((completed_test.id, test));
+                    continue;
+                }
 
-            if fail_fast {
-                // Prevent remaining test threads from panicking
-                std::mem::forget(rx);
-                return Ok(());
+                let fail_fast = match completed_test.result {
+                    TrIgnored | TrOk | TrBench(_) | TrFlaky(_) => false,
+                    TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimeout(_) => opts.fail_fast,
+                };
+
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+
+                if fail_fast {
+                    // Prevent remaining test threads from panicking
+                    std::mem::forget(rx);
+                    return Ok(());
+                }
             }
         }
     }
@@ -523,6 +768,11 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
             notify_about_test_event(event)?;
         }
     }
+
+    if abandoned_tests {
+        // Prevent abandoned test threads from panicking if they ever finish
+        std::mem::forget(rx);
+    }
     Ok(())
 }
 
@@ -557,6 +807,25 @@ pub fn filter_tests(opts: &TestOpts, tests: TestList) -> Vec<TestDescAndFn> {
         }
     }
 
+    // Remove tests that aren't defined in one of the requested files
+    if !opts.filter_files.is_empty() {
+        filtered.retain(|test| {
+            opts.filter_files.iter().any(|file| source_file_matches(test.desc.source_file, file))
+        });
+    }
+
+    // Remove tests that aren't at one of the requested source locations
+    if !opts.filter_lines.is_empty() {
+        let mut keep = vec![false; filtered.len()];
+        for (file, line) in &opts.filter_lines {
+            for i in tests_at_line(&filtered, file, *line) {
+                keep[i] = true;
+            }
+        }
+        let mut keep = keep.into_iter();
+        filtered.retain(|_| keep.next() == Some(true));
+    }
+
     // Skip tests that match any of the skip filters
     //
     // After exact positive filtering above, the filtered set is small, so a
@@ -587,9 +856,49 @@ pub fn filter_tests(opts: &TestOpts, tests: TestList) -> Vec<TestDescAndFn> {
         RunIgnored::No => {}
     }
 
+    // Keep only the tests of the requested shard
+    if let (Some(shard_index), Some(shard_count)) = (opts.shard_index, opts.shard_count) {
+        let mut shards = get_test_shards(&filtered, shard_count, opts.shuffle_seed).into_iter();
+        filtered.retain(|_| shards.next() == Some(shard_index));
+    }
+
     filtered
 }
 
+/// Whether `source_file`, the path of a file as given to rustc, refers to
+/// the file `filter`. Either may be a suffix of the other, as long as it
+/// starts at a path component, so that `src/lib.rs` matches
+/// `crates/foo/src/lib.rs` and vice versa.
+fn source_file_matches(source_file: &str, filter: &str) -> bool {
+    let source_file = source_file.replace('\\', "/");
+    let filter = filter.replace('\\', "/");
+    let filter = filter.strip_prefix("./").unwrap_or(&filter);
+    let (longer, shorter) = if source_file.len() >= filter.len() {
+        (&*source_file, filter)
+    } else {
+        (filter, &*source_file)
+    };
+    !shorter.is_empty()
+        && longer.ends_with(shorter)
+        && (longer.len() == shorter.len()
+            || longer.as_bytes()[longer.len() - shorter.len() - 1] == b'/')
+}
+
+/// Returns the indices of the tests in `file` that contain `line`, from the
+/// line of their name to the end of their body.
+fn tests_at_line(tests: &[TestDescAndFn], file: &str, line: usize) -> Vec<usize> {
+    tests
+        .iter()
+        .enumerate()
+        .filter(|(_, test)| {
+            source_file_matches(test.desc.source_file, file)
+                && test.desc.start_line <= line
+                && line <= test.desc.body_end_line.max(test.desc.end_line)
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
 /// Extract tests whose names exactly match one of the given `filters`, using
 /// binary search on the (assumed sorted) test list.
 fn filter_exact_match(mut tests: Vec<TestDescAndFn>, filters: &[String]) -> Vec<TestDescAndFn> {
@@ -662,7 +971,9 @@ pub fn run_test(
             let name = desc.name.clone();
             let nocapture = opts.nocapture;
             let time_options = opts.time_options;
+            let hard_timeout = time::get_hard_timeout(&desc, opts.test_timeout);
             let bench_benchmarks = opts.bench_benchmarks;
+            let panic_abort = opts.options.panic_abort;
 
             let runtest = move || match strategy {
                 RunStrategy::InProcess => run_test_in_process(
@@ -681,7 +992,9 @@ pub fn run_test(
                     time_options.is_some(),
                     monitor_ch,
                     time_options,
+                    hard_timeout,
                     bench_benchmarks,
+                    panic_abort,
                 ),
             };
 
@@ -782,7 +1095,9 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    hard_timeout: Option<Duration>,
     bench_benchmarks: bool,
+    panic_abort: bool,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -799,7 +1114,11 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match hard_timeout {
+            Some(limit) => output_with_timeout(&mut command, nocapture, limit),
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -816,8 +1135,16 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
-        let result =
-            get_result_from_exit_code(&desc, status, time_opts.as_ref(), exec_time.as_ref());
+        let result = match hard_timeout {
+            Some(limit) if timed_out => TrTimeout(limit),
+            _ => get_result_from_exit_code(
+                &desc,
+                status,
+                panic_abort,
+                time_opts.as_ref(),
+                exec_time.as_ref(),
+            ),
+        };
         (result, test_output, exec_time)
     })();
 
@@ -825,6 +1152,74 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Like `Command::output`, but kills the child if it is still running once
+/// `limit` has elapsed. Returns whether the child had to be killed.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    limit: Duration,
+) -> io::Result<(process::Output, bool)> {
+    // `Command::output` captures both streams by default, `spawn` does not.
+    command.stdin(process::Stdio::null());
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+
+    let deadline = Instant::now() + limit;
+    let mut child = command.spawn()?;
+
+    // Drain the pipes on separate threads so the child can't block on a full pipe.
+    fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    }
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            // The child may have exited in the meantime, in which case
+            // killing it fails, but `wait` still reaps it.
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep((deadline - now).min(Duration::from_millis(10)));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
+/// Runs a test in a process of its own when panics unwind, see `--isolate`.
+/// Panics are caught as they would be in-process, so that the parent can
+/// tell a failed test apart from one that crashed the process.
+fn run_test_in_isolated_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
+    let result = fold_err(catch_unwind(AssertUnwindSafe(|| runnable_test.run())));
+    let test_result = calc_result(&desc, result.err().as_deref(), None, None);
+
+    // As with panic=abort, print the message out to stderr.
+    if let TrFailedMsg(msg) = &test_result {
+        eprintln!("{msg}");
+    }
+
+    if let TrOk = test_result {
+        process::exit(test_result::TR_OK);
+    } else {
+        process::exit(test_result::TR_FAILED);
+    }
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicHookInfo<'_>>| {
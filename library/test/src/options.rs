@@ -36,6 +36,8 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP version 14 output
+    Tap,
 }
 
 /// Whether ignored test should be run or not
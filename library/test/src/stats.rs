@@ -300,3 +300,74 @@ pub fn winsorize(samples: &mut [f64], pct: f64) {
         }
     }
 }
+
+/// Welch's t-test for the difference between the means of two sample sets,
+/// given their summaries and sizes. Returns the two-tailed p-value, i.e. the
+/// probability of seeing a difference at least this large if both sample sets
+/// were drawn from distributions with the same mean.
+///
+/// See: <https://en.wikipedia.org/wiki/Welch%27s_t-test>
+pub(crate) fn welch_t_test(a: &Summary, a_len: usize, b: &Summary, b_len: usize) -> f64 {
+    if a_len < 2 || b_len < 2 {
+        return 1.0;
+    }
+    let (a_len, b_len) = (a_len as f64, b_len as f64);
+    let a_var = a.var / a_len;
+    let b_var = b.var / b_len;
+    let std_err = (a_var + b_var).sqrt();
+    if std_err == 0.0 {
+        // Without any variance, any difference at all is significant.
+        return if a.mean == b.mean { 1.0 } else { 0.0 };
+    }
+    let t = (a.mean - b.mean) / std_err;
+    let df =
+        (a_var + b_var).powi(2) / (a_var.powi(2) / (a_len - 1.0) + b_var.powi(2) / (b_len - 1.0));
+    // The two-tailed tail probability of Student's t-distribution.
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+// Regularized incomplete beta function I_x(a, b), evaluated with a continued
+// fraction as described in Numerical Recipes, 6.4.
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let ln_beta = a.ln_gamma().0 + b.ln_gamma().0 - (a + b).ln_gamma().0;
+    let front = (a * x.ln() + b * (1.0 - x).ln() - ln_beta).exp();
+    // The continued fraction converges quickly only on this side of the mean.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let clamp = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        h *= d * c;
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
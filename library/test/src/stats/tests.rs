@@ -572,6 +572,21 @@ fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
 
+#[test]
+fn test_welch_t_test() {
+    // Example from <https://en.wikipedia.org/wiki/Welch%27s_t-test>: t = -2.46,
+    // with 24.9 degrees of freedom, p = 0.021.
+    let a =
+        [27.5, 21.0, 19.0, 23.6, 17.0, 17.9, 16.9, 20.1, 21.9, 22.6, 23.1, 19.6, 19.0, 21.7, 21.4];
+    let b =
+        [27.1, 22.0, 20.8, 23.4, 23.4, 23.5, 25.8, 22.0, 24.8, 20.2, 21.9, 22.1, 22.9, 20.5, 24.4];
+    let (a, b) = (Summary::new(&a), Summary::new(&b));
+    let p = welch_t_test(&a, 15, &b, 15);
+    assert!((p - 0.021).abs() < 0.001, "{p} is not approximately 0.021");
+    assert_approx_eq!(welch_t_test(&b, 15, &a, 15), p);
+    assert_approx_eq!(welch_t_test(&a, 15, &a, 15), 1.0);
+}
+
 #[bench]
 fn sum_three_items(b: &mut Bencher) {
     b.iter(|| {
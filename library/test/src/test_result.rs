@@ -2,6 +2,7 @@
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 use std::process::ExitStatus;
+use std::time::Duration;
 
 pub use self::TestResult::*;
 use super::bench::BenchSamples;
@@ -14,6 +15,10 @@
 // it means.
 pub(crate) const TR_OK: i32 = 50;
 
+// Return code for a failed test in a secondary process that catches panics,
+// see `--isolate`.
+pub(crate) const TR_FAILED: i32 = 51;
+
 // On Windows we use __fastfail to abort, which is documented to use this
 // exception code.
 #[cfg(windows)]
@@ -35,6 +40,10 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test exceeded its hard time limit and was killed or abandoned.
+    TrTimeout(Duration),
+    /// The test passed after failing the given number of times, see `--retry`.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
@@ -104,27 +113,37 @@ pub(crate) fn calc_result(
 }
 
 /// Creates a `TestResult` depending on the exit code of test subprocess.
+///
+/// `panic_abort` tells whether the subprocess aborts when the test fails; if
+/// it doesn't, an abort is reported as a crash like any other signal.
 pub(crate) fn get_result_from_exit_code(
     desc: &TestDesc,
     status: ExitStatus,
+    #[cfg_attr(not(any(unix, windows)), allow(unused_variables))] panic_abort: bool,
     time_opts: Option<&time::TestTimeOptions>,
     exec_time: Option<&time::TestExecTime>,
 ) -> TestResult {
     let result = match status.code() {
         Some(TR_OK) => TestResult::TrOk,
+        Some(TR_FAILED) => TestResult::TrFailed,
         #[cfg(windows)]
-        Some(STATUS_FAIL_FAST_EXCEPTION) => TestResult::TrFailed,
+        Some(STATUS_FAIL_FAST_EXCEPTION) if panic_abort => TestResult::TrFailed,
         #[cfg(unix)]
         None => match status.signal() {
-            Some(libc::SIGABRT) => TestResult::TrFailed,
-            Some(signal) => {
-                TestResult::TrFailedMsg(format!("child process exited with signal {signal}"))
-            }
+            Some(libc::SIGABRT) if panic_abort => TestResult::TrFailed,
+            Some(signal) => match signal_name(signal) {
+                Some(name) => TestResult::TrFailedMsg(format!(
+                    "child process exited with signal {signal} ({name})"
+                )),
+                None => {
+                    TestResult::TrFailedMsg(format!("child process exited with signal {signal}"))
+                }
+            },
             None => unreachable!("status.code() returned None but status.signal() was None"),
         },
         // Upon an abort, Fuchsia returns the status code ZX_TASK_RETCODE_EXCEPTION_KILL.
         #[cfg(target_os = "fuchsia")]
-        Some(ZX_TASK_RETCODE_EXCEPTION_KILL) => TestResult::TrFailed,
+        Some(ZX_TASK_RETCODE_EXCEPTION_KILL) if panic_abort => TestResult::TrFailed,
         #[cfg(not(unix))]
         None => TestResult::TrFailedMsg(format!("unknown return code")),
         #[cfg(any(windows, unix))]
@@ -147,3 +166,20 @@ pub(crate) fn get_result_from_exit_code(
 
     result
 }
+
+/// Returns the name of the signals that usually end a crashing process.
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    let name = match signal {
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGILL => "SIGILL",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGTERM => "SIGTERM",
+        libc::SIGTRAP => "SIGTRAP",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -1,7 +1,8 @@
 use super::*;
 use crate::{
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{OutputFormatter, PrettyFormatter, TapFormatter},
+    helpers::baseline::Baseline,
     test::{
         MetricMap,
         // FIXME (introduced by #65251)
@@ -9,7 +10,7 @@
         // TestType, TrFailedMsg, TrIgnored, TrOk,
         parse_opts,
     },
-    time::{TestTimeOptions, TimeThreshold},
+    time::{TestTimeOptions, TimeThreshold, parse_duration},
 };
 
 impl TestOpts {
@@ -18,7 +19,10 @@ fn new() -> TestOpts {
             list: false,
             filters: vec![],
             filter_exact: false,
+            filter_files: vec![],
+            filter_lines: vec![],
             force_run_in_process: false,
+            isolate: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
             run_tests: false,
@@ -29,9 +33,15 @@ fn new() -> TestOpts {
             format: OutputFormat::Pretty,
             shuffle: false,
             shuffle_seed: None,
+            shard_index: None,
+            shard_count: None,
             test_threads: None,
             skip: vec![],
             time_options: None,
+            test_timeout: None,
+            retry: 0,
+            save_baseline: None,
+            baseline: None,
             options: Options::new(),
             fail_fast: false,
         }
@@ -50,10 +60,12 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 start_col: 0,
                 end_line: 0,
                 end_col: 0,
+                body_end_line: 0,
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
         },
@@ -67,10 +79,12 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 start_col: 0,
                 end_line: 0,
                 end_col: 0,
+                body_end_line: 0,
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
         },
@@ -92,10 +106,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -120,10 +136,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -149,10 +167,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::Yes,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -178,10 +198,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::YesWithMessage("error message"),
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -212,10 +234,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -251,10 +275,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -283,10 +309,12 @@ fn f() -> Result<(), String> {
                 start_col: 0,
                 end_line: 0,
                 end_col: 0,
+                body_end_line: 0,
                 should_panic,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(f)),
         };
@@ -316,10 +344,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -358,10 +388,12 @@ fn f() -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
             test_type,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -402,10 +434,12 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         start_col: 0,
         end_line: 0,
         end_col: 0,
+        body_end_line: 0,
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
         test_type,
+        timeout: None,
     }
 }
 
@@ -447,6 +481,312 @@ fn test_time_options_threshold() {
     }
 }
 
+fn hard_timeout_test_template(test_threads: usize) -> Vec<CompletedTest> {
+    fn hangs() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+    fn passes() -> Result<(), String> {
+        Ok(())
+    }
+    let desc = |name, timeout| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        source_file: "",
+        start_line: 0,
+        start_col: 0,
+        end_line: 0,
+        end_col: 0,
+        body_end_line: 0,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+        timeout,
+    };
+    let tests = vec![
+        TestDescAndFn {
+            desc: desc("hangs", Some(Duration::from_millis(100))),
+            testfn: DynTestFn(Box::new(hangs)),
+        },
+        TestDescAndFn { desc: desc("passes", None), testfn: DynTestFn(Box::new(passes)) },
+    ];
+    let test_opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        test_timeout: Some(Duration::from_secs(60)),
+        ..TestOpts::new()
+    };
+
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(result) = event {
+            results.push(result);
+        }
+        Ok(())
+    };
+    let tests = TestList::new(tests, TestListOrder::Unsorted);
+    run_tests(&test_opts, tests, notify).unwrap();
+    results.sort_by(|a, b| a.desc.name.as_slice().cmp(b.desc.name.as_slice()));
+    results
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_hard_timeout_abandons_hung_test() {
+    for test_threads in [1, 2] {
+        let results = hard_timeout_test_template(test_threads);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].result, TrTimeout(Duration::from_millis(100)));
+        assert_eq!(results[1].result, TrOk);
+    }
+}
+
+#[test]
+fn test_retry_reports_flaky_tests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static FAILING_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn flaky() -> Result<(), String> {
+        if FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) < 2 { Err("flaked".into()) } else { Ok(()) }
+    }
+    fn failing() -> Result<(), String> {
+        FAILING_RUNS.fetch_add(1, Ordering::SeqCst);
+        Err("always fails".into())
+    }
+    let desc = |name| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        source_file: "",
+        start_line: 0,
+        start_col: 0,
+        end_line: 0,
+        end_col: 0,
+        body_end_line: 0,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+
+    for test_threads in [1, 2] {
+        FLAKY_RUNS.store(0, Ordering::SeqCst);
+        FAILING_RUNS.store(0, Ordering::SeqCst);
+        let tests = vec![
+            TestDescAndFn { desc: desc("failing"), testfn: StaticTestFn(failing) },
+            TestDescAndFn { desc: desc("flaky"), testfn: StaticTestFn(flaky) },
+        ];
+        let test_opts = TestOpts {
+            run_tests: true,
+            test_threads: Some(test_threads),
+            retry: 3,
+            ..TestOpts::new()
+        };
+
+        let mut results = Vec::new();
+        let notify = |event: TestEvent| {
+            if let TestEvent::TeResult(result) = event {
+                results.push(result);
+            }
+            Ok(())
+        };
+        let tests = TestList::new(tests, TestListOrder::Unsorted);
+        run_tests(&test_opts, tests, notify).unwrap();
+        results.sort_by(|a, b| a.desc.name.as_slice().cmp(b.desc.name.as_slice()));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].result, TrFailed);
+        assert_eq!(FAILING_RUNS.load(Ordering::SeqCst), 4);
+        assert_eq!(results[1].result, TrFlaky(2));
+        assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+    }
+}
+
+#[test]
+fn test_retry_notes_dynamic_tests() {
+    let desc = TestDesc {
+        name: StaticTestName("dynamic"),
+        ignore: false,
+        ignore_message: None,
+        source_file: "",
+        start_line: 0,
+        start_col: 0,
+        end_line: 0,
+        end_col: 0,
+        body_end_line: 0,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+    let tests =
+        vec![TestDescAndFn { desc, testfn: DynTestFn(Box::new(|| Err("always fails".into()))) }];
+    let test_opts = TestOpts { run_tests: true, retry: 3, ..TestOpts::new() };
+
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(result) = event {
+            results.push(result);
+        }
+        Ok(())
+    };
+    let tests = TestList::new(tests, TestListOrder::Unsorted);
+    run_tests(&test_opts, tests, notify).unwrap();
+
+    // A `FnOnce` test can only run once, so it fails without being retried.
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].result, TrFailed);
+    let stdout = String::from_utf8_lossy(&results[0].stdout);
+    assert!(stdout.contains("note: not retried"), "{stdout}");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_result_from_exit_code() {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use crate::test_result::{TR_FAILED, TR_OK, get_result_from_exit_code};
+
+    let desc = TestDesc {
+        name: StaticTestName("crashes"),
+        ignore: false,
+        ignore_message: None,
+        source_file: "",
+        start_line: 0,
+        start_col: 0,
+        end_line: 0,
+        end_col: 0,
+        body_end_line: 0,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+    let result = |raw, panic_abort| {
+        get_result_from_exit_code(&desc, ExitStatus::from_raw(raw), panic_abort, None, None)
+    };
+
+    assert_eq!(result(TR_OK << 8, false), TrOk);
+    assert_eq!(result(TR_FAILED << 8, false), TrFailed);
+    assert_eq!(result(3 << 8, false), TrFailedMsg("got unexpected return code 3".to_string()));
+    // An abort is how a failed test ends with panic=abort, but a crash otherwise.
+    assert_eq!(result(libc::SIGABRT, true), TrFailed);
+    assert_eq!(
+        result(libc::SIGABRT, false),
+        TrFailedMsg(format!("child process exited with signal {} (SIGABRT)", libc::SIGABRT))
+    );
+    assert_eq!(
+        result(libc::SIGSEGV, true),
+        TrFailedMsg(format!("child process exited with signal {} (SIGSEGV)", libc::SIGSEGV))
+    );
+}
+
+#[test]
+fn test_bench_baseline() {
+    use crate::helpers::baseline::Verdict;
+    use crate::stats::Summary;
+
+    let slow = Summary::new(&[100.0, 101.0, 99.0, 100.5, 99.5, 100.0, 102.0, 98.0]);
+    let fast = Summary::new(&[90.0, 91.0, 89.0, 90.5, 89.5, 90.0, 92.0, 88.0]);
+
+    let mut baseline = Baseline::new("main");
+    baseline.insert("bench \"slow\"", slow);
+    let json = baseline.to_json();
+    assert_eq!(Baseline::from_json("main", &json), Some(baseline.clone()));
+    assert_eq!(Baseline::from_json("main", &json[1..]), None);
+
+    let change = baseline.compare("bench \"slow\"", &fast).unwrap();
+    assert_eq!(change.baseline_median, 100.0);
+    assert_eq!(change.change_pct, -10.0);
+    assert_eq!(change.verdict(), Verdict::Improved);
+    let change = baseline.compare("bench \"slow\"", &slow).unwrap();
+    assert_eq!(change.change_pct, 0.0);
+    assert_eq!(change.verdict(), Verdict::NoChange);
+    assert_eq!(baseline.compare("other", &slow), None);
+}
+
+#[test]
+fn filter_tests_by_source_location() {
+    fn tests() -> TestList {
+        let tests = [
+            ("a", "src/lib.rs", 10),
+            ("b", "src/lib.rs", 20),
+            ("c", "src/lib.rs", 20),
+            ("d", "src/other.rs", 5),
+            ("e", "src/nested/lib.rs", 15),
+        ]
+        .into_iter()
+        .map(|(name, source_file, line)| TestDescAndFn {
+            desc: TestDesc {
+                name: StaticTestName(name),
+                ignore: false,
+                ignore_message: None,
+                source_file,
+                start_line: line,
+                start_col: 4,
+                end_line: line,
+                end_col: 10,
+                body_end_line: line + 5,
+                should_panic: ShouldPanic::No,
+                compile_fail: false,
+                no_run: false,
+                test_type: TestType::UnitTest,
+                timeout: None,
+            },
+            testfn: DynTestFn(Box::new(move || Ok(()))),
+        })
+        .collect();
+        TestList::new(tests, TestListOrder::Sorted)
+    }
+    let names = |tests: Vec<TestDescAndFn>| {
+        tests.iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>()
+    };
+
+    let by_file = |files: &[&str]| {
+        let filter_files = files.iter().map(|file| file.to_string()).collect();
+        names(filter_tests(&TestOpts { filter_files, ..TestOpts::new() }, tests()))
+    };
+    assert_eq!(by_file(&["src/lib.rs"]), ["a", "b", "c"]);
+    assert_eq!(by_file(&["./src/lib.rs"]), ["a", "b", "c"]);
+    assert_eq!(by_file(&["/home/user/crate/src/lib.rs"]), ["a", "b", "c"]);
+    assert_eq!(by_file(&["lib.rs"]), ["a", "b", "c", "e"]);
+    assert_eq!(by_file(&["b.rs"]), Vec::<String>::new());
+    assert_eq!(by_file(&["src/lib.rs", "src\\other.rs"]), ["a", "b", "c", "d"]);
+
+    let by_line = |locations: &[(&str, usize)]| {
+        let filter_lines = locations.iter().map(|&(file, line)| (file.to_string(), line)).collect();
+        names(filter_tests(&TestOpts { filter_lines, ..TestOpts::new() }, tests()))
+    };
+    assert_eq!(by_line(&[("src/lib.rs", 10)]), ["a"]);
+    assert_eq!(by_line(&[("src/lib.rs", 15)]), ["a"]);
+    assert_eq!(by_line(&[("src/lib.rs", 25)]), ["b", "c"]);
+    assert_eq!(by_line(&[("src/lib.rs", 5)]), Vec::<String>::new());
+    // A line between two tests, e.g. in a helper function, selects nothing.
+    assert_eq!(by_line(&[("src/lib.rs", 17)]), Vec::<String>::new());
+    assert_eq!(by_line(&[("src/lib.rs", 26)]), Vec::<String>::new());
+    assert_eq!(by_line(&[("src/lib.rs", 12), ("src/other.rs", 7)]), ["a", "d"]);
+    assert_eq!(by_line(&[("src/other.rs", 100)]), Vec::<String>::new());
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("30"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+    assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+    assert_eq!(parse_duration("1h"), None);
+    assert_eq!(parse_duration("s"), None);
+    assert_eq!(parse_duration("-1"), None);
+}
+
 #[test]
 fn parse_ignored_flag() {
     let args = vec!["progname".to_string(), "filter".to_string(), "--ignored".to_string()];
@@ -519,10 +859,12 @@ fn exclude_should_panic_option() {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::Yes,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(move || Ok(()))),
     });
@@ -548,10 +890,12 @@ fn tests() -> TestList {
                     start_col: 0,
                     end_line: 0,
                     end_col: 0,
+                    body_end_line: 0,
                     should_panic: ShouldPanic::No,
                     compile_fail: false,
                     no_run: false,
                     test_type: TestType::Unknown,
+                    timeout: None,
                 },
                 testfn: DynTestFn(Box::new(move || Ok(()))),
             })
@@ -645,10 +989,12 @@ fn testfn() -> Result<(), String> {
                 start_col: 0,
                 end_line: 0,
                 end_col: 0,
+                body_end_line: 0,
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(testfn)),
         };
@@ -722,6 +1068,48 @@ fn order_depends_on_more_than_seed() {
     assert!(left.iter().zip(right).any(|(a, b)| a.0 != b.0));
 }
 
+#[test]
+fn shard_tests() {
+    let shard = |shard_index, shuffle_seed| {
+        let opts = TestOpts {
+            shard_index: Some(shard_index),
+            shard_count: Some(3),
+            shuffle_seed,
+            ..TestOpts::new()
+        };
+        let tests = TestList::new(sample_tests(), TestListOrder::Unsorted);
+        filter_tests(&opts, tests).into_iter().map(|t| t.desc.name.to_string()).collect::<Vec<_>>()
+    };
+
+    for shuffle_seed in [None, Some(7)] {
+        let shards: Vec<_> = (0..3).map(|i| shard(i, shuffle_seed)).collect();
+
+        // Every test is in exactly one shard, and the shards are balanced.
+        let mut all: Vec<_> = shards.concat();
+        all.sort();
+        let mut expected: Vec<_> =
+            sample_tests().into_iter().map(|t| t.desc.name.to_string()).collect();
+        expected.sort();
+        assert_eq!(all, expected);
+        assert!(shards.iter().all(|s| s.len() == 3 || s.len() == 4));
+
+        // The assignment doesn't depend on the order the tests are passed in.
+        let mut reversed = sample_tests();
+        reversed.reverse();
+        let opts = TestOpts {
+            shard_index: Some(1),
+            shard_count: Some(3),
+            shuffle_seed,
+            ..TestOpts::new()
+        };
+        let tests = TestList::new(reversed, TestListOrder::Unsorted);
+        let mut shard1: Vec<_> =
+            filter_tests(&opts, tests).into_iter().map(|t| t.desc.name.to_string()).collect();
+        shard1.reverse();
+        assert_eq!(shard1, shards[1]);
+    }
+}
+
 #[test]
 fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -779,10 +1167,12 @@ fn f(_: &mut Bencher) -> Result<(), String> {
         start_col: 0,
         end_line: 0,
         end_col: 0,
+        body_end_line: 0,
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     crate::bench::benchmark(TestId(0), desc, tx, true, f);
@@ -807,10 +1197,12 @@ fn f(b: &mut Bencher) -> Result<(), String> {
         start_col: 0,
         end_line: 0,
         end_col: 0,
+        body_end_line: 0,
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     crate::bench::benchmark(TestId(0), desc, tx, true, f);
@@ -828,10 +1220,12 @@ fn should_sort_failures_before_printing_them() {
         start_col: 0,
         end_line: 0,
         end_col: 0,
+        body_end_line: 0,
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     let test_b = TestDesc {
@@ -843,10 +1237,12 @@ fn should_sort_failures_before_printing_them() {
         start_col: 0,
         end_line: 0,
         end_col: 0,
+        body_end_line: 0,
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
@@ -856,6 +1252,7 @@ fn should_sort_failures_before_printing_them() {
         total: 0,
         passed: 0,
         failed: 0,
+        flaky: 0,
         ignored: 0,
         filtered_out: 0,
         measured: 0,
@@ -866,6 +1263,9 @@ fn should_sort_failures_before_printing_them() {
         not_failures: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        baseline: None,
+        bench_results: Baseline::default(),
     };
 
     out.write_failures(&st).unwrap();
@@ -879,6 +1279,91 @@ fn should_sort_failures_before_printing_them() {
     assert!(apos < bpos);
 }
 
+#[test]
+fn tap_output() {
+    let desc = |name, test_type, ignore_message| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message,
+        source_file: "",
+        start_line: 0,
+        start_col: 0,
+        end_line: 0,
+        end_col: 0,
+        body_end_line: 0,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type,
+        timeout: None,
+    };
+    let st = console::ConsoleTestState {
+        log_out: None,
+        total: 5,
+        passed: 2,
+        failed: 2,
+        flaky: 0,
+        ignored: 1,
+        filtered_out: 0,
+        measured: 0,
+        exec_time: None,
+        metrics: MetricMap::new(),
+        failures: Vec::new(),
+        options: Options::new(),
+        not_failures: Vec::new(),
+        ignores: Vec::new(),
+        time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        baseline: None,
+        bench_results: Baseline::default(),
+    };
+
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(5, None).unwrap();
+    let results = [
+        (desc("a", TestType::UnitTest, None), TrOk, ""),
+        (
+            desc("b", TestType::UnitTest, None),
+            TrFailed,
+            "\nthread 'b' panicked at src/lib.rs:1:1:\nboom\nnote: run with `RUST_BACKTRACE=1`\n",
+        ),
+        (desc("c", TestType::UnitTest, Some("slow #1")), TrIgnored, ""),
+        (desc("src/lib.rs - f (line 3)", TestType::DocTest, None), TrFailedMsg("oops".into()), ""),
+        (desc("src/lib.rs - e (line 1)", TestType::DocTest, None), TrOk, ""),
+    ];
+    for (desc, result, stdout) in &results {
+        out.write_result(desc, result, None, stdout.as_bytes(), &st).unwrap();
+    }
+    out.write_run_finish(&st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let expected = r#"TAP version 14
+ok 1 - a
+not ok 2 - b
+  ---
+  message: "boom"
+  severity: fail
+  stdout: "\nthread 'b' panicked at src/lib.rs:1:1:\nboom\nnote: run with `RUST_BACKTRACE=1`\n"
+  ...
+ok 3 - c # SKIP slow \#1
+# Subtest: src/lib.rs
+    ok 1 - e (line 1)
+    not ok 2 - f (line 3)
+      ---
+      message: "oops"
+      severity: fail
+      ...
+    1..2
+not ok 4 - src/lib.rs
+1..4
+# 2 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out
+"#;
+    assert_eq!(s, expected);
+}
+
 #[test]
 #[cfg(not(target_os = "emscripten"))]
 fn test_dyn_bench_returning_err_fails_when_run_as_test() {
@@ -895,10 +1380,12 @@ fn f(_: &mut Bencher) -> Result<(), String> {
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            body_end_line: 0,
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynBenchFn(Box::new(f)),
     };
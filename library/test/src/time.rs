@@ -2,6 +2,7 @@
 //! execution.
 //! The purposes of this module:
 //! - Check whether test is timed out.
+//! - Determine the hard time limit after which a test is killed.
 //! - Provide helpers for `report-time` and `measure-time` options.
 //! - Provide newtypes for executions times.
 
@@ -61,6 +62,28 @@ pub(crate) fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Returns the hard time limit for the test: its own `timeout` if it has one,
+/// otherwise the limit passed with `--test-timeout`.
+pub(crate) fn get_hard_timeout(
+    desc: &TestDesc,
+    test_timeout: Option<Duration>,
+) -> Option<Duration> {
+    desc.timeout.or(test_timeout)
+}
+
+/// Parses a duration of the form `<number>[ms|s|m]`, defaulting to seconds.
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let digits_end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(digits_end);
+    let value = value.parse::<u64>().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(value)),
+        "" | "s" => Some(Duration::from_secs(value)),
+        "m" => Some(Duration::from_secs(value.checked_mul(60)?)),
+        _ => None,
+    }
+}
+
 /// The measured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::fmt;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 pub use NamePadding::*;
 pub use TestFn::*;
@@ -102,6 +103,15 @@ pub fn padding(&self) -> NamePadding {
         }
     }
 
+    /// Returns a copy of the test function, if it can be run more than once.
+    pub(crate) fn try_clone(&self) -> Option<TestFn> {
+        match *self {
+            StaticTestFn(f) => Some(StaticTestFn(f)),
+            StaticBenchAsTestFn(f) => Some(StaticBenchAsTestFn(f)),
+            _ => None,
+        }
+    }
+
     pub(crate) fn into_runnable(self) -> Runnable {
         match self {
             StaticTestFn(f) => Runnable::Test(RunnableTest::Static(f)),
@@ -203,10 +213,14 @@ pub struct TestDesc {
     pub start_col: usize,
     pub end_line: usize,
     pub end_col: usize,
+    /// Last line of the test, including its body. Used by `--filter-line`.
+    pub body_end_line: usize,
     pub should_panic: options::ShouldPanic,
     pub compile_fail: bool,
     pub no_run: bool,
     pub test_type: TestType,
+    /// Per-test hard time limit, overriding `--test-timeout`.
+    pub timeout: Option<Duration>,
 }
 
 impl TestDesc {
@@ -271,6 +285,7 @@ pub const fn new_doctest(
                 start_col: 0,
                 end_line: 0,
                 end_col: 0,
+                body_end_line: start_line,
                 compile_fail: false,
                 no_run,
                 should_panic: if should_panic {
@@ -279,6 +294,7 @@ pub const fn new_doctest(
                     options::ShouldPanic::No
                 },
                 test_type: TestType::DocTest,
+                timeout: None,
             },
             testfn,
         }
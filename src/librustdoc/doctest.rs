@@ -1131,11 +1131,15 @@ fn generate_test_desc_and_fn(
             start_col: 0,
             end_line: 0,
             end_col: 0,
+            #[cfg(not(bootstrap))]
+            body_end_line: 0,
             // compiler failures are test failures
             should_panic: test::ShouldPanic::No,
             compile_fail: scraped_test.langstr.compile_fail,
             no_run: scraped_test.no_run(&rustdoc_options),
             test_type: test::TestType::DocTest,
+            #[cfg(not(bootstrap))]
+            timeout: None,
         },
         testfn: test::DynTestFn(Box::new(move || {
             doctest_run_fn(
@@ -0,0 +1,52 @@
+//@compile-flags: -Zmiri-strict-provenance
+// Loads racing with stores must never see a freed `ArcInner`.
+#![feature(atomic_arc)]
+
+use std::sync::{Arc, AtomicArc, AtomicOptionArc};
+use std::thread;
+
+fn load_store() {
+    let atomic = AtomicArc::new(Arc::new(0));
+    thread::scope(|s| {
+        for _ in 0..3 {
+            s.spawn(|| {
+                for _ in 0..10 {
+                    assert!(*atomic.load() < 10);
+                }
+            });
+        }
+        s.spawn(|| {
+            for i in 1..10 {
+                atomic.store(Arc::new(i));
+                thread::yield_now();
+            }
+        });
+    });
+    assert_eq!(*atomic.into_inner(), 9);
+}
+
+fn swap_option() {
+    let atomic = AtomicOptionArc::new(Some(Arc::new(String::from("first"))));
+    thread::scope(|s| {
+        s.spawn(|| {
+            for _ in 0..10 {
+                if let Some(value) = atomic.load() {
+                    assert!(value.starts_with("first") || value.starts_with("second"));
+                }
+            }
+        });
+        s.spawn(|| {
+            let first = atomic.swap(None).unwrap();
+            thread::yield_now();
+            atomic.store(Some(Arc::new(String::from("second"))));
+            // Store the first `Arc` again while it may still be loaded.
+            let second = atomic.swap(Some(first.clone()));
+            assert!(atomic.compare_exchange(Some(&first), second).is_ok());
+        });
+    });
+}
+
+fn main() {
+    load_store();
+    swap_option();
+}
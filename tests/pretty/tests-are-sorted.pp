@@ -22,10 +22,12 @@ pub const m_test: test::TestDescAndFn =
             start_col: 4usize,
             end_line: 7usize,
             end_col: 10usize,
+            body_end_line: 7usize,
             compile_fail: false,
             no_run: false,
             should_panic: test::ShouldPanic::No,
             test_type: test::TestType::Unknown,
+            timeout: ::core::option::Option::None,
         },
         testfn: test::StaticTestFn(#[coverage(off)] ||
                 test::assert_test_result(m_test())),
@@ -46,10 +48,12 @@ pub const z_test: test::TestDescAndFn =
             start_col: 4usize,
             end_line: 11usize,
             end_col: 10usize,
+            body_end_line: 11usize,
             compile_fail: false,
             no_run: false,
             should_panic: test::ShouldPanic::No,
             test_type: test::TestType::Unknown,
+            timeout: ::core::option::Option::None,
         },
         testfn: test::StaticTestFn(#[coverage(off)] ||
                 test::assert_test_result(z_test())),
@@ -71,10 +75,12 @@ pub const a_test: test::TestDescAndFn =
             start_col: 4usize,
             end_line: 14usize,
             end_col: 10usize,
+            body_end_line: 14usize,
             compile_fail: false,
             no_run: false,
             should_panic: test::ShouldPanic::No,
             test_type: test::TestType::Unknown,
+            timeout: ::core::option::Option::None,
         },
         testfn: test::StaticTestFn(#[coverage(off)] ||
                 test::assert_test_result(a_test())),
@@ -0,0 +1,60 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test
+//@ run-flags: -Zunstable-options --isolate --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout: "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+//@ only-unix
+//@ needs-subprocess
+
+// Crashing tests are reported as failures of their own with `--isolate`, and
+// the tests after them still run.
+
+#![cfg(test)]
+
+unsafe extern "C" {
+    fn signal(signum: i32, handler: usize) -> usize;
+    fn raise(signum: i32) -> i32;
+}
+
+#[test]
+fn it_aborts() {
+    std::process::abort();
+}
+
+#[test]
+fn it_exits() {
+    std::process::exit(123);
+}
+
+#[test]
+fn it_fails() {
+    assert_eq!(1 + 1, 5);
+}
+
+#[test]
+fn it_segfaults() {
+    // std handles SIGSEGV to report stack overflows, so restore the default
+    // action first. SIGSEGV is 11 and SIG_DFL is 0 on every unix.
+    unsafe {
+        signal(11, 0);
+        raise(11);
+    }
+}
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+fn no_residual_environment() {
+    for (key, _) in std::env::vars() {
+        // Look for keys like __RUST_TEST_INVOKE.
+        if key.contains("TEST_INVOKE") {
+            panic!("shouldn't have '{}' in environment", key);
+        }
+    }
+}
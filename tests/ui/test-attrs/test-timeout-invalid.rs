@@ -0,0 +1,11 @@
+//@ compile-flags: --test
+
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout = "soon"] //~ ERROR invalid test timeout `soon`
+fn unknown_unit() {}
+
+#[test]
+#[test_timeout = "0s"] //~ ERROR invalid test timeout `0s`
+fn zero() {}
@@ -0,0 +1,31 @@
+//! Checks that `#[test_timeout]` overrides `--test-timeout` for a single test,
+//! and that a test running past its limit is killed and reported as failed.
+
+//@ no-prefer-dynamic
+//@ compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+//@ run-flags: --test-threads=1 -Zunstable-options --test-timeout=60s
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout: "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+//@ needs-subprocess
+
+#![cfg(test)]
+#![feature(test_timeout)]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+#[test_timeout = "100ms"]
+fn hangs() {
+    thread::sleep(Duration::from_secs(60));
+}
+
+#[test]
+#[test_timeout = "30s"]
+fn finishes() {}
+
+#[test]
+fn uses_default_timeout() {}